@@ -94,10 +94,11 @@ CREATE TABLE IF NOT EXISTS kanban_subtasks (
 
 CREATE TABLE IF NOT EXISTS kanban_activity (
   id TEXT PRIMARY KEY,
-  board_id TEXT NOT NULL REFERENCES kanban_boards(id) ON DELETE CASCADE,
-  card_id TEXT REFERENCES kanban_cards(id) ON DELETE SET NULL,
-  column_id TEXT REFERENCES kanban_columns(id) ON DELETE SET NULL,
-  action TEXT NOT NULL,
+  board_id TEXT NOT NULL,
+  entity_type TEXT NOT NULL,
+  entity_id TEXT NOT NULL,
+  activity_type TEXT NOT NULL,
+  title TEXT NOT NULL,
   meta TEXT,
   created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
 );
@@ -114,9 +115,17 @@ CREATE TABLE IF NOT EXISTS notes (
   board_id TEXT
 );
 
+CREATE TABLE IF NOT EXISTS deletions (
+  entity_type TEXT NOT NULL,
+  entity_id TEXT NOT NULL,
+  board_id TEXT NOT NULL,
+  deleted_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
 CREATE INDEX IF NOT EXISTS idx_columns_board_position ON kanban_columns(board_id, position);
 CREATE INDEX IF NOT EXISTS idx_cards_board_position ON kanban_cards(board_id, position);
 CREATE INDEX IF NOT EXISTS idx_cards_column_position ON kanban_cards(column_id, position);
 CREATE UNIQUE INDEX IF NOT EXISTS idx_subtasks_card_position ON kanban_subtasks(card_id, position);
 CREATE INDEX IF NOT EXISTS idx_subtasks_card ON kanban_subtasks(card_id);
 CREATE INDEX IF NOT EXISTS idx_activity_board_created ON kanban_activity(board_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_deletions_board_deleted ON deletions(board_id, deleted_at);
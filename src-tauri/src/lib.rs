@@ -1,28 +1,34 @@
 use anyhow::anyhow;
 use base64::{Engine as _, engine::general_purpose};
 use chrono::{DateTime, Utc};
+use futures_util::future::join4;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
 use sha2::{Digest, Sha256};
 use sqlx::{
     Acquire, QueryBuilder, Row, Sqlite, Transaction,
-    sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqlitePoolOptions, SqliteRow},
+    sqlite::{
+        SqliteConnectOptions, SqliteConnection, SqliteJournalMode, SqlitePool, SqlitePoolOptions,
+        SqliteRow,
+    },
 };
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::convert::TryInto;
 use std::fs::{self, File};
 use std::io::{self};
 use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tauri::menu::{MenuBuilder, MenuItemBuilder, PredefinedMenuItem, SubmenuBuilder};
-use tauri::{AppHandle, Emitter, Manager, State};
+use tauri::{AppHandle, Emitter, Manager, Runtime, State};
 use tauri_plugin_opener::OpenerExt;
 use tokio::time as tokio_time;
 use uuid::Uuid;
 use zip::{CompressionMethod, ZipArchive, ZipWriter, write::FileOptions};
 
 const KANBAN_SCHEMA: &str = include_str!("../schema/kanban.sql");
+/// Stored in `PRAGMA user_version`; bump whenever `initialize_schema` gains a migration.
+const SCHEMA_VERSION: i64 = 10;
 const DATABASE_FILE: &str = "modulo.db";
 const DEFAULT_BOARD_ICON: &str = "Folder";
 const DEFAULT_WORKSPACE_ID: &str = "workspace-default";
@@ -94,7 +100,73 @@ const ALLOWED_COLUMN_ICONS: &[&str] = &[
 
 type DbPool = SqlitePool;
 
-async fn establish_pool(app: &AppHandle) -> Result<DbPool, String> {
+/// Error returned by card and column commands. Serializes to `{ code, message }`
+/// so the frontend can branch on `code` instead of matching message text.
+#[derive(Debug)]
+enum CommandError {
+    NotFound(String),
+    Validation(String),
+    Conflict(String),
+    Database(String),
+    Io(String),
+}
+
+impl CommandError {
+    fn code(&self) -> &'static str {
+        match self {
+            CommandError::NotFound(_) => "not_found",
+            CommandError::Validation(_) => "validation",
+            CommandError::Conflict(_) => "conflict",
+            CommandError::Database(_) => "database",
+            CommandError::Io(_) => "io",
+        }
+    }
+}
+
+impl std::fmt::Display for CommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CommandError::NotFound(message)
+            | CommandError::Validation(message)
+            | CommandError::Conflict(message)
+            | CommandError::Database(message)
+            | CommandError::Io(message) => f.write_str(message),
+        }
+    }
+}
+
+impl Serialize for CommandError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("CommandError", 2)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
+
+impl From<sqlx::Error> for CommandError {
+    fn from(error: sqlx::Error) -> Self {
+        match &error {
+            sqlx::Error::RowNotFound => {
+                CommandError::NotFound("Registro não encontrado.".to_string())
+            }
+            sqlx::Error::Database(db_error) if db_error.is_unique_violation() => {
+                CommandError::Conflict(db_error.message().to_string())
+            }
+            _ => CommandError::Database(error.to_string()),
+        }
+    }
+}
+
+impl From<std::io::Error> for CommandError {
+    fn from(error: std::io::Error) -> Self {
+        CommandError::Io(error.to_string())
+    }
+}
+
+async fn establish_pool<R: Runtime>(app: &AppHandle<R>) -> Result<DbPool, String> {
     let app_data_dir = app
         .path()
         .app_data_dir()
@@ -123,8 +195,23 @@ async fn establish_pool(app: &AppHandle) -> Result<DbPool, String> {
     Ok(pool)
 }
 
+/// Tells every open window that `board_id` changed so other views of it can reload.
+/// Called only after a successful commit; emit failures are logged, not returned.
+fn emit_board_changed<R: Runtime>(app: &AppHandle<R>, board_id: &str, kind: &str) {
+    if let Err(e) = app.emit(
+        "board-changed",
+        json!({ "boardId": board_id, "kind": kind }),
+    ) {
+        log::warn!("Failed to emit board-changed event: {e}");
+    }
+}
+
 #[tauri::command]
-async fn create_subtask(pool: State<'_, DbPool>, args: CreateSubtaskArgs) -> Result<Value, String> {
+async fn create_subtask<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    args: CreateSubtaskArgs,
+) -> Result<Value, String> {
     let title = args.title.trim().to_string();
     if title.is_empty() {
         return Err("O título da subtask não pode ser vazio.".to_string());
@@ -204,9 +291,104 @@ async fn create_subtask(pool: State<'_, DbPool>, args: CreateSubtaskArgs) -> Res
         .await
         .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
 
+    emit_board_changed(&app, &args.board_id, "subtask_created");
+
     Ok(mapped)
 }
 
+#[tauri::command]
+async fn create_subtasks<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    board_id: String,
+    card_id: String,
+    titles: Vec<String>,
+) -> Result<Vec<Value>, String> {
+    let mut cleaned = Vec::with_capacity(titles.len());
+    for title in &titles {
+        let trimmed = title.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        validate_string_input(trimmed, 200, "Título da subtask")?;
+        cleaned.push(trimmed.to_string());
+    }
+
+    if cleaned.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
+
+    let card_board_id =
+        sqlx::query_scalar::<_, Option<String>>("SELECT board_id FROM kanban_cards WHERE id = ?")
+            .bind(&card_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| format!("Falha ao carregar cartão: {e}"))?
+            .flatten()
+            .ok_or_else(|| "Cartão não encontrado.".to_string())?;
+
+    if card_board_id != board_id {
+        return Err("A subtask precisa pertencer ao mesmo quadro do cartão.".to_string());
+    }
+
+    // MAX rather than COUNT: deletes leave gaps that would collide with
+    // `idx_subtasks_card_position`.
+    let next_position = sqlx::query_scalar::<_, i64>(
+        "SELECT COALESCE(MAX(position), -1) + 1 FROM kanban_subtasks WHERE card_id = ?",
+    )
+    .bind(&card_id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| format!("Falha ao calcular posição das subtasks: {e}"))?;
+
+    let mut created_ids = Vec::with_capacity(cleaned.len());
+    for (index, title) in cleaned.iter().enumerate() {
+        let id = Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO kanban_subtasks (id, board_id, card_id, title, is_completed, position, created_at, updated_at) VALUES (?, ?, ?, ?, 0, ?, strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))",
+        )
+        .bind(&id)
+        .bind(&board_id)
+        .bind(&card_id)
+        .bind(title)
+        .bind(next_position + index as i64)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao criar subtask: {e}"))?;
+        created_ids.push(id);
+    }
+
+    normalize_subtask_positions_tx(&mut tx, &card_id)
+        .await
+        .map_err(|e| format!("Falha ao normalizar posições das subtasks: {e}"))?;
+
+    let mut created = Vec::with_capacity(created_ids.len());
+    for id in &created_ids {
+        let row = sqlx::query(
+            "SELECT id, board_id, card_id, title, is_completed, position, created_at, updated_at FROM kanban_subtasks WHERE id = ?",
+        )
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao carregar subtask: {e}"))?;
+
+        created.push(map_subtask_row(row).map_err(|e| e.to_string())?);
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
+
+    emit_board_changed(&app, &board_id, "subtask_created");
+
+    Ok(created)
+}
+
 #[tauri::command]
 async fn update_subtask(pool: State<'_, DbPool>, args: UpdateSubtaskArgs) -> Result<Value, String> {
     let mut tx = pool
@@ -269,53 +451,111 @@ async fn update_subtask(pool: State<'_, DbPool>, args: UpdateSubtaskArgs) -> Res
     }
 
     if let Some(target_position) = args.target_position {
-        let subtask_ids = sqlx::query_as::<_, (String,)>(
-            "SELECT id FROM kanban_subtasks WHERE card_id = ? ORDER BY position ASC, created_at ASC",
+        reorder_subtask_tx(&mut tx, &args.card_id, &args.id, target_position).await?;
+    }
+
+    let row = sqlx::query(
+        "SELECT id, board_id, card_id, title, is_completed, position, created_at, updated_at FROM kanban_subtasks WHERE id = ?",
+    )
+    .bind(&args.id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| format!("Falha ao recuperar subtask: {e}"))?;
+
+    let mapped = map_subtask_row(row).map_err(|e| e.to_string())?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
+
+    Ok(mapped)
+}
+
+/// Moves a subtask to `target_position` (clamped) within its card. Positions are
+/// parked above the current range first so the unique (card_id, position) index holds.
+async fn reorder_subtask_tx(
+    tx: &mut Transaction<'_, Sqlite>,
+    card_id: &str,
+    subtask_id: &str,
+    target_position: i64,
+) -> Result<(), String> {
+    let subtask_ids = sqlx::query_as::<_, (String,)>(
+        "SELECT id FROM kanban_subtasks WHERE card_id = ? ORDER BY position ASC, created_at ASC",
+    )
+    .bind(card_id)
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(|e| format!("Falha ao carregar subtasks: {e}"))?;
+
+    let current_index = subtask_ids
+        .iter()
+        .position(|(id,)| id == subtask_id)
+        .ok_or_else(|| "Subtask não encontrada.".to_string())?;
+
+    let mut ordered: Vec<String> = subtask_ids.into_iter().map(|(id,)| id).collect();
+    let moving = ordered.remove(current_index);
+
+    let clamped = target_position.clamp(0, ordered.len() as i64);
+    ordered.insert(clamped as usize, moving);
+
+    let offset = ordered.len() as i64;
+    for (index, id) in ordered.iter().enumerate() {
+        sqlx::query(
+            "UPDATE kanban_subtasks SET position = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
         )
-        .bind(&args.card_id)
-        .fetch_all(&mut *tx)
+        .bind(index as i64 + offset)
+        .bind(id)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| format!("Falha ao reordenar subtasks: {e}"))?;
+    }
+
+    normalize_subtask_positions_tx(tx, card_id)
         .await
-        .map_err(|e| format!("Falha ao carregar subtasks: {e}"))?;
+        .map_err(|e| format!("Falha ao normalizar posições das subtasks: {e}"))?;
 
-        let current_index = subtask_ids
-            .iter()
-            .position(|(id,)| id == &args.id)
-            .ok_or_else(|| "Subtask não encontrada.".to_string())?;
+    Ok(())
+}
 
-        let mut ordered: Vec<String> = subtask_ids.into_iter().map(|(id,)| id).collect();
-        let moving = ordered.remove(current_index);
+#[tauri::command]
+async fn reorder_subtask(
+    pool: State<'_, DbPool>,
+    card_id: String,
+    board_id: String,
+    subtask_id: String,
+    target_position: i64,
+) -> Result<Value, String> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
 
-        let mut clamped = target_position;
-        if clamped < 0 {
-            clamped = 0;
-        }
-        if clamped as usize > ordered.len() {
-            clamped = ordered.len() as i64;
-        }
+    let record = sqlx::query_as::<_, (String, String)>(
+        "SELECT board_id, card_id FROM kanban_subtasks WHERE id = ?",
+    )
+    .bind(&subtask_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| format!("Falha ao carregar subtask: {e}"))?;
 
-        ordered.insert(clamped as usize, moving);
+    let Some((board_id_db, card_id_db)) = record else {
+        return Err("Subtask não encontrada.".to_string());
+    };
 
-        let offset = ordered.len() as i64;
-        for (index, id) in ordered.iter().enumerate() {
-            sqlx::query(
-                "UPDATE kanban_subtasks SET position = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
-            )
-            .bind(index as i64 + offset)
-            .bind(id)
-            .execute(&mut *tx)
-            .await
-            .map_err(|e| format!("Falha ao reordenar subtasks: {e}"))?;
-        }
+    if board_id_db != board_id {
+        return Err("A subtask não pertence ao quadro informado.".to_string());
+    }
 
-        normalize_subtask_positions_tx(&mut tx, &args.card_id)
-            .await
-            .map_err(|e| format!("Falha ao normalizar posições das subtasks: {e}"))?;
+    if card_id_db != card_id {
+        return Err("A subtask não pertence ao cartão informado.".to_string());
     }
 
+    reorder_subtask_tx(&mut tx, &card_id, &subtask_id, target_position).await?;
+
     let row = sqlx::query(
         "SELECT id, board_id, card_id, title, is_completed, position, created_at, updated_at FROM kanban_subtasks WHERE id = ?",
     )
-    .bind(&args.id)
+    .bind(&subtask_id)
     .fetch_one(&mut *tx)
     .await
     .map_err(|e| format!("Falha ao recuperar subtask: {e}"))?;
@@ -329,6 +569,57 @@ async fn update_subtask(pool: State<'_, DbPool>, args: UpdateSubtaskArgs) -> Res
     Ok(mapped)
 }
 
+/// Returns how many subtasks changed state; ones already in `is_completed` are left untouched.
+#[tauri::command]
+async fn set_all_subtasks_completed(
+    pool: State<'_, DbPool>,
+    card_id: String,
+    board_id: String,
+    is_completed: bool,
+) -> Result<u64, String> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
+
+    let card_board_id =
+        sqlx::query_scalar::<_, String>("SELECT board_id FROM kanban_cards WHERE id = ?")
+            .bind(&card_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| format!("Falha ao carregar cartão: {e}"))?
+            .ok_or_else(|| "Cartão não encontrado.".to_string())?;
+
+    if card_board_id != board_id {
+        return Err("O cartão não pertence ao quadro informado.".to_string());
+    }
+
+    let flag = if is_completed { 1 } else { 0 };
+    let result = sqlx::query(
+        "UPDATE kanban_subtasks SET is_completed = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE card_id = ? AND is_completed <> ?",
+    )
+    .bind(flag)
+    .bind(&card_id)
+    .bind(flag)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("Falha ao atualizar subtasks: {e}"))?;
+
+    sqlx::query(
+        "UPDATE kanban_cards SET updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
+    )
+    .bind(&card_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("Falha ao atualizar cartão: {e}"))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
+
+    Ok(result.rows_affected())
+}
+
 #[tauri::command]
 async fn delete_subtask(pool: State<'_, DbPool>, args: DeleteSubtaskArgs) -> Result<(), String> {
     let mut tx = pool
@@ -392,6 +683,23 @@ fn directory_size(path: &Path) -> Result<u64, std::io::Error> {
     Ok(total)
 }
 
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) -> Result<(), std::io::Error> {
+    if !path.exists() {
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        if entry.metadata()?.is_dir() {
+            collect_files(&entry.path(), files)?;
+        } else {
+            files.push(entry.path());
+        }
+    }
+
+    Ok(())
+}
+
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct StorageStats {
@@ -407,7 +715,7 @@ struct StorageStats {
 }
 
 #[tauri::command]
-async fn get_storage_stats(app: AppHandle) -> Result<StorageStats, String> {
+async fn get_storage_stats<R: Runtime>(app: AppHandle<R>) -> Result<StorageStats, String> {
     let app_data_dir = app
         .path()
         .app_data_dir()
@@ -445,7 +753,7 @@ async fn get_storage_stats(app: AppHandle) -> Result<StorageStats, String> {
 }
 
 #[tauri::command]
-async fn clear_attachments(app: AppHandle) -> Result<(), String> {
+async fn clear_attachments<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
     let app_data_dir = app
         .path()
         .app_data_dir()
@@ -463,41 +771,181 @@ async fn clear_attachments(app: AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+/// `upload_image` writes the file and thumbnail before inserting their row, so
+/// the sweep leaves recent files alone rather than racing an upload.
+const ATTACHMENT_SWEEP_GRACE: Duration = Duration::from_secs(5 * 60);
+
+/// Deletes files under `attachments` that no attachment row (or legacy card
+/// attachment list) references, and reports rows whose file is gone. Files
+/// modified within `ATTACHMENT_SWEEP_GRACE` are kept.
 #[tauri::command]
-async fn reset_application_data(app: AppHandle, pool: State<'_, DbPool>) -> Result<(), String> {
+async fn cleanup_orphaned_attachments<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+) -> Result<Value, String> {
     let app_data_dir = app
         .path()
         .app_data_dir()
         .map_err(|e| format!("Failed to resolve app data directory: {e}"))?;
+    let attachments_dir = app_data_dir.join("attachments");
 
-    if app_data_dir.exists() {
-        // Remove everything except the directory itself
-        for entry in fs::read_dir(&app_data_dir)
-            .map_err(|e| format!("Failed to read application data directory: {e}"))?
-        {
-            let entry = entry.map_err(|e| format!("Failed to access entry: {e}"))?;
-            let path = entry.path();
-            if path.is_dir() {
-                fs::remove_dir_all(&path)
-                    .map_err(|e| format!("Failed to remove directory {path:?}: {e}"))?;
-            } else {
-                fs::remove_file(&path)
-                    .map_err(|e| format!("Failed to remove file {path:?}: {e}"))?;
-            }
+    let rows = sqlx::query_as::<_, (String, String, String, i64, String, Option<String>)>(
+        "SELECT id, card_id, board_id, version, storage_path, thumbnail_path FROM kanban_attachments WHERE is_external = 0",
+    )
+    .fetch_all(&*pool)
+    .await
+    .map_err(|e| format!("Failed to load attachments: {e}"))?;
+
+    let mut referenced: HashSet<String> = HashSet::new();
+    let mut missing_attachments = Vec::new();
+    for (id, card_id, board_id, version, storage_path, thumbnail_path) in rows {
+        if !app_data_dir.join(&storage_path).exists() {
+            log::warn!("Attachment {id} v{version} references a missing file: {storage_path}");
+            missing_attachments.push(json!({
+                "id": id,
+                "cardId": card_id,
+                "boardId": board_id,
+                "version": version,
+                "storagePath": storage_path.clone(),
+            }));
+        }
+
+        referenced.insert(storage_path);
+        if let Some(thumbnail_path) = thumbnail_path {
+            referenced.insert(thumbnail_path);
         }
     }
 
-    // Ensure critical directories exist again
-    let attachments_dir = app_data_dir.join("attachments");
-    fs::create_dir_all(&attachments_dir)
-        .map_err(|e| format!("Failed to recreate attachments directory: {e}"))?;
+    let legacy_lists: Vec<Option<String>> =
+        sqlx::query_scalar("SELECT attachments FROM kanban_cards WHERE attachments IS NOT NULL")
+            .fetch_all(&*pool)
+            .await
+            .map_err(|e| format!("Failed to load legacy attachments: {e}"))?;
+    for list in legacy_lists.into_iter().flatten() {
+        if let Ok(paths) = serde_json::from_str::<Vec<String>>(&list) {
+            referenced.extend(paths);
+        }
+    }
 
-    let icons_dir = app_data_dir.join(WORKSPACE_ICON_DIR);
-    fs::create_dir_all(&icons_dir)
-        .map_err(|e| format!("Failed to recreate workspace icon directory: {e}"))?;
+    let mut files = Vec::new();
+    collect_files(&attachments_dir, &mut files)
+        .map_err(|e| format!("Failed to scan attachments directory: {e}"))?;
 
-    if let Ok(pref_path) = get_preferences_path(&app)
-        && pref_path.exists()
+    let mut removed_files = 0u64;
+    let mut freed_bytes = 0u64;
+    for file in files {
+        let Ok(relative) = file.strip_prefix(&app_data_dir) else {
+            continue;
+        };
+        let relative_path = relative
+            .iter()
+            .map(|component| component.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        if referenced.contains(&relative_path) {
+            continue;
+        }
+
+        let Ok(metadata) = fs::metadata(&file) else {
+            continue;
+        };
+        let age = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok());
+        if age.is_none_or(|age| age < ATTACHMENT_SWEEP_GRACE) {
+            continue;
+        }
+
+        let size = metadata.len();
+        match fs::remove_file(&file) {
+            Ok(()) => {
+                log::info!("Removed orphaned attachment file {relative_path} ({size} bytes)");
+                removed_files += 1;
+                freed_bytes += size;
+            }
+            Err(e) => log::warn!("Failed to remove orphaned attachment file {relative_path}: {e}"),
+        }
+    }
+
+    Ok(json!({
+        "removedFiles": removed_files,
+        "freedBytes": freed_bytes,
+        "missingAttachments": missing_attachments,
+    }))
+}
+
+/// Reclaims free pages without touching any data and returns the new sizes.
+#[tauri::command]
+async fn optimize_database<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+) -> Result<StorageStats, String> {
+    let pool_ref = pool.inner();
+
+    sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)")
+        .execute(pool_ref)
+        .await
+        .map_err(|e| format!("Failed to checkpoint database: {e}"))?;
+
+    sqlx::query("VACUUM")
+        .execute(pool_ref)
+        .await
+        .map_err(|e| format!("Failed to vacuum database: {e}"))?;
+
+    sqlx::query("ANALYZE")
+        .execute(pool_ref)
+        .await
+        .map_err(|e| format!("Failed to analyze database: {e}"))?;
+
+    // VACUUM goes through the WAL, so checkpoint again for the main file to shrink
+    sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)")
+        .execute(pool_ref)
+        .await
+        .map_err(|e| format!("Failed to checkpoint database: {e}"))?;
+
+    get_storage_stats(app).await
+}
+
+#[tauri::command]
+async fn reset_application_data<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+) -> Result<(), String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data directory: {e}"))?;
+
+    if app_data_dir.exists() {
+        // Remove everything except the directory itself
+        for entry in fs::read_dir(&app_data_dir)
+            .map_err(|e| format!("Failed to read application data directory: {e}"))?
+        {
+            let entry = entry.map_err(|e| format!("Failed to access entry: {e}"))?;
+            let path = entry.path();
+            if path.is_dir() {
+                fs::remove_dir_all(&path)
+                    .map_err(|e| format!("Failed to remove directory {path:?}: {e}"))?;
+            } else {
+                fs::remove_file(&path)
+                    .map_err(|e| format!("Failed to remove file {path:?}: {e}"))?;
+            }
+        }
+    }
+
+    // Ensure critical directories exist again
+    let attachments_dir = app_data_dir.join("attachments");
+    fs::create_dir_all(&attachments_dir)
+        .map_err(|e| format!("Failed to recreate attachments directory: {e}"))?;
+
+    let icons_dir = app_data_dir.join(WORKSPACE_ICON_DIR);
+    fs::create_dir_all(&icons_dir)
+        .map_err(|e| format!("Failed to recreate workspace icon directory: {e}"))?;
+
+    if let Ok(pref_path) = get_preferences_path(&app)
+        && pref_path.exists()
     {
         fs::remove_file(&pref_path)
             .map_err(|e| format!("Failed to remove preferences file: {e}"))?;
@@ -521,6 +969,7 @@ async fn reset_application_data(app: AppHandle, pool: State<'_, DbPool>) -> Resu
 
     for table in [
         "kanban_activity",
+        "board_stats_cache",
         "kanban_subtasks",
         "kanban_card_tags",
         "kanban_attachments",
@@ -529,6 +978,7 @@ async fn reset_application_data(app: AppHandle, pool: State<'_, DbPool>) -> Resu
         "kanban_tags",
         "kanban_boards",
         "notes",
+        "deletions",
         "workspaces",
     ] {
         let stmt = format!("DELETE FROM {table}");
@@ -573,7 +1023,10 @@ async fn reset_application_data(app: AppHandle, pool: State<'_, DbPool>) -> Resu
 }
 
 #[tauri::command]
-async fn export_application_data(app: AppHandle, destination_path: String) -> Result<(), String> {
+async fn export_application_data<R: Runtime>(
+    app: AppHandle<R>,
+    destination_path: String,
+) -> Result<(), String> {
     use std::path::PathBuf;
 
     let app_data_dir = app
@@ -643,7 +1096,10 @@ async fn export_application_data(app: AppHandle, destination_path: String) -> Re
 }
 
 #[tauri::command]
-async fn import_application_data(app: AppHandle, destination_path: String) -> Result<(), String> {
+async fn import_application_data<R: Runtime>(
+    app: AppHandle<R>,
+    destination_path: String,
+) -> Result<(), String> {
     use std::path::PathBuf;
 
     let app_data_dir = app
@@ -718,14 +1174,39 @@ async fn import_application_data(app: AppHandle, destination_path: String) -> Re
     Ok(())
 }
 
-#[tauri::command]
-async fn open_attachment(app: AppHandle, file_path: String) -> Result<(), String> {
-    let app_data_dir = app
+async fn resolve_attachment_path<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &DbPool,
+    file_path: &str,
+) -> Result<PathBuf, String> {
+    let is_external = sqlx::query_scalar::<_, i64>(
+        "SELECT 1 FROM kanban_attachments WHERE storage_path = ? AND is_external = 1 LIMIT 1",
+    )
+    .bind(file_path)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| format!("Failed to look up attachment metadata: {e}"))?
+    .is_some();
+
+    // External attachments store the absolute path of the original file
+    if is_external {
+        return Ok(PathBuf::from(file_path));
+    }
+
+    Ok(app
         .path()
         .app_data_dir()
-        .map_err(|e| format!("Failed to resolve app data directory: {e}"))?;
+        .map_err(|e| format!("Failed to resolve app data directory: {e}"))?
+        .join(file_path))
+}
 
-    let full_path = app_data_dir.join(&file_path);
+#[tauri::command]
+async fn open_attachment<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    file_path: String,
+) -> Result<(), String> {
+    let full_path = resolve_attachment_path(&app, &pool, &file_path).await?;
 
     if !full_path.exists() {
         return Err(format!("Attachment not found: {}", file_path));
@@ -762,6 +1243,10 @@ struct UpdateCardArgs {
     remind_at: Option<Option<String>>,
     #[serde(default)]
     clear_remind_at: Option<bool>,
+    #[serde(default)]
+    remind_recurrence: Option<String>,
+    #[serde(default)]
+    estimate_minutes: Option<Option<i64>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -869,69 +1354,87 @@ struct UpdateWorkspaceArgs {
     color: Option<Option<String>>,
 }
 
-#[tauri::command]
-async fn update_card(
-    app: AppHandle,
-    pool: State<'_, DbPool>,
-    args: UpdateCardArgs,
-) -> Result<(), String> {
-    log::info!(
-        "Attempting to update card with id: {}, board_id: {}",
-        args.id,
-        args.board_id
-    );
-
-    log::info!("update_card: raw due_date arg = {:?}", args.due_date);
-
+/// Applies an `UpdateCardArgs` patch and logs its activity inside `tx`, shared by
+/// `update_card` and the update path of `save_card`. Returns false when the patch
+/// changes nothing, in which case nothing was written.
+async fn update_card_tx<R: Runtime>(
+    app: &AppHandle<R>,
+    tx: &mut Transaction<'_, Sqlite>,
+    args: &UpdateCardArgs,
+) -> Result<bool, CommandError> {
     if args.title.as_ref().is_some_and(|t| t.trim().is_empty()) {
-        return Err("O título do cartão não pode ser vazio.".to_string());
+        return Err(CommandError::Validation(
+            "O título do cartão não pode ser vazio.".to_string(),
+        ));
     }
 
     if let Some(ref priority) = args.priority {
-        validate_priority(priority)?;
+        validate_priority(priority).map_err(CommandError::Validation)?;
     }
 
-    let mut tx = pool
-        .begin()
-        .await
-        .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
+    if let Some(estimate_minutes) = args.estimate_minutes {
+        validate_estimate_minutes(estimate_minutes).map_err(CommandError::Validation)?;
+    }
 
-    let existing = sqlx::query_as::<_, (String, String)>(
-        "SELECT board_id, column_id FROM kanban_cards WHERE id = ?",
+    if let Some(ref recurrence) = args.remind_recurrence {
+        validate_remind_recurrence(recurrence).map_err(CommandError::Validation)?;
+    }
+
+    let existing = sqlx::query_as::<
+        _,
+        (String, String, String, String, Option<String>, Option<String>),
+    >(
+        "SELECT board_id, column_id, title, priority, due_date, remind_at FROM kanban_cards WHERE id = ?",
     )
     .bind(&args.id)
-    .fetch_optional(&mut *tx)
+    .fetch_optional(&mut **tx)
     .await
-    .map_err(|e| format!("Falha ao carregar cartão: {e}"))?;
-
-    let Some((board_id_db, _column_id)) = existing else {
-        return Err("Cartão não encontrado.".to_string());
+    .map_err(|e| CommandError::Database(format!("Falha ao carregar cartão: {e}")))?;
+
+    let Some((
+        board_id_db,
+        _column_id,
+        current_title,
+        current_priority,
+        current_due_date,
+        current_remind_at,
+    )) = existing
+    else {
+        return Err(CommandError::NotFound("Cartão não encontrado.".to_string()));
     };
 
     if board_id_db != args.board_id {
-        return Err("O cartão não pertence ao quadro informado.".to_string());
+        return Err(CommandError::Validation(
+            "O cartão não pertence ao quadro informado.".to_string(),
+        ));
     }
 
     let mut has_changes = false;
     let mut new_remind_at: Option<String> = None;
+    // Outer None: due date untouched; Some(None): cleared.
+    let mut new_due_date: Option<Option<String>> = None;
 
-    // Build the SQL query manually
-    let mut sql =
-        String::from("UPDATE kanban_cards SET updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')");
+    let mut builder = QueryBuilder::<Sqlite>::new(
+        "UPDATE kanban_cards SET updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+    );
 
     // Handle title update
     if let Some(ref title) = args.title {
         let trimmed = title.trim().to_string();
         if trimmed.is_empty() {
-            return Err("O título do cartão não pode ser vazio.".to_string());
+            return Err(CommandError::Validation(
+                "O título do cartão não pode ser vazio.".to_string(),
+            ));
         }
         log::info!(
             "Updating title to: '{}' (length: {})",
             trimmed,
             trimmed.len()
         );
-        validate_string_input(&trimmed, 200, "Título do cartão")?;
-        sql.push_str(&format!(", title = '{}'", trimmed.replace('\'', "''")));
+        validate_string_input(&trimmed, 200, "Título do cartão")
+            .map_err(CommandError::Validation)?;
+        builder.push(", title = ");
+        builder.push_bind(trimmed);
         has_changes = true;
     }
 
@@ -941,21 +1444,23 @@ async fn update_card(
             Some(value) => normalize_optional_text(Some(value.clone())),
             None => None,
         };
-        let desc_str = normalized.unwrap_or_default().replace('\'', "''");
-        sql.push_str(&format!(", description = '{}'", desc_str));
+        builder.push(", description = ");
+        builder.push_bind(normalized.unwrap_or_default());
         has_changes = true;
     }
 
     // Handle priority update
     if let Some(ref priority) = args.priority {
-        sql.push_str(&format!(", priority = '{}'", priority));
+        builder.push(", priority = ");
+        builder.push_bind(priority.clone());
         has_changes = true;
     }
 
     // Handle due date update
     if args.clear_due_date.unwrap_or(false) {
         // Pedido explícito para limpar a data de vencimento
-        sql.push_str(", due_date = NULL");
+        builder.push(", due_date = NULL");
+        new_due_date = Some(None);
         has_changes = true;
     } else if let Some(ref due_date) = args.due_date {
         match due_date {
@@ -964,15 +1469,19 @@ async fn update_card(
                 let trimmed = value.trim();
                 if trimmed.is_empty() {
                     // String vazia ou só espaços: limpar o campo no banco
-                    sql.push_str(", due_date = NULL");
+                    builder.push(", due_date = NULL");
+                    new_due_date = Some(None);
                 } else {
-                    let escaped = trimmed.replace('\'', "''");
-                    sql.push_str(&format!(", due_date = '{}'", escaped));
+                    validate_iso_datetime(trimmed).map_err(CommandError::Validation)?;
+                    builder.push(", due_date = ");
+                    builder.push_bind(trimmed.to_string());
+                    new_due_date = Some(Some(trimmed.to_string()));
                 }
             }
             // Frontend enviou null explicitamente: limpar o campo
             None => {
-                sql.push_str(", due_date = NULL");
+                builder.push(", due_date = NULL");
+                new_due_date = Some(None);
             }
         }
         has_changes = true;
@@ -980,22 +1489,64 @@ async fn update_card(
 
     // Handle reminder update
     if args.clear_remind_at.unwrap_or(false) {
-        sql.push_str(", remind_at = NULL");
+        builder.push(", remind_at = NULL");
         has_changes = true;
     } else if let Some(ref remind_at) = args.remind_at {
         match remind_at {
             Some(value) => {
                 let trimmed = value.trim();
                 if trimmed.is_empty() {
-                    sql.push_str(", remind_at = NULL");
+                    builder.push(", remind_at = NULL");
                 } else {
-                    let escaped = trimmed.replace('\'', "''");
-                    sql.push_str(&format!(", remind_at = '{}'", escaped));
+                    validate_iso_datetime(trimmed).map_err(CommandError::Validation)?;
+                    builder.push(", remind_at = ");
+                    builder.push_bind(trimmed.to_string());
                     new_remind_at = Some(trimmed.to_string());
                 }
             }
             None => {
-                sql.push_str(", remind_at = NULL");
+                builder.push(", remind_at = NULL");
+            }
+        }
+        has_changes = true;
+    }
+
+    // A due date moved before the stored reminder drops it, like set_card_due_date
+    let reminder_untouched = !args.clear_remind_at.unwrap_or(false) && args.remind_at.is_none();
+    let new_due = new_due_date
+        .as_ref()
+        .and_then(|due| due.as_deref())
+        .and_then(parse_due_date);
+    if reminder_untouched
+        && let Some(due) = new_due
+        && current_remind_at
+            .as_deref()
+            .and_then(parse_due_date)
+            .is_some_and(|remind| due < remind)
+    {
+        builder.push(", remind_at = NULL");
+    }
+
+    // Handle reminder recurrence update
+    if let Some(ref recurrence) = args.remind_recurrence {
+        builder.push(", remind_recurrence = ");
+        if recurrence == "none" {
+            builder.push("NULL");
+        } else {
+            builder.push_bind(recurrence.clone());
+        }
+        has_changes = true;
+    }
+
+    // Handle estimate update
+    if let Some(estimate_minutes) = args.estimate_minutes {
+        builder.push(", estimate_minutes = ");
+        match estimate_minutes {
+            Some(minutes) => {
+                builder.push_bind(minutes);
+            }
+            None => {
+                builder.push("NULL");
             }
         }
         has_changes = true;
@@ -1006,37 +1557,114 @@ async fn update_card(
             "update_card: no changes detected for card id {}, skipping UPDATE",
             args.id
         );
-        return Ok(());
+        return Ok(false);
     }
 
-    sql.push_str(&format!(" WHERE id = '{}'", args.id.replace('\'', "''")));
+    builder.push(" WHERE id = ");
+    builder.push_bind(&args.id);
 
-    log::info!("Executing SQL: {}", sql);
+    log::debug!("Executing SQL: {}", builder.sql());
 
-    // Execute the query
-    let result = sqlx::query(&sql).execute(&mut *tx).await.map_err(|e| {
+    let result = builder.build().execute(&mut **tx).await.map_err(|e| {
         log::error!("Failed to execute update query: {}", e);
-        format!("Falha ao atualizar cartão: {e}")
+        CommandError::Database(format!("Falha ao atualizar cartão: {e}"))
     })?;
 
     // Schedule reminder notification if a new remind_at was set
     if let Some(when) = new_remind_at.clone() {
-        let app_handle = app.clone();
-        schedule_card_reminder(app_handle, when, args.id.clone());
+        schedule_card_reminder(app.clone(), when, args.id.clone());
     }
 
     log::info!("Update affected {} rows", result.rows_affected());
 
+    let activity_title = args
+        .title
+        .as_deref()
+        .map(str::trim)
+        .unwrap_or(current_title.as_str());
+
+    log_activity_tx(
+        tx,
+        &args.board_id,
+        "card",
+        &args.id,
+        "card_updated",
+        activity_title,
+        None,
+    )
+    .await
+    .map_err(CommandError::Database)?;
+
+    if let Some(priority) = args.priority.as_deref()
+        && priority != current_priority
+    {
+        log_activity_tx(
+            tx,
+            &args.board_id,
+            "card",
+            &args.id,
+            "card_priority_changed",
+            activity_title,
+            Some(json!({ "from": current_priority, "to": priority })),
+        )
+        .await
+        .map_err(CommandError::Database)?;
+    }
+
+    if let Some(due_date) = new_due_date
+        && due_date != current_due_date
+    {
+        log_activity_tx(
+            tx,
+            &args.board_id,
+            "card",
+            &args.id,
+            "card_due_date_changed",
+            activity_title,
+            Some(json!({ "from": current_due_date, "to": due_date })),
+        )
+        .await
+        .map_err(CommandError::Database)?;
+    }
+
+    Ok(true)
+}
+
+#[tauri::command]
+async fn update_card<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    args: UpdateCardArgs,
+) -> Result<(), CommandError> {
+    log::info!(
+        "Attempting to update card with id: {}, board_id: {}",
+        args.id,
+        args.board_id
+    );
+
+    log::info!("update_card: raw due_date arg = {:?}", args.due_date);
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao abrir transação: {e}")))?;
+
+    if !update_card_tx(&app, &mut tx, &args).await? {
+        return Ok(());
+    }
+
     tx.commit().await.map_err(|e| {
         log::error!("Failed to commit transaction: {}", e);
-        format!("Falha ao confirmar transação: {e}")
+        CommandError::Database(format!("Falha ao confirmar transação: {e}"))
     })?;
 
+    emit_board_changed(&app, &args.board_id, "card_updated");
+
     log::info!("Card update completed successfully");
     Ok(())
 }
 
-fn schedule_card_reminder(app: AppHandle, when_iso: String, card_id: String) {
+fn schedule_card_reminder<R: Runtime>(app: AppHandle<R>, when_iso: String, card_id: String) {
     log::info!("Scheduling reminder for card {} at {}", card_id, when_iso);
 
     tauri::async_runtime::spawn(async move {
@@ -1071,8 +1699,32 @@ fn schedule_card_reminder(app: AppHandle, when_iso: String, card_id: String) {
             tokio_time::sleep(Duration::from_millis(delay)).await;
         }
 
+        // The reminder may have been moved, cleared or re-armed since this task started.
+        let pool = app.state::<DbPool>().inner().clone();
+        let current = sqlx::query_as::<_, (Option<String>, Option<String>)>(
+            "SELECT remind_at, remind_recurrence FROM kanban_cards WHERE id = ? AND archived_at IS NULL",
+        )
+        .bind(&card_id)
+        .fetch_optional(&pool)
+        .await;
+
+        let recurrence = match current {
+            Ok(Some((Some(remind_at), recurrence))) if remind_at == when_iso => recurrence,
+            Ok(_) => {
+                log::info!(
+                    "Reminder for card {} is no longer current, skipping",
+                    card_id
+                );
+                return;
+            }
+            Err(e) => {
+                log::error!("Failed to load reminder for card {}: {}", card_id, e);
+                return;
+            }
+        };
+
         if let Err(e) = send_native_notification(
-            app,
+            app.clone(),
             "Task reminder".to_string(),
             Some(format!("You asked to be reminded about card {}", card_id)),
         )
@@ -1084,40 +1736,270 @@ fn schedule_card_reminder(app: AppHandle, when_iso: String, card_id: String) {
                 e
             );
         }
+
+        let step = match recurrence.as_deref() {
+            Some("daily") => chrono::Duration::days(1),
+            Some("weekly") => chrono::Duration::weeks(1),
+            _ => return,
+        };
+
+        // Skip occurrences missed while the app was closed so the next one is in the future.
+        let now = Utc::now();
+        let mut next = parsed + step;
+        while next <= now {
+            next += step;
+        }
+        let next_iso = next.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string();
+
+        let result = sqlx::query(
+            "UPDATE kanban_cards SET remind_at = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ? AND remind_at = ?",
+        )
+        .bind(&next_iso)
+        .bind(&card_id)
+        .bind(&when_iso)
+        .execute(&pool)
+        .await;
+
+        match result {
+            Ok(done) if done.rows_affected() > 0 => {
+                schedule_card_reminder(app, next_iso, card_id);
+            }
+            Ok(_) => log::info!("Reminder for card {} changed before re-arming", card_id),
+            Err(e) => log::error!("Failed to re-arm reminder for card {}: {}", card_id, e),
+        }
     });
 }
 
+fn validate_remind_recurrence(recurrence: &str) -> Result<(), String> {
+    match recurrence {
+        "none" | "daily" | "weekly" => Ok(()),
+        _ => Err(
+            "Recorrência de lembrete inválida. Utilize 'none', 'daily' ou 'weekly'.".to_string(),
+        ),
+    }
+}
+
+/// Reminders computed further in the past than this are rejected instead of firing at once.
+const REMINDER_PAST_TOLERANCE_MINUTES: i64 = 5;
+
+/// Rejects values `julianday()` cannot read. Date pickers send date-only values,
+/// so `YYYY-MM-DD` is accepted alongside full RFC 3339 timestamps.
+fn validate_iso_datetime(value: &str) -> Result<(), String> {
+    parse_due_date(value).map(|_| ()).ok_or_else(|| {
+        format!(
+            "Data inválida: \"{}\". Utilize o formato ISO 8601 (RFC 3339).",
+            value.trim()
+        )
+    })
+}
+
+/// Trims a due date for storage; blank values clear it and anything else must parse.
+fn normalize_due_date(value: Option<&str>) -> Result<Option<String>, String> {
+    match value.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(value) => {
+            validate_iso_datetime(value)?;
+            Ok(Some(value.to_string()))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Accepts full RFC 3339 due dates as well as date-only values, read as midnight UTC.
+fn parse_due_date(value: &str) -> Option<DateTime<Utc>> {
+    let trimmed = value.trim();
+    DateTime::parse_from_rfc3339(trimmed)
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            chrono::NaiveDate::parse_from_str(trimmed, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+                .map(|naive| naive.and_utc())
+        })
+}
+
 #[tauri::command]
-async fn move_column(
+async fn set_reminder_before_due<R: Runtime>(
+    app: AppHandle<R>,
     pool: State<'_, DbPool>,
     board_id: String,
-    column_id: String,
-    target_index: i64,
-) -> Result<(), String> {
-    let mut tx = pool
-        .begin()
-        .await
-        .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
+    card_id: String,
+    minutes_before: i64,
+) -> Result<Value, String> {
+    if minutes_before < 0 {
+        return Err("A antecedência do lembrete não pode ser negativa.".to_string());
+    }
 
-    let columns = sqlx::query_as::<_, (String,)>(
-        "SELECT id FROM kanban_columns WHERE board_id = ? ORDER BY position ASC, created_at ASC",
+    let card_record = sqlx::query_as::<_, (String, Option<String>)>(
+        "SELECT board_id, due_date FROM kanban_cards WHERE id = ?",
     )
-    .bind(&board_id)
-    .fetch_all(&mut *tx)
+    .bind(&card_id)
+    .fetch_optional(&*pool)
     .await
-    .map_err(|e| format!("Falha ao carregar colunas: {e}"))?;
+    .map_err(|e| format!("Falha ao carregar cartão: {e}"))?;
 
-    if columns.is_empty() {
-        return Err("Nenhuma coluna encontrada para o quadro informado.".to_string());
+    let Some((stored_board_id, due_date)) = card_record else {
+        return Err("Cartão não encontrado.".to_string());
+    };
+
+    if stored_board_id != board_id {
+        return Err("O cartão não pertence ao quadro informado.".to_string());
     }
 
-    let current_index = columns
-        .iter()
-        .position(|(id,)| id == &column_id)
-        .ok_or_else(|| "Coluna não encontrada.".to_string())?;
+    let due = due_date
+        .as_deref()
+        .filter(|value| !value.trim().is_empty())
+        .ok_or_else(|| "O cartão não possui data de vencimento.".to_string())?;
+    let due =
+        parse_due_date(due).ok_or_else(|| "Data de vencimento do cartão inválida.".to_string())?;
 
-    let mut ids: Vec<String> = columns.into_iter().map(|(id,)| id).collect();
-    let removed_id = ids.remove(current_index);
+    let remind_at = due - chrono::Duration::minutes(minutes_before);
+    if remind_at < Utc::now() - chrono::Duration::minutes(REMINDER_PAST_TOLERANCE_MINUTES) {
+        return Err("O lembrete calculado já passou.".to_string());
+    }
+
+    let remind_at = remind_at.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string();
+
+    sqlx::query(
+        "UPDATE kanban_cards SET remind_at = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
+    )
+    .bind(&remind_at)
+    .bind(&card_id)
+    .execute(&*pool)
+    .await
+    .map_err(|e| format!("Falha ao atualizar lembrete: {e}"))?;
+
+    schedule_card_reminder(app, remind_at.clone(), card_id.clone());
+
+    Ok(json!({
+        "cardId": card_id,
+        "remindAt": remind_at,
+    }))
+}
+
+/// Sets or clears a card's due date. A reminder that would now fire after the
+/// new due date is dropped.
+#[tauri::command]
+async fn set_card_due_date<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    board_id: String,
+    card_id: String,
+    due_date: Option<String>,
+) -> Result<Value, CommandError> {
+    let due_date = due_date
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let new_due = match due_date.as_deref() {
+        Some(value) => {
+            validate_iso_datetime(value).map_err(CommandError::Validation)?;
+            parse_due_date(value)
+        }
+        None => None,
+    };
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao abrir transação: {e}")))?;
+
+    let card_record = sqlx::query_as::<_, (String, String, Option<String>, Option<String>)>(
+        "SELECT board_id, title, due_date, remind_at FROM kanban_cards WHERE id = ?",
+    )
+    .bind(&card_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| CommandError::Database(format!("Falha ao carregar cartão: {e}")))?;
+
+    let Some((stored_board_id, title, current_due_date, remind_at)) = card_record else {
+        return Err(CommandError::NotFound("Cartão não encontrado.".to_string()));
+    };
+
+    if stored_board_id != board_id {
+        return Err(CommandError::Validation(
+            "O cartão não pertence ao quadro informado.".to_string(),
+        ));
+    }
+
+    let clear_reminder = match (new_due, remind_at.as_deref().and_then(parse_due_date)) {
+        (Some(due), Some(remind)) => due < remind,
+        _ => false,
+    };
+
+    let mut builder = QueryBuilder::<Sqlite>::new("UPDATE kanban_cards SET due_date = ");
+    builder.push_bind(due_date.clone());
+    if clear_reminder {
+        builder.push(", remind_at = NULL");
+    }
+    builder.push(", updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ");
+    builder.push_bind(&card_id);
+
+    builder
+        .build()
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao atualizar cartão: {e}")))?;
+
+    if due_date != current_due_date {
+        log_activity_tx(
+            &mut tx,
+            &board_id,
+            "card",
+            &card_id,
+            "card_due_date_changed",
+            &title,
+            Some(json!({ "from": current_due_date, "to": due_date })),
+        )
+        .await
+        .map_err(CommandError::Database)?;
+    }
+
+    let card = fetch_card_by_id(&mut tx, &card_id)
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao carregar cartão: {e}")))?
+        .ok_or_else(|| CommandError::NotFound("Cartão não encontrado.".to_string()))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao confirmar transação: {e}")))?;
+
+    emit_board_changed(&app, &board_id, "card_updated");
+
+    Ok(card)
+}
+
+#[tauri::command]
+async fn move_column<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    board_id: String,
+    column_id: String,
+    target_index: i64,
+) -> Result<(), CommandError> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao abrir transação: {e}")))?;
+
+    let columns = sqlx::query_as::<_, (String,)>(
+        "SELECT id FROM kanban_columns WHERE board_id = ? ORDER BY position ASC, created_at ASC",
+    )
+    .bind(&board_id)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| CommandError::Database(format!("Falha ao carregar colunas: {e}")))?;
+
+    if columns.is_empty() {
+        return Err(CommandError::NotFound("Coluna não encontrada.".to_string()));
+    }
+
+    let current_index = columns
+        .iter()
+        .position(|(id,)| id == &column_id)
+        .ok_or_else(|| CommandError::NotFound("Coluna não encontrada.".to_string()))?;
+
+    let mut ids: Vec<String> = columns.into_iter().map(|(id,)| id).collect();
+    let removed_id = ids.remove(current_index);
 
     let mut clamped_index = target_index;
     if clamped_index < 0 {
@@ -1137,77 +2019,191 @@ async fn move_column(
         .bind(id)
         .execute(&mut *tx)
         .await
-        .map_err(|e| format!("Falha ao atualizar posições das colunas: {e}"))?;
+        .map_err(|e| CommandError::Database(format!("Falha ao atualizar posições das colunas: {e}")))?;
     }
 
     tx.commit()
         .await
-        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
+        .map_err(|e| CommandError::Database(format!("Falha ao confirmar transação: {e}")))?;
+
+    emit_board_changed(&app, &board_id, "column_moved");
 
     Ok(())
 }
 
 #[tauri::command]
-async fn move_card(
+async fn reorder_columns(
     pool: State<'_, DbPool>,
     board_id: String,
-    card_id: String,
-    from_column_id: String,
-    to_column_id: String,
-    target_index: i64,
+    ordered_ids: Vec<String>,
 ) -> Result<(), String> {
     let mut tx = pool
         .begin()
         .await
         .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
 
-    let card_info = sqlx::query_as::<_, (String, String)>(
-        "SELECT column_id, board_id FROM kanban_cards WHERE id = ?",
+    let existing: BTreeSet<String> =
+        sqlx::query_scalar::<_, String>("SELECT id FROM kanban_columns WHERE board_id = ?")
+            .bind(&board_id)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(|e| format!("Falha ao carregar colunas: {e}"))?
+            .into_iter()
+            .collect();
+
+    let requested: BTreeSet<String> = ordered_ids.iter().cloned().collect();
+    if requested.len() != ordered_ids.len() {
+        return Err("A nova ordem contém colunas repetidas.".to_string());
+    }
+    if requested != existing {
+        return Err(
+            "A nova ordem precisa conter exatamente as colunas do quadro informado.".to_string(),
+        );
+    }
+
+    for (index, id) in ordered_ids.iter().enumerate() {
+        sqlx::query(
+            "UPDATE kanban_columns SET position = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
+        )
+        .bind(index as i64)
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao atualizar posições das colunas: {e}"))?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
+
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DragMoveArgs {
+    board_id: String,
+    card_id: String,
+    from_column_id: String,
+    to_column_id: String,
+    target_index: i64,
+    #[serde(default)]
+    priority: Option<String>,
+    #[serde(default)]
+    force: Option<bool>,
+}
+
+/// Final placement of a moved card. `wip_warning` is set when the move left the
+/// destination over its WIP limit without being blocked (soft mode or `force`).
+struct CardMoveOutcome {
+    position: i64,
+    wip_warning: Option<String>,
+}
+
+/// Moves a card using the column sizes read inside `tx`, so a stale
+/// `target_index` is clamped against the live destination.
+async fn move_card_tx(
+    tx: &mut Transaction<'_, Sqlite>,
+    args: &DragMoveArgs,
+) -> Result<CardMoveOutcome, CommandError> {
+    let board_id = args.board_id.as_str();
+    let card_id = args.card_id.as_str();
+    let from_column_id = args.from_column_id.as_str();
+    let to_column_id = args.to_column_id.as_str();
+    let target_index = args.target_index;
+
+    if let Some(priority) = args.priority.as_deref() {
+        validate_priority(priority).map_err(CommandError::Validation)?;
+    }
+
+    let card_info = sqlx::query_as::<_, (String, String, String)>(
+        "SELECT column_id, board_id, title FROM kanban_cards WHERE id = ?",
     )
-    .bind(&card_id)
-    .fetch_optional(&mut *tx)
+    .bind(card_id)
+    .fetch_optional(&mut **tx)
     .await
-    .map_err(|e| format!("Falha ao carregar cartão: {e}"))?;
+    .map_err(|e| CommandError::Database(format!("Falha ao carregar cartão: {e}")))?;
 
-    let (current_column_id, card_board_id) =
-        card_info.ok_or_else(|| "Cartão não encontrado.".to_string())?;
+    let (current_column_id, card_board_id, card_title) =
+        card_info.ok_or_else(|| CommandError::NotFound("Cartão não encontrado.".to_string()))?;
 
     if card_board_id != board_id {
-        return Err("O cartão não pertence ao quadro informado.".to_string());
+        return Err(CommandError::Validation(
+            "O cartão não pertence ao quadro informado.".to_string(),
+        ));
     }
 
     if current_column_id != from_column_id {
-        return Err("O cartão não pertence à coluna de origem informada.".to_string());
+        return Err(CommandError::Validation(
+            "O cartão não pertence à coluna de origem informada.".to_string(),
+        ));
     }
 
-    let target_column_board =
-        sqlx::query_scalar::<_, Option<String>>("SELECT board_id FROM kanban_columns WHERE id = ?")
-            .bind(&to_column_id)
-            .fetch_one(&mut *tx)
-            .await
-            .map_err(|e| format!("Falha ao carregar coluna de destino: {e}"))?
-            .ok_or_else(|| "Coluna de destino não encontrada.".to_string())?;
+    let (target_column_board, wip_limit, wip_mode) =
+        sqlx::query_as::<_, (String, Option<i64>, String)>(
+            "SELECT board_id, wip_limit, wip_mode FROM kanban_columns WHERE id = ?",
+        )
+        .bind(to_column_id)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao carregar coluna de destino: {e}")))?
+        .ok_or_else(|| CommandError::NotFound("Coluna de destino não encontrada.".to_string()))?;
 
     if target_column_board != board_id {
-        return Err("A coluna de destino não pertence ao quadro informado.".to_string());
+        return Err(CommandError::Validation(
+            "A coluna de destino não pertence ao quadro informado.".to_string(),
+        ));
+    }
+
+    let mut wip_warning = None;
+    if from_column_id != to_column_id
+        && let Some(limit) = wip_limit
+    {
+        let target_count = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM kanban_cards WHERE column_id = ? AND archived_at IS NULL",
+        )
+        .bind(to_column_id)
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(|e| {
+            CommandError::Database(format!("Falha ao contar cartões da coluna de destino: {e}"))
+        })?;
+
+        if target_count + 1 > limit {
+            if wip_mode == "hard" && !args.force.unwrap_or(false) {
+                return Err(CommandError::Conflict(format!(
+                    "A coluna de destino atingiu o limite WIP de {limit} cartões."
+                )));
+            }
+
+            wip_warning = Some(format!(
+                "A coluna de destino ultrapassou o limite WIP de {limit} cartões."
+            ));
+        }
     }
 
     let mut source_cards = sqlx::query_as::<_, (String,)>(
         "SELECT id FROM kanban_cards WHERE column_id = ? ORDER BY position ASC, created_at ASC",
     )
-    .bind(&from_column_id)
-    .fetch_all(&mut *tx)
+    .bind(from_column_id)
+    .fetch_all(&mut **tx)
     .await
-    .map_err(|e| format!("Falha ao carregar cartões da coluna de origem: {e}"))?;
+    .map_err(|e| {
+        CommandError::Database(format!(
+            "Falha ao carregar cartões da coluna de origem: {e}"
+        ))
+    })?;
 
     let current_index = source_cards
         .iter()
-        .position(|(id,)| id == &card_id)
-        .ok_or_else(|| "Cartão não encontrado na coluna de origem.".to_string())?;
+        .position(|(id,)| id == card_id)
+        .ok_or_else(|| {
+            CommandError::Validation("Cartão não encontrado na coluna de origem.".to_string())
+        })?;
 
     source_cards.remove(current_index);
 
-    if from_column_id == to_column_id {
+    let final_position = if from_column_id == to_column_id {
         let mut reordered: Vec<String> = source_cards.into_iter().map(|(id,)| id).collect();
         let mut clamped = target_index;
         if clamped < 0 {
@@ -1216,7 +2212,7 @@ async fn move_card(
         if clamped as usize > reordered.len() {
             clamped = reordered.len() as i64;
         }
-        reordered.insert(clamped as usize, card_id.clone());
+        reordered.insert(clamped as usize, card_id.to_string());
 
         for (index, id) in reordered.iter().enumerate() {
             sqlx::query(
@@ -1224,10 +2220,12 @@ async fn move_card(
             )
             .bind(index as i64)
             .bind(id)
-            .execute(&mut *tx)
+            .execute(&mut **tx)
             .await
-            .map_err(|e| format!("Falha ao atualizar posições dos cartões: {e}"))?;
+            .map_err(|e| CommandError::Database(format!("Falha ao atualizar posições dos cartões: {e}")))?;
         }
+
+        clamped
     } else {
         // Atualiza posições na coluna de origem após remover o cartão
         for (index, (id,)) in source_cards.iter().enumerate() {
@@ -1236,18 +2234,22 @@ async fn move_card(
             )
             .bind(index as i64)
             .bind(id)
-            .execute(&mut *tx)
+            .execute(&mut **tx)
             .await
-            .map_err(|e| format!("Falha ao atualizar posições na coluna de origem: {e}"))?;
+            .map_err(|e| CommandError::Database(format!("Falha ao atualizar posições na coluna de origem: {e}")))?;
         }
 
         let target_cards = sqlx::query_as::<_, (String,)>(
             "SELECT id FROM kanban_cards WHERE column_id = ? ORDER BY position ASC, created_at ASC",
         )
-        .bind(&to_column_id)
-        .fetch_all(&mut *tx)
+        .bind(to_column_id)
+        .fetch_all(&mut **tx)
         .await
-        .map_err(|e| format!("Falha ao carregar cartões da coluna de destino: {e}"))?;
+        .map_err(|e| {
+            CommandError::Database(format!(
+                "Falha ao carregar cartões da coluna de destino: {e}"
+            ))
+        })?;
 
         let mut clamped = target_index;
         if clamped < 0 {
@@ -1258,16 +2260,16 @@ async fn move_card(
         }
 
         let mut reordered: Vec<String> = target_cards.into_iter().map(|(id,)| id).collect();
-        reordered.insert(clamped as usize, card_id.clone());
+        reordered.insert(clamped as usize, card_id.to_string());
 
         sqlx::query(
             "UPDATE kanban_cards SET column_id = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
         )
-        .bind(&to_column_id)
-        .bind(&card_id)
-        .execute(&mut *tx)
+        .bind(to_column_id)
+        .bind(card_id)
+        .execute(&mut **tx)
         .await
-        .map_err(|e| format!("Falha ao mover cartão para coluna de destino: {e}"))?;
+        .map_err(|e| CommandError::Database(format!("Falha ao mover cartão para coluna de destino: {e}")))?;
 
         for (index, id) in reordered.iter().enumerate() {
             sqlx::query(
@@ -1275,76 +2277,456 @@ async fn move_card(
             )
             .bind(index as i64)
             .bind(id)
-            .execute(&mut *tx)
+            .execute(&mut **tx)
             .await
-            .map_err(|e| format!("Falha ao atualizar posições na coluna de destino: {e}"))?;
+            .map_err(|e| CommandError::Database(format!("Falha ao atualizar posições na coluna de destino: {e}")))?;
         }
+
+        clamped
+    };
+
+    if let Some(priority) = args.priority.as_deref() {
+        sqlx::query(
+            "UPDATE kanban_cards SET priority = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
+        )
+        .bind(priority)
+        .bind(card_id)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao atualizar prioridade do cartão: {e}")))?;
     }
 
-    tx.commit()
+    let (from_column_title, to_column_title) =
+        sqlx::query_as::<_, (Option<String>, Option<String>)>(
+            "SELECT (SELECT title FROM kanban_columns WHERE id = ?), (SELECT title FROM kanban_columns WHERE id = ?)",
+        )
+        .bind(from_column_id)
+        .bind(to_column_id)
+        .fetch_one(&mut **tx)
         .await
-        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
+        .map_err(|e| CommandError::Database(format!("Falha ao carregar títulos das colunas: {e}")))?;
 
-    Ok(())
+    log_activity_tx(
+        tx,
+        board_id,
+        "card",
+        card_id,
+        "card_moved",
+        &card_title,
+        Some(json!({
+            "fromColumnId": from_column_id,
+            "fromColumnTitle": from_column_title,
+            "toColumnId": to_column_id,
+            "toColumnTitle": to_column_title,
+            "position": final_position,
+        })),
+    )
+    .await
+    .map_err(CommandError::Database)?;
+
+    Ok(CardMoveOutcome {
+        position: final_position,
+        wip_warning,
+    })
 }
 
-async fn initialize_schema(pool: &DbPool) -> Result<(), String> {
-    for statement in KANBAN_SCHEMA.split(';') {
-        let sql = statement.trim();
-        if sql.is_empty() {
-            continue;
-        }
+/// Reads `auto_complete_subtasks_on_done`; unreadable preferences just skip the feature.
+async fn auto_complete_subtasks_enabled<R: Runtime>(app: &AppHandle<R>) -> bool {
+    load_preferences(app.clone())
+        .await
+        .map(|preferences| preferences.auto_complete_subtasks_on_done)
+        .unwrap_or_else(|e| {
+            log::warn!("Skipping subtask auto-completion: {e}");
+            false
+        })
+}
 
-        sqlx::query(sql)
-            .execute(pool)
-            .await
-            .map_err(|e| format!("Failed to execute schema statement `{sql}`: {e}"))?;
+/// Completes the card's pending subtasks when the move landed it in a done column.
+/// Moving back out of done leaves them as is. Returns how many were completed.
+async fn complete_subtasks_on_done_tx(
+    tx: &mut Transaction<'_, Sqlite>,
+    args: &DragMoveArgs,
+) -> Result<u64, CommandError> {
+    if args.from_column_id == args.to_column_id {
+        return Ok(0);
     }
 
-    ensure_workspace_support(pool).await?;
-    ensure_board_icon_column(pool).await?;
-    ensure_board_emoji_color_columns(pool).await?;
-    ensure_card_attachments_column(pool).await?;
-    ensure_card_remind_at_column(pool).await?;
-    ensure_column_customization_columns(pool).await?;
-    ensure_notes_board_id_column(pool).await?;
-    ensure_board_favorite_column(pool).await?;
-
-    Ok(())
-}
-
-async fn ensure_board_icon_column(pool: &DbPool) -> Result<(), String> {
-    let column_exists = sqlx::query_scalar::<_, Option<i64>>(
-        "SELECT 1 FROM pragma_table_info('kanban_boards') WHERE name = 'icon' LIMIT 1",
+    let target_kind = sqlx::query_scalar::<_, Option<String>>(
+        "SELECT column_kind FROM kanban_columns WHERE id = ? AND board_id = ?",
     )
-    .fetch_optional(pool)
+    .bind(&args.to_column_id)
+    .bind(&args.board_id)
+    .fetch_optional(&mut **tx)
     .await
-    .map_err(|e| format!("Failed to inspect kanban_boards schema: {e}"))?
-    .flatten()
-    .is_some();
+    .map_err(|e| CommandError::Database(format!("Falha ao carregar coluna: {e}")))?
+    .flatten();
 
-    if !column_exists {
-        sqlx::query("ALTER TABLE kanban_boards ADD COLUMN icon TEXT")
-            .execute(pool)
-            .await
-            .map_err(|e| format!("Failed to add icon column to kanban_boards: {e}"))?;
+    if target_kind.as_deref() != Some("done") {
+        return Ok(0);
     }
 
-    sqlx::query("UPDATE kanban_boards SET icon = ? WHERE icon IS NULL OR TRIM(icon) = ''")
-        .bind(DEFAULT_BOARD_ICON)
-        .execute(pool)
-        .await
-        .map_err(|e| format!("Failed to backfill board icons: {e}"))?;
+    let completed = sqlx::query(
+        "UPDATE kanban_subtasks SET is_completed = 1, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE card_id = ? AND is_completed = 0",
+    )
+    .bind(&args.card_id)
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| CommandError::Database(format!("Falha ao concluir subtasks: {e}")))?
+    .rows_affected();
 
-    Ok(())
+    Ok(completed)
 }
 
-async fn ensure_board_emoji_color_columns(pool: &DbPool) -> Result<(), String> {
-    let emoji_exists = sqlx::query_scalar::<_, Option<i64>>(
-        "SELECT 1 FROM pragma_table_info('kanban_boards') WHERE name = 'emoji' LIMIT 1",
-    )
-    .fetch_optional(pool)
-    .await
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn move_card<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    board_id: String,
+    card_id: String,
+    from_column_id: String,
+    to_column_id: String,
+    target_index: i64,
+    priority: Option<String>,
+    force: Option<bool>,
+) -> Result<Value, CommandError> {
+    let args = DragMoveArgs {
+        board_id,
+        card_id,
+        from_column_id,
+        to_column_id,
+        target_index,
+        priority,
+        force,
+    };
+
+    let auto_complete_subtasks = auto_complete_subtasks_enabled(&app).await;
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao abrir transação: {e}")))?;
+
+    let outcome = move_card_tx(&mut tx, &args).await?;
+
+    let mut completed_subtasks = 0;
+    if auto_complete_subtasks {
+        completed_subtasks = complete_subtasks_on_done_tx(&mut tx, &args).await?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao confirmar transação: {e}")))?;
+
+    emit_board_changed(&app, &args.board_id, "card_moved");
+    if completed_subtasks > 0 {
+        emit_board_changed(&app, &args.board_id, "subtasks_completed");
+    }
+
+    Ok(json!({
+        "columnId": args.to_column_id,
+        "position": outcome.position,
+        "wipWarning": outcome.wip_warning,
+    }))
+}
+
+#[tauri::command]
+async fn drag_move_card<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    args: DragMoveArgs,
+) -> Result<Value, CommandError> {
+    let auto_complete_subtasks = auto_complete_subtasks_enabled(&app).await;
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao abrir transação: {e}")))?;
+
+    let outcome = move_card_tx(&mut tx, &args).await?;
+
+    let mut completed_subtasks = 0;
+    if auto_complete_subtasks {
+        completed_subtasks = complete_subtasks_on_done_tx(&mut tx, &args).await?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao confirmar transação: {e}")))?;
+
+    emit_board_changed(&app, &args.board_id, "card_moved");
+    if completed_subtasks > 0 {
+        emit_board_changed(&app, &args.board_id, "subtasks_completed");
+    }
+
+    Ok(json!({
+        "columnId": args.to_column_id,
+        "position": outcome.position,
+        "wipWarning": outcome.wip_warning,
+    }))
+}
+
+/// Moves `card_ids` in order into `to_column_id` starting at `start_index`, one
+/// `move_card_tx` per card, so the result matches repeated `move_card` calls.
+#[tauri::command]
+async fn move_cards_bulk<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    board_id: String,
+    card_ids: Vec<String>,
+    to_column_id: String,
+    start_index: i64,
+) -> Result<(), CommandError> {
+    if card_ids.is_empty() {
+        return Ok(());
+    }
+
+    let unique_ids: BTreeSet<&str> = card_ids.iter().map(String::as_str).collect();
+    if unique_ids.len() != card_ids.len() {
+        return Err(CommandError::Validation(
+            "A lista de cartões contém itens repetidos.".to_string(),
+        ));
+    }
+
+    let auto_complete_subtasks = auto_complete_subtasks_enabled(&app).await;
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao abrir transação: {e}")))?;
+
+    let column_board_id =
+        sqlx::query_scalar::<_, String>("SELECT board_id FROM kanban_columns WHERE id = ?")
+            .bind(&to_column_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| {
+                CommandError::Database(format!("Falha ao carregar coluna de destino: {e}"))
+            })?
+            .ok_or_else(|| {
+                CommandError::NotFound("Coluna de destino não encontrada.".to_string())
+            })?;
+
+    if column_board_id != board_id {
+        return Err(CommandError::Validation(
+            "A coluna de destino não pertence ao quadro informado.".to_string(),
+        ));
+    }
+
+    let mut source_columns = Vec::with_capacity(card_ids.len());
+    for card_id in &card_ids {
+        let (column_id, card_board_id, archived_at) =
+            sqlx::query_as::<_, (String, String, Option<String>)>(
+                "SELECT column_id, board_id, archived_at FROM kanban_cards WHERE id = ?",
+            )
+            .bind(card_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| CommandError::Database(format!("Falha ao carregar cartão: {e}")))?
+            .ok_or_else(|| CommandError::NotFound(format!("Cartão {card_id} não encontrado.")))?;
+
+        if card_board_id != board_id {
+            return Err(CommandError::Validation(format!(
+                "O cartão {card_id} não pertence ao quadro informado."
+            )));
+        }
+
+        if archived_at.is_some() {
+            return Err(CommandError::Validation(format!(
+                "O cartão {card_id} está arquivado."
+            )));
+        }
+
+        source_columns.push(column_id);
+    }
+
+    // move_card_tx positions among every card of the column, archived ones included
+    let target_count =
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM kanban_cards WHERE column_id = ?")
+            .bind(&to_column_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| {
+                CommandError::Database(format!("Falha ao contar cartões da coluna de destino: {e}"))
+            })?;
+
+    let already_in_target = source_columns
+        .iter()
+        .filter(|column_id| **column_id == to_column_id)
+        .count() as i64;
+    let start = start_index.clamp(0, target_count - already_in_target);
+
+    let mut completed_subtasks = 0;
+    for (offset, (card_id, from_column_id)) in card_ids.into_iter().zip(source_columns).enumerate()
+    {
+        let args = DragMoveArgs {
+            board_id: board_id.clone(),
+            card_id,
+            from_column_id,
+            to_column_id: to_column_id.clone(),
+            target_index: start + offset as i64,
+            priority: None,
+            force: None,
+        };
+        move_card_tx(&mut tx, &args).await?;
+        if auto_complete_subtasks {
+            completed_subtasks += complete_subtasks_on_done_tx(&mut tx, &args).await?;
+        }
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao confirmar transação: {e}")))?;
+
+    emit_board_changed(&app, &board_id, "card_moved");
+    if completed_subtasks > 0 {
+        emit_board_changed(&app, &board_id, "subtasks_completed");
+    }
+
+    Ok(())
+}
+
+fn position_issues(entries: &[(String, i64)]) -> Vec<(String, i64, &'static str)> {
+    let mut counts: HashMap<i64, usize> = HashMap::new();
+    for (_, position) in entries {
+        *counts.entry(*position).or_insert(0) += 1;
+    }
+
+    entries
+        .iter()
+        .filter_map(|(id, position)| {
+            if *position < 0 {
+                Some((id.clone(), *position, "negative"))
+            } else if counts.get(position).copied().unwrap_or(0) > 1 {
+                Some((id.clone(), *position, "duplicate"))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[tauri::command]
+async fn find_position_anomalies(
+    pool: State<'_, DbPool>,
+    board_id: String,
+) -> Result<Value, String> {
+    let columns = sqlx::query_as::<_, (String, i64)>(
+        "SELECT id, position FROM kanban_columns WHERE board_id = ? ORDER BY position ASC, created_at ASC",
+    )
+    .bind(&board_id)
+    .fetch_all(&*pool)
+    .await
+    .map_err(|e| format!("Falha ao carregar colunas: {e}"))?;
+
+    let column_issues: Vec<Value> = position_issues(&columns)
+        .into_iter()
+        .map(|(id, position, issue)| json!({ "id": id, "position": position, "issue": issue }))
+        .collect();
+
+    let mut card_issues = Vec::new();
+    for (column_id, _) in &columns {
+        let cards = sqlx::query_as::<_, (String, i64)>(
+            "SELECT id, position FROM kanban_cards WHERE column_id = ? ORDER BY position ASC, created_at ASC",
+        )
+        .bind(column_id)
+        .fetch_all(&*pool)
+        .await
+        .map_err(|e| format!("Falha ao carregar cartões da coluna: {e}"))?;
+
+        let issues: Vec<Value> = position_issues(&cards)
+            .into_iter()
+            .map(|(id, position, issue)| {
+                json!({ "cardId": id, "position": position, "issue": issue })
+            })
+            .collect();
+
+        if !issues.is_empty() {
+            card_issues.push(json!({ "columnId": column_id, "issues": issues }));
+        }
+    }
+
+    Ok(json!({
+        "columns": column_issues,
+        "cards": card_issues,
+    }))
+}
+
+async fn initialize_schema(pool: &DbPool) -> Result<(), String> {
+    for statement in KANBAN_SCHEMA.split(';') {
+        let sql = statement.trim();
+        if sql.is_empty() {
+            continue;
+        }
+
+        sqlx::query(sql)
+            .execute(pool)
+            .await
+            .map_err(|e| format!("Failed to execute schema statement `{sql}`: {e}"))?;
+    }
+
+    ensure_workspace_support(pool).await?;
+    ensure_board_icon_column(pool).await?;
+    ensure_board_emoji_color_columns(pool).await?;
+    ensure_card_attachments_column(pool).await?;
+    ensure_card_remind_at_column(pool).await?;
+    ensure_card_estimate_column(pool).await?;
+    ensure_column_customization_columns(pool).await?;
+    ensure_notes_board_id_column(pool).await?;
+    ensure_board_favorite_column(pool).await?;
+    ensure_attachment_external_column(pool).await?;
+    ensure_board_stats_cache(pool).await?;
+    ensure_column_wip_mode_column(pool).await?;
+    ensure_search_index(pool).await?;
+    ensure_column_intake_flag(pool).await?;
+    ensure_activity_log_table(pool).await?;
+    ensure_activity_entity_index(pool).await?;
+    ensure_column_kind_column(pool).await?;
+    ensure_workspace_position_column(pool).await?;
+    ensure_card_remind_recurrence_column(pool).await?;
+    ensure_tag_position_column(pool).await?;
+    prune_deletions(pool).await?;
+
+    sqlx::query(&format!("PRAGMA user_version = {SCHEMA_VERSION}"))
+        .execute(pool)
+        .await
+        .map_err(|e| format!("Failed to record schema version: {e}"))?;
+
+    Ok(())
+}
+
+async fn ensure_board_icon_column(pool: &DbPool) -> Result<(), String> {
+    let column_exists = sqlx::query_scalar::<_, Option<i64>>(
+        "SELECT 1 FROM pragma_table_info('kanban_boards') WHERE name = 'icon' LIMIT 1",
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| format!("Failed to inspect kanban_boards schema: {e}"))?
+    .flatten()
+    .is_some();
+
+    if !column_exists {
+        sqlx::query("ALTER TABLE kanban_boards ADD COLUMN icon TEXT")
+            .execute(pool)
+            .await
+            .map_err(|e| format!("Failed to add icon column to kanban_boards: {e}"))?;
+    }
+
+    sqlx::query("UPDATE kanban_boards SET icon = ? WHERE icon IS NULL OR TRIM(icon) = ''")
+        .bind(DEFAULT_BOARD_ICON)
+        .execute(pool)
+        .await
+        .map_err(|e| format!("Failed to backfill board icons: {e}"))?;
+
+    Ok(())
+}
+
+async fn ensure_board_emoji_color_columns(pool: &DbPool) -> Result<(), String> {
+    let emoji_exists = sqlx::query_scalar::<_, Option<i64>>(
+        "SELECT 1 FROM pragma_table_info('kanban_boards') WHERE name = 'emoji' LIMIT 1",
+    )
+    .fetch_optional(pool)
+    .await
     .map_err(|e| format!("Failed to inspect kanban_boards schema: {e}"))?
     .flatten()
     .is_some();
@@ -1423,6 +2805,26 @@ async fn ensure_card_remind_at_column(pool: &DbPool) -> Result<(), String> {
     Ok(())
 }
 
+async fn ensure_card_estimate_column(pool: &DbPool) -> Result<(), String> {
+    let column_exists = sqlx::query_scalar::<_, Option<i64>>(
+        "SELECT 1 FROM pragma_table_info('kanban_cards') WHERE name = 'estimate_minutes' LIMIT 1",
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| format!("Failed to inspect kanban_cards schema: {e}"))?
+    .flatten()
+    .is_some();
+
+    if !column_exists {
+        sqlx::query("ALTER TABLE kanban_cards ADD COLUMN estimate_minutes INTEGER")
+            .execute(pool)
+            .await
+            .map_err(|e| format!("Failed to add estimate_minutes column to kanban_cards: {e}"))?;
+    }
+
+    Ok(())
+}
+
 async fn ensure_column_customization_columns(pool: &DbPool) -> Result<(), String> {
     let color_exists = sqlx::query_scalar::<_, Option<i64>>(
         "SELECT 1 FROM pragma_table_info('kanban_columns') WHERE name = 'color' LIMIT 1",
@@ -1543,6 +2945,7 @@ fn map_workspace_row(row: SqliteRow) -> Result<Value, sqlx::Error> {
         "name": row.try_get::<String, _>("name")?,
         "color": row.try_get::<Option<String>, _>("color")?,
         "iconPath": row.try_get::<Option<String>, _>("icon_path")?,
+        "position": row.try_get::<i64, _>("position")?,
         "createdAt": row.try_get::<String, _>("created_at")?,
         "updatedAt": row.try_get::<String, _>("updated_at")?,
         "archivedAt": row.try_get::<Option<String>, _>("archived_at")?,
@@ -1574,6 +2977,7 @@ fn map_column_row(row: SqliteRow) -> Result<Value, sqlx::Error> {
         "title": row.try_get::<String, _>("title")?,
         "position": row.try_get::<i64, _>("position")?,
         "wipLimit": row.try_get::<Option<i64>, _>("wip_limit")?,
+        "wipMode": row.try_get::<String, _>("wip_mode")?,
         "color": row.try_get::<Option<String>, _>("color")?,
         "icon": row
             .try_get::<Option<String>, _>("icon")?
@@ -1583,6 +2987,8 @@ fn map_column_row(row: SqliteRow) -> Result<Value, sqlx::Error> {
             .try_get::<Option<i64>, _>("is_enabled")?
             .map(|value| value != 0)
             .unwrap_or(true),
+        "isDefaultIntake": row.try_get::<i64, _>("is_default_intake")? != 0,
+        "columnKind": row.try_get::<Option<String>, _>("column_kind")?,
         "createdAt": row.try_get::<String, _>("created_at")?,
         "updatedAt": row.try_get::<String, _>("updated_at")?,
         "archivedAt": row.try_get::<Option<String>, _>("archived_at")?,
@@ -1595,6 +3001,7 @@ fn map_tag_row(row: SqliteRow) -> Result<Value, sqlx::Error> {
         "boardId": row.try_get::<String, _>("board_id")?,
         "label": row.try_get::<String, _>("label")?,
         "color": row.try_get::<Option<String>, _>("color")?,
+        "position": row.try_get::<i64, _>("position")?,
         "createdAt": row.try_get::<String, _>("created_at")?,
         "updatedAt": row.try_get::<String, _>("updated_at")?,
     }))
@@ -1614,7 +3021,6 @@ fn map_subtask_row(row: SqliteRow) -> Result<Value, sqlx::Error> {
 }
 
 #[derive(Debug, Clone)]
-#[allow(dead_code)]
 struct AttachmentRecord {
     id: String,
     card_id: String,
@@ -1627,6 +3033,7 @@ struct AttachmentRecord {
     checksum: Option<String>,
     storage_path: String,
     thumbnail_path: Option<String>,
+    is_external: bool,
     created_at: String,
     updated_at: String,
 }
@@ -1645,6 +3052,7 @@ impl AttachmentRecord {
             checksum: row.try_get("checksum")?,
             storage_path: row.try_get("storage_path")?,
             thumbnail_path: row.try_get("thumbnail_path")?,
+            is_external: row.try_get::<i64, _>("is_external")? != 0,
             created_at: row.try_get("created_at")?,
             updated_at: row.try_get("updated_at")?,
         })
@@ -1663,6 +3071,7 @@ impl AttachmentRecord {
             "checksum": self.checksum,
             "storagePath": self.storage_path,
             "thumbnailPath": self.thumbnail_path,
+            "isExternal": self.is_external,
             "createdAt": self.created_at,
             "updatedAt": self.updated_at,
         })
@@ -1671,7 +3080,6 @@ impl AttachmentRecord {
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-#[allow(dead_code)]
 struct ListAttachmentsArgs {
     board_id: String,
     card_id: String,
@@ -1679,7 +3087,6 @@ struct ListAttachmentsArgs {
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-#[allow(dead_code)]
 struct ManageAttachmentVersionArgs {
     board_id: String,
     card_id: String,
@@ -1697,6 +3104,8 @@ fn map_card_row(row: SqliteRow) -> Result<Value, sqlx::Error> {
     let priority: String = row.try_get("priority")?;
     let due_date: Option<String> = row.try_get("due_date")?;
     let remind_at: Option<String> = row.try_get("remind_at")?;
+    let remind_recurrence: Option<String> = row.try_get("remind_recurrence")?;
+    let estimate_minutes: Option<i64> = row.try_get("estimate_minutes")?;
     let created_at: String = row.try_get("created_at")?;
     let updated_at: String = row.try_get("updated_at")?;
     let archived_at: Option<String> = row.try_get("archived_at")?;
@@ -1760,6 +3169,8 @@ fn map_card_row(row: SqliteRow) -> Result<Value, sqlx::Error> {
         "priority": priority,
         "dueDate": due_date,
         "remindAt": remind_at,
+        "remindRecurrence": remind_recurrence,
+        "estimateMinutes": estimate_minutes,
         "attachments": attachments,
         "createdAt": created_at,
         "updatedAt": updated_at,
@@ -1769,6 +3180,13 @@ fn map_card_row(row: SqliteRow) -> Result<Value, sqlx::Error> {
     }))
 }
 
+fn validate_estimate_minutes(estimate_minutes: Option<i64>) -> Result<(), String> {
+    if estimate_minutes.is_some_and(|minutes| minutes < 0) {
+        return Err("A estimativa do cartão não pode ser negativa.".to_string());
+    }
+    Ok(())
+}
+
 fn normalize_optional_text(value: Option<String>) -> Option<String> {
     value
         .map(|v| v.trim().to_string())
@@ -2005,7 +3423,7 @@ async fn set_card_tags_tx(
     }
 
     let mut fetch_builder = QueryBuilder::new(
-        "SELECT id, board_id, label, color, created_at, updated_at FROM kanban_tags WHERE board_id = ",
+        "SELECT id, board_id, label, color, position, created_at, updated_at FROM kanban_tags WHERE board_id = ",
     );
     fetch_builder.push_bind(board_id);
     fetch_builder.push(" AND id IN (");
@@ -2044,8 +3462,44 @@ async fn set_card_tags_tx(
 }
 
 #[tauri::command]
-async fn load_boards(pool: State<'_, DbPool>) -> Result<Vec<Value>, String> {
-    sqlx::query("SELECT id, workspace_id, title, description, icon, emoji, color, created_at, updated_at, archived_at FROM kanban_boards ORDER BY created_at ASC")
+async fn get_icon_options() -> Result<Value, String> {
+    Ok(json!({
+        "boardIcons": ALLOWED_BOARD_ICONS,
+        "columnIcons": ALLOWED_COLUMN_ICONS,
+        "defaultBoardIcon": DEFAULT_BOARD_ICON,
+        "defaultColumnIcon": DEFAULT_COLUMN_ICON,
+    }))
+}
+
+/// Board filter (board aliased `b`) hiding boards whose workspace is archived. A
+/// macro so the `const` search sections can `concat!` it.
+macro_rules! board_workspace_active_sql {
+    () => {
+        "NOT EXISTS (SELECT 1 FROM workspaces w WHERE w.id = b.workspace_id AND w.archived_at IS NOT NULL)"
+    };
+}
+
+const BOARD_WORKSPACE_ACTIVE_SQL: &str = board_workspace_active_sql!();
+
+/// Without `include_archived`, boards that are archived or whose workspace is
+/// archived are left out.
+#[tauri::command]
+async fn load_boards(
+    pool: State<'_, DbPool>,
+    include_archived: Option<bool>,
+) -> Result<Vec<Value>, String> {
+    let archived_filter = if include_archived.unwrap_or(false) {
+        String::new()
+    } else {
+        format!(" WHERE b.archived_at IS NULL AND {BOARD_WORKSPACE_ACTIVE_SQL}")
+    };
+    let sql = format!(
+        "SELECT b.id, b.workspace_id, b.title, b.description, b.icon, b.emoji, b.color, b.created_at, b.updated_at, b.archived_at
+        FROM kanban_boards b{archived_filter}
+        ORDER BY b.created_at ASC"
+    );
+
+    sqlx::query(&sql)
         .try_map(map_board_row)
         .fetch_all(&*pool)
         .await
@@ -2056,17 +3510,135 @@ async fn load_boards(pool: State<'_, DbPool>) -> Result<Vec<Value>, String> {
 }
 
 #[tauri::command]
-async fn rename_board(
+async fn load_boards_for_workspace(
     pool: State<'_, DbPool>,
-    id: String,
-    mut title: String,
-    description: Option<String>,
-) -> Result<(), String> {
-    title = title.trim().to_string();
-    if title.is_empty() {
-        return Err("O nome do quadro não pode ser vazio.".to_string());
+    workspace_id: String,
+    include_archived: Option<bool>,
+) -> Result<Vec<Value>, String> {
+    let workspace_exists =
+        sqlx::query_scalar::<_, Option<i64>>("SELECT 1 FROM workspaces WHERE id = ? LIMIT 1")
+            .bind(&workspace_id)
+            .fetch_optional(&*pool)
+            .await
+            .map_err(|e| format!("Falha ao verificar workspace: {e}"))?
+            .flatten()
+            .is_some();
+
+    if !workspace_exists {
+        return Err("Workspace não encontrado.".to_string());
     }
-    validate_string_input(&title, 200, "Nome do quadro")?;
+
+    let archived_filter = if include_archived.unwrap_or(false) {
+        ""
+    } else {
+        " AND archived_at IS NULL"
+    };
+    let sql = format!(
+        "SELECT id, workspace_id, title, description, icon, emoji, color, created_at, updated_at, archived_at FROM kanban_boards WHERE workspace_id = ?{archived_filter} ORDER BY created_at ASC"
+    );
+
+    sqlx::query(&sql)
+        .bind(&workspace_id)
+        .try_map(map_board_row)
+        .fetch_all(&*pool)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to load boards for workspace {workspace_id}: {e}");
+            e.to_string()
+        })
+}
+
+#[tauri::command]
+async fn get_board(pool: State<'_, DbPool>, id: String) -> Result<Value, String> {
+    sqlx::query(
+        "SELECT id, workspace_id, title, description, icon, emoji, color, created_at, updated_at, archived_at FROM kanban_boards WHERE id = ?",
+    )
+    .bind(&id)
+    .try_map(map_board_row)
+    .fetch_optional(&*pool)
+    .await
+    .map_err(|e| {
+        log::error!("Failed to load board {id}: {e}");
+        e.to_string()
+    })?
+    .ok_or_else(|| "Quadro não encontrado.".to_string())
+}
+
+#[tauri::command]
+async fn load_boards_with_stats<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+) -> Result<Vec<Value>, String> {
+    let keywords = load_done_column_keywords(&app).await;
+    let done_predicate = done_column_predicate("col", &keywords);
+
+    let query = format!(
+        "SELECT b.id, b.workspace_id, b.title, b.description, b.icon, b.emoji, b.color, b.created_at, b.updated_at, b.archived_at,
+            (SELECT COUNT(*) FROM kanban_cards c WHERE c.board_id = b.id AND c.archived_at IS NULL) AS active_cards,
+            (SELECT COUNT(*) FROM kanban_cards c JOIN kanban_columns col ON col.id = c.column_id
+                WHERE c.board_id = b.id AND c.archived_at IS NULL AND {done_predicate}) AS done_cards
+        FROM kanban_boards b
+        WHERE b.archived_at IS NULL AND {BOARD_WORKSPACE_ACTIVE_SQL}
+        ORDER BY b.created_at ASC"
+    );
+
+    let mut statement = sqlx::query(&query);
+    for pattern in done_keyword_patterns(&keywords) {
+        statement = statement.bind(pattern);
+    }
+
+    let rows = statement
+        .fetch_all(&*pool)
+        .await
+        .map_err(|e| format!("Falha ao carregar quadros: {e}"))?;
+
+    let mut boards = Vec::with_capacity(rows.len());
+    for row in rows {
+        let active_cards: i64 = row.try_get("active_cards").map_err(|e| e.to_string())?;
+        let done_cards: i64 = row.try_get("done_cards").map_err(|e| e.to_string())?;
+        let mut board = map_board_row(row).map_err(|e| format!("Falha ao mapear quadro: {e}"))?;
+        board["activeCards"] = json!(active_cards);
+        board["doneCards"] = json!(done_cards);
+        board["completionPercent"] = json!(completion_percent(done_cards, active_cards));
+        boards.push(board);
+    }
+
+    Ok(boards)
+}
+
+#[tauri::command]
+async fn get_last_active_board(pool: State<'_, DbPool>) -> Result<Option<Value>, String> {
+    sqlx::query(concat!(
+        "SELECT id, workspace_id, title, description, icon, emoji, color, created_at, updated_at, archived_at
+        FROM kanban_boards b
+        WHERE b.archived_at IS NULL AND ",
+        board_workspace_active_sql!(),
+        "
+        ORDER BY MAX(
+            b.updated_at,
+            COALESCE((SELECT MAX(c.updated_at) FROM kanban_cards c WHERE c.board_id = b.id), b.updated_at),
+            COALESCE((SELECT MAX(n.updated_at) FROM notes n WHERE n.board_id = b.id), b.updated_at)
+        ) DESC
+        LIMIT 1",
+    ))
+    .try_map(map_board_row)
+    .fetch_optional(&*pool)
+    .await
+    .map_err(|e| format!("Falha ao carregar último quadro ativo: {e}"))
+}
+
+#[tauri::command]
+async fn rename_board(
+    pool: State<'_, DbPool>,
+    id: String,
+    mut title: String,
+    description: Option<String>,
+) -> Result<(), String> {
+    title = title.trim().to_string();
+    if title.is_empty() {
+        return Err("O nome do quadro não pode ser vazio.".to_string());
+    }
+    validate_string_input(&title, 200, "Nome do quadro")?;
 
     let normalized_description = normalize_optional_text(description);
 
@@ -2117,6 +3689,71 @@ async fn update_board_icon(
     Ok(())
 }
 
+#[tauri::command]
+async fn toggle_board_favorite(pool: State<'_, DbPool>, id: String) -> Result<bool, String> {
+    let result = sqlx::query(
+        "UPDATE kanban_boards SET is_favorite = CASE WHEN is_favorite = 1 THEN 0 ELSE 1 END, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
+    )
+    .bind(&id)
+    .execute(&*pool)
+    .await
+    .map_err(|e| {
+        log::error!("Failed to toggle favorite for board {id}: {e}");
+        e.to_string()
+    })?;
+
+    if result.rows_affected() == 0 {
+        return Err("Quadro não encontrado.".to_string());
+    }
+
+    sqlx::query_scalar::<_, bool>("SELECT is_favorite FROM kanban_boards WHERE id = ?")
+        .bind(&id)
+        .fetch_one(&*pool)
+        .await
+        .map_err(|e| format!("Falha ao carregar quadro: {e}"))
+}
+
+/// Archived boards keep all their data; they are only hidden from board lists.
+#[tauri::command]
+async fn archive_board(pool: State<'_, DbPool>, id: String) -> Result<(), String> {
+    let result = sqlx::query(
+        "UPDATE kanban_boards SET archived_at = COALESCE(archived_at, strftime('%Y-%m-%dT%H:%M:%fZ', 'now')), updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
+    )
+    .bind(&id)
+    .execute(&*pool)
+    .await
+    .map_err(|e| {
+        log::error!("Failed to archive board {id}: {e}");
+        e.to_string()
+    })?;
+
+    if result.rows_affected() == 0 {
+        return Err("Quadro não encontrado.".to_string());
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+async fn unarchive_board(pool: State<'_, DbPool>, id: String) -> Result<(), String> {
+    let result = sqlx::query(
+        "UPDATE kanban_boards SET archived_at = NULL, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
+    )
+    .bind(&id)
+    .execute(&*pool)
+    .await
+    .map_err(|e| {
+        log::error!("Failed to unarchive board {id}: {e}");
+        e.to_string()
+    })?;
+
+    if result.rows_affected() == 0 {
+        return Err("Quadro não encontrado.".to_string());
+    }
+
+    Ok(())
+}
+
 #[tauri::command]
 async fn update_board_workspace(
     pool: State<'_, DbPool>,
@@ -2164,9 +3801,21 @@ async fn update_board_workspace(
 
 #[tauri::command]
 async fn delete_board(pool: State<'_, DbPool>, id: String) -> Result<(), String> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
+
+    let title = sqlx::query_scalar::<_, String>("SELECT title FROM kanban_boards WHERE id = ?")
+        .bind(&id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao carregar quadro: {e}"))?
+        .ok_or_else(|| "Quadro não encontrado.".to_string())?;
+
     let result = sqlx::query("DELETE FROM kanban_boards WHERE id = ?")
         .bind(&id)
-        .execute(&*pool)
+        .execute(&mut *tx)
         .await
         .map_err(|e| {
             log::error!("Failed to delete board {id}: {e}");
@@ -2177,6 +3826,13 @@ async fn delete_board(pool: State<'_, DbPool>, id: String) -> Result<(), String>
         return Err("Quadro não encontrado.".to_string());
     }
 
+    record_deletion_tx(&mut tx, "board", &id, &id).await?;
+    log_activity_tx(&mut tx, &id, "board", &id, "board_deleted", &title, None).await?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
+
     Ok(())
 }
 
@@ -2228,6 +3884,11 @@ async fn create_board(pool: State<'_, DbPool>, args: CreateBoardArgs) -> Result<
     let normalized_emoji = normalize_optional_text(args.emoji);
     let normalized_color = normalize_optional_text(args.color);
 
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
+
     sqlx::query(
         "INSERT INTO kanban_boards (id, workspace_id, title, description, icon, emoji, color, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))",
     )
@@ -2238,18 +3899,247 @@ async fn create_board(pool: State<'_, DbPool>, args: CreateBoardArgs) -> Result<
     .bind(normalized_icon)
     .bind(normalized_emoji)
     .bind(normalized_color)
-    .execute(&*pool)
+    .execute(&mut *tx)
     .await
-    .map(|_| ())
     .map_err(|e| {
         log::error!("Failed to create board: {e}");
         e.to_string()
-    })
+    })?;
+
+    log_activity_tx(
+        &mut tx,
+        &args.id,
+        "board",
+        &args.id,
+        "board_created",
+        &title,
+        None,
+    )
+    .await?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
+
+    Ok(())
+}
+
+/// Copies a board with fresh ids for every entity. Attachments are never copied;
+/// cards (with subtasks and tag links) only when `include_cards` is set.
+#[tauri::command]
+async fn duplicate_board(
+    pool: State<'_, DbPool>,
+    source_board_id: String,
+    new_board_id: String,
+    new_title: String,
+    workspace_id: String,
+    include_cards: Option<bool>,
+) -> Result<Value, String> {
+    let new_board_id = new_board_id.trim().to_string();
+    if new_board_id.is_empty() {
+        return Err("Identificador do quadro inválido.".to_string());
+    }
+
+    let title = new_title.trim().to_string();
+    if title.is_empty() {
+        return Err("O nome do quadro não pode ser vazio.".to_string());
+    }
+    validate_string_input(&title, 200, "Nome do quadro")?;
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
+
+    let workspace_exists =
+        sqlx::query_scalar::<_, Option<i64>>("SELECT 1 FROM workspaces WHERE id = ? LIMIT 1")
+            .bind(&workspace_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| format!("Falha ao verificar workspace: {e}"))?
+            .is_some();
+
+    if !workspace_exists {
+        return Err("Workspace não encontrado.".to_string());
+    }
+
+    let inserted = sqlx::query(
+        "INSERT INTO kanban_boards (id, workspace_id, title, description, icon, emoji, color, created_at, updated_at)
+        SELECT ?, ?, ?, description, icon, emoji, color, strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+        FROM kanban_boards WHERE id = ?",
+    )
+    .bind(&new_board_id)
+    .bind(&workspace_id)
+    .bind(&title)
+    .bind(&source_board_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("Falha ao duplicar quadro: {e}"))?;
+
+    if inserted.rows_affected() == 0 {
+        return Err("Quadro não encontrado.".to_string());
+    }
+
+    let column_ids = sqlx::query_scalar::<_, String>(
+        "SELECT id FROM kanban_columns WHERE board_id = ? ORDER BY position ASC",
+    )
+    .bind(&source_board_id)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| format!("Falha ao carregar colunas: {e}"))?;
+
+    let mut column_map: HashMap<String, String> = HashMap::new();
+    for old_column_id in column_ids {
+        let new_column_id = Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO kanban_columns (id, board_id, title, position, color, icon, is_enabled, wip_limit, wip_mode, is_default_intake, column_kind, created_at, updated_at, archived_at)
+            SELECT ?, ?, title, position, color, icon, is_enabled, wip_limit, wip_mode, is_default_intake, column_kind, strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), archived_at
+            FROM kanban_columns WHERE id = ?",
+        )
+        .bind(&new_column_id)
+        .bind(&new_board_id)
+        .bind(&old_column_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao duplicar coluna: {e}"))?;
+        column_map.insert(old_column_id, new_column_id);
+    }
+
+    let tag_ids = sqlx::query_scalar::<_, String>(
+        "SELECT id FROM kanban_tags WHERE board_id = ? ORDER BY created_at ASC",
+    )
+    .bind(&source_board_id)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| format!("Falha ao carregar tags: {e}"))?;
+
+    let mut tag_map: HashMap<String, String> = HashMap::new();
+    for old_tag_id in tag_ids {
+        let new_tag_id = Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO kanban_tags (id, board_id, label, color, position, created_at, updated_at)
+            SELECT ?, ?, label, color, position, strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+            FROM kanban_tags WHERE id = ?",
+        )
+        .bind(&new_tag_id)
+        .bind(&new_board_id)
+        .bind(&old_tag_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao duplicar tag: {e}"))?;
+        tag_map.insert(old_tag_id, new_tag_id);
+    }
+
+    if include_cards.unwrap_or(false) {
+        let cards = sqlx::query_as::<_, (String, String)>(
+            "SELECT id, column_id FROM kanban_cards WHERE board_id = ? AND archived_at IS NULL ORDER BY position ASC",
+        )
+        .bind(&source_board_id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao carregar cartões: {e}"))?;
+
+        for (old_card_id, old_column_id) in cards {
+            let Some(new_column_id) = column_map.get(&old_column_id) else {
+                continue;
+            };
+            let new_card_id = Uuid::new_v4().to_string();
+
+            sqlx::query(
+                "INSERT INTO kanban_cards (id, board_id, column_id, title, description, position, priority, due_date, remind_at, remind_recurrence, estimate_minutes, created_at, updated_at)
+                SELECT ?, ?, ?, title, description, position, priority, due_date, remind_at, remind_recurrence, estimate_minutes, strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+                FROM kanban_cards WHERE id = ?",
+            )
+            .bind(&new_card_id)
+            .bind(&new_board_id)
+            .bind(new_column_id)
+            .bind(&old_card_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Falha ao duplicar cartão: {e}"))?;
+
+            let subtask_ids = sqlx::query_scalar::<_, String>(
+                "SELECT id FROM kanban_subtasks WHERE card_id = ? ORDER BY position ASC",
+            )
+            .bind(&old_card_id)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(|e| format!("Falha ao carregar subtarefas: {e}"))?;
+
+            for old_subtask_id in subtask_ids {
+                sqlx::query(
+                    "INSERT INTO kanban_subtasks (id, board_id, card_id, title, is_completed, position, created_at, updated_at)
+                    SELECT ?, ?, ?, title, is_completed, position, strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+                    FROM kanban_subtasks WHERE id = ?",
+                )
+                .bind(Uuid::new_v4().to_string())
+                .bind(&new_board_id)
+                .bind(&new_card_id)
+                .bind(&old_subtask_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| format!("Falha ao duplicar subtarefa: {e}"))?;
+            }
+
+            let card_tag_ids = sqlx::query_scalar::<_, String>(
+                "SELECT tag_id FROM kanban_card_tags WHERE card_id = ?",
+            )
+            .bind(&old_card_id)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(|e| format!("Falha ao carregar tags do cartão: {e}"))?;
+
+            for old_tag_id in card_tag_ids {
+                let Some(new_tag_id) = tag_map.get(&old_tag_id) else {
+                    continue;
+                };
+                sqlx::query("INSERT INTO kanban_card_tags (card_id, tag_id) VALUES (?, ?)")
+                    .bind(&new_card_id)
+                    .bind(new_tag_id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| format!("Falha ao associar tag ao cartão: {e}"))?;
+            }
+        }
+
+        for new_column_id in column_map.values() {
+            normalize_card_positions_tx(&mut tx, new_column_id)
+                .await
+                .map_err(|e| format!("Falha ao normalizar posições dos cartões: {e}"))?;
+        }
+    }
+
+    let board = sqlx::query(
+        "SELECT id, workspace_id, title, description, icon, emoji, color, created_at, updated_at, archived_at FROM kanban_boards WHERE id = ?",
+    )
+    .bind(&new_board_id)
+    .try_map(map_board_row)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| format!("Falha ao carregar quadro duplicado: {e}"))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
+
+    Ok(board)
 }
 
 #[tauri::command]
-async fn load_workspaces(pool: State<'_, DbPool>) -> Result<Vec<Value>, String> {
-    sqlx::query("SELECT id, name, color, icon_path, created_at, updated_at, archived_at FROM workspaces ORDER BY created_at ASC")
+async fn load_workspaces(
+    pool: State<'_, DbPool>,
+    include_archived: Option<bool>,
+) -> Result<Vec<Value>, String> {
+    let archived_filter = if include_archived.unwrap_or(false) {
+        ""
+    } else {
+        " WHERE archived_at IS NULL"
+    };
+    let sql = format!(
+        "SELECT id, name, color, icon_path, position, created_at, updated_at, archived_at FROM workspaces{archived_filter} ORDER BY position ASC, created_at ASC"
+    );
+
+    sqlx::query(&sql)
         .try_map(map_workspace_row)
         .fetch_all(&*pool)
         .await
@@ -2260,8 +4150,8 @@ async fn load_workspaces(pool: State<'_, DbPool>) -> Result<Vec<Value>, String>
 }
 
 #[tauri::command]
-async fn create_workspace(
-    app: AppHandle,
+async fn create_workspace<R: Runtime>(
+    app: AppHandle<R>,
     pool: State<'_, DbPool>,
     args: CreateWorkspaceArgs,
 ) -> Result<Value, String> {
@@ -2296,7 +4186,7 @@ async fn create_workspace(
     };
 
     let insert_result = sqlx::query(
-        "INSERT INTO workspaces (id, name, color, icon_path, created_at, updated_at) VALUES (?, ?, ?, ?, strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))",
+        "INSERT INTO workspaces (id, name, color, icon_path, position, created_at, updated_at) VALUES (?, ?, ?, ?, (SELECT COALESCE(MAX(position), -1) + 1 FROM workspaces), strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))",
     )
     .bind(workspace_id)
     .bind(&name)
@@ -2313,7 +4203,7 @@ async fn create_workspace(
         return Err(error.to_string());
     }
 
-    sqlx::query("SELECT id, name, color, icon_path, created_at, updated_at, archived_at FROM workspaces WHERE id = ?")
+    sqlx::query("SELECT id, name, color, icon_path, position, created_at, updated_at, archived_at FROM workspaces WHERE id = ?")
         .bind(workspace_id)
         .try_map(map_workspace_row)
         .fetch_one(&*pool)
@@ -2381,8 +4271,8 @@ async fn update_workspace(
 }
 
 #[tauri::command]
-async fn delete_workspace(
-    app: AppHandle,
+async fn delete_workspace<R: Runtime>(
+    app: AppHandle<R>,
     pool: State<'_, DbPool>,
     id: String,
 ) -> Result<(), String> {
@@ -2436,25 +4326,198 @@ async fn delete_workspace(
     Ok(())
 }
 
+/// Archived workspaces keep their boards; they are only hidden from the
+/// workspace list.
 #[tauri::command]
-async fn update_workspace_icon(
-    app: AppHandle,
-    pool: State<'_, DbPool>,
-    workspace_id: String,
-    file_path: String,
-) -> Result<Value, String> {
-    let workspace_id = workspace_id.trim();
+async fn archive_workspace(pool: State<'_, DbPool>, id: String) -> Result<(), String> {
+    let workspace_id = id.trim();
     if workspace_id.is_empty() {
         return Err("Identificador do workspace inválido.".to_string());
     }
 
-    let existing_icon: Option<String> =
-        sqlx::query_scalar("SELECT icon_path FROM workspaces WHERE id = ?")
-            .bind(workspace_id)
-            .fetch_optional(&*pool)
-            .await
-            .map_err(|e| format!("Falha ao carregar workspace: {e}"))?
-            .flatten();
+    if workspace_id == DEFAULT_WORKSPACE_ID {
+        return Err("Não é possível arquivar o workspace padrão.".to_string());
+    }
+
+    let result = sqlx::query(
+        "UPDATE workspaces SET archived_at = COALESCE(archived_at, strftime('%Y-%m-%dT%H:%M:%fZ', 'now')), updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
+    )
+    .bind(workspace_id)
+    .execute(&*pool)
+    .await
+    .map_err(|e| {
+        log::error!("Failed to archive workspace {workspace_id}: {e}");
+        e.to_string()
+    })?;
+
+    if result.rows_affected() == 0 {
+        return Err("Workspace não encontrado.".to_string());
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+async fn unarchive_workspace(pool: State<'_, DbPool>, id: String) -> Result<(), String> {
+    let workspace_id = id.trim();
+    if workspace_id.is_empty() {
+        return Err("Identificador do workspace inválido.".to_string());
+    }
+
+    let result = sqlx::query(
+        "UPDATE workspaces SET archived_at = NULL, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
+    )
+    .bind(workspace_id)
+    .execute(&*pool)
+    .await
+    .map_err(|e| {
+        log::error!("Failed to unarchive workspace {workspace_id}: {e}");
+        e.to_string()
+    })?;
+
+    if result.rows_affected() == 0 {
+        return Err("Workspace não encontrado.".to_string());
+    }
+
+    Ok(())
+}
+
+/// `ordered_ids` must list every workspace, archived ones included, exactly once.
+#[tauri::command]
+async fn reorder_workspaces(
+    pool: State<'_, DbPool>,
+    ordered_ids: Vec<String>,
+) -> Result<(), String> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
+
+    let mut existing_ids: Vec<String> = sqlx::query_scalar("SELECT id FROM workspaces")
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao carregar workspaces: {e}"))?;
+    existing_ids.sort();
+
+    let mut requested_ids = ordered_ids.clone();
+    requested_ids.sort();
+
+    if requested_ids != existing_ids {
+        return Err(
+            "A nova ordem deve conter cada workspace existente exatamente uma vez.".to_string(),
+        );
+    }
+
+    for (index, workspace_id) in ordered_ids.iter().enumerate() {
+        sqlx::query(
+            "UPDATE workspaces SET position = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
+        )
+        .bind(index as i64)
+        .bind(workspace_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao reordenar workspaces: {e}"))?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+async fn merge_workspaces<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    source_id: String,
+    target_id: String,
+) -> Result<u64, String> {
+    let source_id = source_id.trim();
+    let target_id = target_id.trim();
+    if source_id.is_empty() || target_id.is_empty() {
+        return Err("Identificador do workspace inválido.".to_string());
+    }
+
+    if source_id == target_id {
+        return Err("Selecione workspaces diferentes para mesclar.".to_string());
+    }
+
+    if source_id == DEFAULT_WORKSPACE_ID {
+        return Err("Não é possível remover o workspace padrão.".to_string());
+    }
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
+
+    let source_icon: Option<String> =
+        sqlx::query_scalar::<_, Option<String>>("SELECT icon_path FROM workspaces WHERE id = ?")
+            .bind(source_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| format!("Falha ao carregar workspace de origem: {e}"))?
+            .ok_or_else(|| "Workspace de origem não encontrado.".to_string())?;
+
+    let target_exists =
+        sqlx::query_scalar::<_, Option<i64>>("SELECT 1 FROM workspaces WHERE id = ? LIMIT 1")
+            .bind(target_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| format!("Falha ao carregar workspace de destino: {e}"))?
+            .is_some();
+
+    if !target_exists {
+        return Err("Workspace de destino não encontrado.".to_string());
+    }
+
+    let moved = sqlx::query(
+        "UPDATE kanban_boards SET workspace_id = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE workspace_id = ?",
+    )
+    .bind(target_id)
+    .bind(source_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("Falha ao mover quadros: {e}"))?
+    .rows_affected();
+
+    sqlx::query("DELETE FROM workspaces WHERE id = ?")
+        .bind(source_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao remover workspace de origem: {e}"))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
+
+    if let Some(relative) = source_icon {
+        let _ = remove_workspace_icon_file(&app, &relative);
+    }
+
+    Ok(moved)
+}
+
+#[tauri::command]
+async fn update_workspace_icon<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    workspace_id: String,
+    file_path: String,
+) -> Result<Value, String> {
+    let workspace_id = workspace_id.trim();
+    if workspace_id.is_empty() {
+        return Err("Identificador do workspace inválido.".to_string());
+    }
+
+    let existing_icon: Option<String> =
+        sqlx::query_scalar("SELECT icon_path FROM workspaces WHERE id = ?")
+            .bind(workspace_id)
+            .fetch_optional(&*pool)
+            .await
+            .map_err(|e| format!("Falha ao carregar workspace: {e}"))?
+            .flatten();
 
     let new_icon = copy_workspace_icon(&app, workspace_id, &file_path)?;
 
@@ -2476,7 +4539,7 @@ async fn update_workspace_icon(
         let _ = remove_workspace_icon_file(&app, &previous);
     }
 
-    sqlx::query("SELECT id, name, color, icon_path, created_at, updated_at, archived_at FROM workspaces WHERE id = ?")
+    sqlx::query("SELECT id, name, color, icon_path, position, created_at, updated_at, archived_at FROM workspaces WHERE id = ?")
         .bind(workspace_id)
         .try_map(map_workspace_row)
         .fetch_one(&*pool)
@@ -2488,8 +4551,8 @@ async fn update_workspace_icon(
 }
 
 #[tauri::command]
-async fn remove_workspace_icon(
-    app: AppHandle,
+async fn remove_workspace_icon<R: Runtime>(
+    app: AppHandle<R>,
     pool: State<'_, DbPool>,
     workspace_id: String,
 ) -> Result<Value, String> {
@@ -2525,7 +4588,7 @@ async fn remove_workspace_icon(
         let _ = remove_workspace_icon_file(&app, &relative);
     }
 
-    sqlx::query("SELECT id, name, color, icon_path, created_at, updated_at, archived_at FROM workspaces WHERE id = ?")
+    sqlx::query("SELECT id, name, color, icon_path, position, created_at, updated_at, archived_at FROM workspaces WHERE id = ?")
         .bind(workspace_id)
         .try_map(map_workspace_row)
         .fetch_one(&*pool)
@@ -2537,127 +4600,471 @@ async fn remove_workspace_icon(
 }
 
 #[tauri::command]
-async fn load_columns(pool: State<'_, DbPool>, board_id: String) -> Result<Vec<Value>, String> {
-    sqlx::query("SELECT id, board_id, title, position, color, icon, is_enabled, wip_limit, created_at, updated_at, archived_at FROM kanban_columns WHERE board_id = ? ORDER BY position ASC")
-        .bind(board_id)
-        .try_map(map_column_row)
-        .fetch_all(&*pool)
-        .await
-        .map_err(|e| {
-            log::error!("Failed to load columns: {e}");
-            e.to_string()
-        })
-}
-
-#[tauri::command]
-#[allow(clippy::too_many_arguments)]
-async fn create_column(
+async fn verify_workspace_icons<R: Runtime>(
+    app: AppHandle<R>,
     pool: State<'_, DbPool>,
-    id: String,
-    board_id: String,
-    mut title: String,
-    position: i64,
-    color: Option<String>,
-    icon: Option<String>,
-    is_enabled: Option<bool>,
-    wip_limit: Option<i64>,
-) -> Result<(), String> {
-    title = title.trim().to_string();
-    if title.is_empty() {
-        return Err("O nome da coluna não pode ser vazio.".to_string());
-    }
-    validate_string_input(&title, 200, "Nome da coluna")?;
+    heal: Option<bool>,
+) -> Result<Vec<Value>, String> {
+    let heal = heal.unwrap_or(false);
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data directory: {e}"))?;
 
-    let normalized_color = normalize_column_color(color)?;
-    let normalized_icon = normalize_column_icon(icon)?;
-    let normalized_is_enabled = is_enabled.unwrap_or(true);
+    let workspaces = sqlx::query_as::<_, (String, String)>(
+        "SELECT id, icon_path FROM workspaces WHERE icon_path IS NOT NULL AND TRIM(icon_path) <> ''",
+    )
+    .fetch_all(&*pool)
+    .await
+    .map_err(|e| format!("Falha ao carregar workspaces: {e}"))?;
 
-    let normalized_wip_limit = match wip_limit {
-        Some(limit) if limit < 1 => {
-            return Err("O limite WIP deve ser um número inteiro positivo.".to_string());
+    let mut affected = Vec::new();
+    for (workspace_id, icon_path) in workspaces {
+        if app_data_dir.join(&icon_path).is_file() {
+            continue;
         }
-        Some(limit) => Some(limit),
-        None => None,
-    };
-
-    let mut tx = pool
-        .begin()
-        .await
-        .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
 
-    let max_position = sqlx::query_scalar::<_, Option<i64>>(
-        "SELECT MAX(position) FROM kanban_columns WHERE board_id = ?",
-    )
-    .bind(&board_id)
-    .fetch_one(&mut *tx)
-    .await
-    .map_err(|e| format!("Falha ao obter posição máxima das colunas: {e}"))?
-    .unwrap_or(-1);
+        if heal {
+            sqlx::query(
+                "UPDATE workspaces SET icon_path = NULL, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
+            )
+            .bind(&workspace_id)
+            .execute(&*pool)
+            .await
+            .map_err(|e| format!("Falha ao limpar ícone do workspace: {e}"))?;
+        }
 
-    let mut normalized_position = position;
-    if normalized_position < 0 || normalized_position > max_position + 1 {
-        normalized_position = max_position + 1;
+        affected.push(json!({
+            "workspaceId": workspace_id,
+            "iconPath": icon_path,
+            "cleared": heal,
+        }));
     }
 
-    let duplicate = sqlx::query_scalar::<_, Option<i64>>(
-        "SELECT 1 FROM kanban_columns WHERE board_id = ? AND position = ? LIMIT 1",
-    )
-    .bind(&board_id)
-    .bind(normalized_position)
-    .fetch_optional(&mut *tx)
-    .await
-    .map_err(|e| format!("Falha ao verificar posições duplicadas: {e}"))?;
+    Ok(affected)
+}
 
-    if duplicate.is_some() {
-        return Err(format!(
-            "Já existe uma coluna na posição {}. Ajuste a ordem e tente novamente.",
-            normalized_position
-        ));
-    }
+/// A cropped icon is written before `set_workspace_icon_path` records it, so the
+/// sweep leaves recent files alone rather than racing that update.
+const WORKSPACE_ICON_SWEEP_GRACE: Duration = Duration::from_secs(5 * 60);
 
-    sqlx::query(
-        "INSERT INTO kanban_columns (id, board_id, title, position, color, icon, is_enabled, wip_limit, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))",
-    )
-    .bind(&id)
-    .bind(&board_id)
-    .bind(&title)
-    .bind(normalized_position)
-    .bind(normalized_color.as_deref())
-    .bind(normalized_icon.as_deref())
-    .bind(if normalized_is_enabled { 1 } else { 0 })
-    .bind(normalized_wip_limit)
-    .execute(&mut *tx)
-    .await
-    .map_err(|e| format!("Falha ao criar coluna: {e}"))?;
+/// Removes files named after a workspace id (`<id>_...`) other than `keep`. With
+/// `min_age`, files modified more recently than that are kept as well.
+fn remove_stray_workspace_icons(
+    icons_dir: &Path,
+    workspace_id: &str,
+    keep: Option<&str>,
+    min_age: Option<Duration>,
+) -> u32 {
+    let Ok(entries) = fs::read_dir(icons_dir) else {
+        return 0;
+    };
 
-    normalize_column_positions_tx(&mut tx, &board_id)
-        .await
-        .map_err(|e| format!("Falha ao normalizar posições das colunas: {e}"))?;
+    let prefix = format!("{workspace_id}_");
+    let keep = keep.map(|path| path.replace('\\', "/"));
 
-    tx.commit()
-        .await
-        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
+    let mut removed = 0u32;
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        let Some(filename) = entry.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        if !path.is_file() || !filename.starts_with(&prefix) {
+            continue;
+        }
 
-    Ok(())
+        let relative = format!("{WORKSPACE_ICON_DIR}/{filename}");
+        if keep.as_deref() == Some(relative.as_str()) {
+            continue;
+        }
+
+        if let Some(min_age) = min_age {
+            let age = entry
+                .metadata()
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .and_then(|modified| SystemTime::now().duration_since(modified).ok());
+            if age.is_none_or(|age| age < min_age) {
+                continue;
+            }
+        }
+
+        match fs::remove_file(&path) {
+            Ok(()) => removed += 1,
+            Err(e) => log::warn!("Failed to remove stray workspace icon {filename}: {e}"),
+        }
+    }
+
+    removed
 }
 
+/// Deletes stray icon files named after a workspace id (`<id>_...`) that are not the
+/// workspace's current `icon_path`. Returns how many files were removed.
 #[tauri::command]
-async fn update_column(pool: State<'_, DbPool>, args: UpdateColumnArgs) -> Result<(), String> {
-    let mut tx = pool
-        .begin()
-        .await
-        .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
+async fn consolidate_workspace_icons<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+) -> Result<u32, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data directory: {e}"))?;
+    let icons_dir = app_data_dir.join(WORKSPACE_ICON_DIR);
 
-    let existing_board =
-        sqlx::query_scalar::<_, Option<String>>("SELECT board_id FROM kanban_columns WHERE id = ?")
-            .bind(&args.id)
-            .fetch_one(&mut *tx)
+    if !icons_dir.is_dir() {
+        return Ok(0);
+    }
+
+    let workspaces =
+        sqlx::query_as::<_, (String, Option<String>)>("SELECT id, icon_path FROM workspaces")
+            .fetch_all(&*pool)
             .await
-            .map_err(|e| format!("Falha ao carregar coluna: {e}"))?
-            .ok_or_else(|| "Coluna não encontrada.".to_string())?;
+            .map_err(|e| format!("Falha ao carregar workspaces: {e}"))?;
 
-    if existing_board != args.board_id {
-        return Err("A coluna não pertence ao quadro informado.".to_string());
+    let removed: u32 = workspaces
+        .iter()
+        .map(|(workspace_id, icon_path)| {
+            remove_stray_workspace_icons(
+                &icons_dir,
+                workspace_id,
+                icon_path.as_deref(),
+                Some(WORKSPACE_ICON_SWEEP_GRACE),
+            )
+        })
+        .sum();
+
+    Ok(removed)
+}
+
+/// Disk used by a workspace: every stored attachment version on its boards plus the
+/// icon file. External attachments live outside the app data directory and are skipped.
+#[tauri::command]
+async fn get_workspace_storage<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    workspace_id: String,
+) -> Result<Value, String> {
+    let icon_path =
+        sqlx::query_scalar::<_, Option<String>>("SELECT icon_path FROM workspaces WHERE id = ?")
+            .bind(&workspace_id)
+            .fetch_optional(&*pool)
+            .await
+            .map_err(|e| format!("Falha ao carregar workspace: {e}"))?
+            .ok_or_else(|| "Workspace não encontrado.".to_string())?;
+
+    let (attachment_count, attachment_bytes) = sqlx::query_as::<_, (i64, i64)>(
+        "SELECT COUNT(att.id), COALESCE(SUM(att.size_bytes), 0)
+        FROM kanban_attachments att
+        JOIN kanban_boards b ON b.id = att.board_id
+        WHERE b.workspace_id = ? AND att.is_external = 0",
+    )
+    .bind(&workspace_id)
+    .fetch_one(&*pool)
+    .await
+    .map_err(|e| format!("Falha ao calcular armazenamento do workspace: {e}"))?;
+
+    let icon_bytes = match icon_path.filter(|path| !path.trim().is_empty()) {
+        Some(relative) => {
+            let app_data_dir = app
+                .path()
+                .app_data_dir()
+                .map_err(|e| format!("Failed to resolve app data directory: {e}"))?;
+            fs::metadata(app_data_dir.join(relative))
+                .map(|metadata| metadata.len() as i64)
+                .unwrap_or(0)
+        }
+        None => 0,
+    };
+
+    Ok(json!({
+        "workspaceId": workspace_id,
+        "attachmentCount": attachment_count,
+        "attachmentBytes": attachment_bytes,
+        "iconBytes": icon_bytes,
+        "totalBytes": attachment_bytes + icon_bytes,
+    }))
+}
+
+#[tauri::command]
+async fn load_columns(
+    pool: State<'_, DbPool>,
+    board_id: String,
+    enabled_only: Option<bool>,
+) -> Result<Vec<Value>, CommandError> {
+    let enabled_filter = if enabled_only.unwrap_or(false) {
+        " AND is_enabled = 1"
+    } else {
+        ""
+    };
+    let sql = format!(
+        "SELECT id, board_id, title, position, color, icon, is_enabled, wip_limit, wip_mode, is_default_intake, column_kind, created_at, updated_at, archived_at FROM kanban_columns WHERE board_id = ?{enabled_filter} ORDER BY position ASC"
+    );
+    sqlx::query(&sql)
+        .bind(board_id)
+        .try_map(map_column_row)
+        .fetch_all(&*pool)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to load columns: {e}");
+            CommandError::from(e)
+        })
+}
+
+#[tauri::command]
+async fn get_column(
+    pool: State<'_, DbPool>,
+    id: String,
+    board_id: String,
+) -> Result<Value, CommandError> {
+    sqlx::query(
+        "SELECT id, board_id, title, position, color, icon, is_enabled, wip_limit, wip_mode, is_default_intake, column_kind, created_at, updated_at, archived_at FROM kanban_columns WHERE id = ? AND board_id = ?",
+    )
+    .bind(&id)
+    .bind(&board_id)
+    .try_map(map_column_row)
+    .fetch_optional(&*pool)
+    .await
+    .map_err(|e| CommandError::Database(format!("Falha ao carregar coluna: {e}")))?
+    .ok_or_else(|| CommandError::NotFound("Coluna não encontrada.".to_string()))
+}
+
+async fn count_cards_in_column(
+    conn: &mut SqliteConnection,
+    column_id: &str,
+    board_id: &str,
+    include_archived: bool,
+) -> Result<i64, sqlx::Error> {
+    let archived_filter = if include_archived {
+        ""
+    } else {
+        " AND archived_at IS NULL"
+    };
+    let sql = format!(
+        "SELECT COUNT(*) FROM kanban_cards WHERE column_id = ? AND board_id = ?{archived_filter}"
+    );
+    sqlx::query_scalar::<_, i64>(&sql)
+        .bind(column_id)
+        .bind(board_id)
+        .fetch_one(&mut *conn)
+        .await
+}
+
+#[tauri::command]
+async fn count_column_cards(
+    pool: State<'_, DbPool>,
+    column_id: String,
+    board_id: String,
+    include_archived: Option<bool>,
+) -> Result<i64, CommandError> {
+    let mut conn = pool
+        .acquire()
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao obter conexão com o banco: {e}")))?;
+
+    count_cards_in_column(
+        &mut conn,
+        &column_id,
+        &board_id,
+        include_archived.unwrap_or(false),
+    )
+    .await
+    .map_err(|e| CommandError::Database(format!("Falha ao contar cartões da coluna: {e}")))
+}
+
+/// Next free position for a new item: `scope` is "card" (parent is a column),
+/// "column" (parent is a board) or "subtask" (parent is a card).
+#[tauri::command]
+async fn get_next_position(
+    pool: State<'_, DbPool>,
+    scope: String,
+    parent_id: String,
+) -> Result<i64, String> {
+    let sql = match scope.as_str() {
+        "card" => "SELECT COALESCE(MAX(position), -1) + 1 FROM kanban_cards WHERE column_id = ?",
+        "column" => "SELECT COALESCE(MAX(position), -1) + 1 FROM kanban_columns WHERE board_id = ?",
+        "subtask" => {
+            "SELECT COALESCE(MAX(position), -1) + 1 FROM kanban_subtasks WHERE card_id = ?"
+        }
+        _ => {
+            return Err("Escopo inválido. Utilize 'card', 'column' ou 'subtask'.".to_string());
+        }
+    };
+
+    sqlx::query_scalar::<_, i64>(sql)
+        .bind(&parent_id)
+        .fetch_one(&*pool)
+        .await
+        .map_err(|e| format!("Falha ao calcular próxima posição: {e}"))
+}
+
+async fn unique_column_titles_enforced<R: Runtime>(app: &AppHandle<R>) -> bool {
+    load_preferences(app.clone())
+        .await
+        .map(|preferences| preferences.enforce_unique_column_titles)
+        .unwrap_or_else(|e| {
+            log::warn!("Falling back to non-unique column titles: {e}");
+            false
+        })
+}
+
+/// Compares in Rust so the check stays case-insensitive beyond ASCII, which `LOWER` is not.
+async fn ensure_unique_column_title_tx(
+    tx: &mut Transaction<'_, Sqlite>,
+    board_id: &str,
+    title: &str,
+    exclude_column_id: Option<&str>,
+) -> Result<(), CommandError> {
+    let columns = sqlx::query_as::<_, (String, String)>(
+        "SELECT id, title FROM kanban_columns WHERE board_id = ?",
+    )
+    .bind(board_id)
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(|e| CommandError::Database(format!("Falha ao carregar colunas: {e}")))?;
+
+    let normalized = title.trim().to_lowercase();
+    let duplicate = columns.iter().any(|(id, existing)| {
+        Some(id.as_str()) != exclude_column_id && existing.trim().to_lowercase() == normalized
+    });
+
+    if duplicate {
+        return Err(CommandError::Validation(format!(
+            "Já existe uma coluna chamada \"{}\" neste quadro.",
+            title.trim()
+        )));
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn create_column<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    id: String,
+    board_id: String,
+    mut title: String,
+    position: i64,
+    color: Option<String>,
+    icon: Option<String>,
+    is_enabled: Option<bool>,
+    wip_limit: Option<i64>,
+) -> Result<(), CommandError> {
+    title = title.trim().to_string();
+    if title.is_empty() {
+        return Err(CommandError::Validation(
+            "O nome da coluna não pode ser vazio.".to_string(),
+        ));
+    }
+    validate_string_input(&title, 200, "Nome da coluna").map_err(CommandError::Validation)?;
+
+    let normalized_color = normalize_column_color(color).map_err(CommandError::Validation)?;
+    let normalized_icon = normalize_column_icon(icon).map_err(CommandError::Validation)?;
+    let normalized_is_enabled = is_enabled.unwrap_or(true);
+
+    let normalized_wip_limit = match wip_limit {
+        Some(limit) if limit < 1 => {
+            return Err(CommandError::Validation(
+                "O limite WIP deve ser um número inteiro positivo.".to_string(),
+            ));
+        }
+        Some(limit) => Some(limit),
+        None => None,
+    };
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao abrir transação: {e}")))?;
+
+    let max_position = sqlx::query_scalar::<_, Option<i64>>(
+        "SELECT MAX(position) FROM kanban_columns WHERE board_id = ?",
+    )
+    .bind(&board_id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| CommandError::Database(format!("Falha ao obter posição máxima das colunas: {e}")))?
+    .unwrap_or(-1);
+
+    let mut normalized_position = position;
+    if normalized_position < 0 || normalized_position > max_position + 1 {
+        normalized_position = max_position + 1;
+    }
+
+    if unique_column_titles_enforced(&app).await {
+        ensure_unique_column_title_tx(&mut tx, &board_id, &title, None).await?;
+    }
+
+    let duplicate = sqlx::query_scalar::<_, Option<i64>>(
+        "SELECT 1 FROM kanban_columns WHERE board_id = ? AND position = ? LIMIT 1",
+    )
+    .bind(&board_id)
+    .bind(normalized_position)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| CommandError::Database(format!("Falha ao verificar posições duplicadas: {e}")))?;
+
+    if duplicate.is_some() {
+        return Err(CommandError::Conflict(format!(
+            "Já existe uma coluna na posição {}. Ajuste a ordem e tente novamente.",
+            normalized_position
+        )));
+    }
+
+    sqlx::query(
+        "INSERT INTO kanban_columns (id, board_id, title, position, color, icon, is_enabled, wip_limit, wip_mode, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, 'hard', strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))",
+    )
+    .bind(&id)
+    .bind(&board_id)
+    .bind(&title)
+    .bind(normalized_position)
+    .bind(normalized_color.as_deref())
+    .bind(normalized_icon.as_deref())
+    .bind(if normalized_is_enabled { 1 } else { 0 })
+    .bind(normalized_wip_limit)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| CommandError::Database(format!("Falha ao criar coluna: {e}")))?;
+
+    normalize_column_positions_tx(&mut tx, &board_id)
+        .await
+        .map_err(|e| {
+            CommandError::Database(format!("Falha ao normalizar posições das colunas: {e}"))
+        })?;
+
+    tx.commit()
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao confirmar transação: {e}")))?;
+
+    emit_board_changed(&app, &board_id, "column_created");
+
+    Ok(())
+}
+
+#[tauri::command]
+async fn update_column<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    args: UpdateColumnArgs,
+) -> Result<(), CommandError> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao abrir transação: {e}")))?;
+
+    let existing_board =
+        sqlx::query_scalar::<_, String>("SELECT board_id FROM kanban_columns WHERE id = ?")
+            .bind(&args.id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| CommandError::Database(format!("Falha ao carregar coluna: {e}")))?
+            .ok_or_else(|| CommandError::NotFound("Coluna não encontrada.".to_string()))?;
+
+    if existing_board != args.board_id {
+        return Err(CommandError::Validation(
+            "A coluna não pertence ao quadro informado.".to_string(),
+        ));
     }
 
     let mut builder = QueryBuilder::new(
@@ -2668,16 +5075,22 @@ async fn update_column(pool: State<'_, DbPool>, args: UpdateColumnArgs) -> Resul
     if let Some(title) = args.title.as_ref() {
         let trimmed = title.trim();
         if trimmed.is_empty() {
-            return Err("O nome da coluna não pode ser vazio.".to_string());
+            return Err(CommandError::Validation(
+                "O nome da coluna não pode ser vazio.".to_string(),
+            ));
+        }
+        validate_string_input(trimmed, 200, "Nome da coluna").map_err(CommandError::Validation)?;
+        if unique_column_titles_enforced(&app).await {
+            ensure_unique_column_title_tx(&mut tx, &args.board_id, trimmed, Some(&args.id)).await?;
         }
-        validate_string_input(trimmed, 200, "Nome da coluna")?;
         builder.push(", title = ");
         builder.push_bind(trimmed.to_string());
         has_changes = true;
     }
 
     if let Some(color_payload) = args.color {
-        let normalized_color = normalize_column_color(color_payload)?;
+        let normalized_color =
+            normalize_column_color(color_payload).map_err(CommandError::Validation)?;
         builder.push(", color = ");
         if let Some(color) = normalized_color {
             builder.push_bind(color);
@@ -2688,7 +5101,8 @@ async fn update_column(pool: State<'_, DbPool>, args: UpdateColumnArgs) -> Resul
     }
 
     if let Some(icon_payload) = args.icon {
-        let normalized_icon = normalize_column_icon(icon_payload)?;
+        let normalized_icon =
+            normalize_column_icon(icon_payload).map_err(CommandError::Validation)?;
         builder.push(", icon = ");
         if let Some(icon) = normalized_icon {
             builder.push_bind(icon);
@@ -2715,437 +5129,163 @@ async fn update_column(pool: State<'_, DbPool>, args: UpdateColumnArgs) -> Resul
         .build()
         .execute(&mut *tx)
         .await
-        .map_err(|e| format!("Falha ao atualizar coluna: {e}"))?;
+        .map_err(|e| CommandError::Database(format!("Falha ao atualizar coluna: {e}")))?;
 
     tx.commit()
         .await
-        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
+        .map_err(|e| CommandError::Database(format!("Falha ao confirmar transação: {e}")))?;
 
     Ok(())
 }
 
+const VALID_COLUMN_KINDS: [&str; 5] = ["backlog", "todo", "in_progress", "review", "done"];
+
+/// Classifies a column for the statistics queries; an empty `kind` clears it.
 #[tauri::command]
-async fn delete_column(
+async fn set_column_kind(
     pool: State<'_, DbPool>,
     id: String,
     board_id: String,
-) -> Result<(), String> {
-    let mut tx = pool
-        .begin()
-        .await
-        .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
-
-    // Check if column exists and belongs to the board
-    let existing_board =
-        sqlx::query_scalar::<_, Option<String>>("SELECT board_id FROM kanban_columns WHERE id = ?")
-            .bind(&id)
-            .fetch_one(&mut *tx)
-            .await
-            .map_err(|e| format!("Falha ao carregar coluna: {e}"))?
-            .ok_or_else(|| "Coluna não encontrada.".to_string())?;
-
-    if existing_board != board_id {
-        return Err("A coluna não pertence ao quadro informado.".to_string());
-    }
+    kind: String,
+) -> Result<(), CommandError> {
+    let kind = kind.trim();
+    let normalized_kind = if kind.is_empty() {
+        None
+    } else if VALID_COLUMN_KINDS.contains(&kind) {
+        Some(kind)
+    } else {
+        return Err(CommandError::Validation(format!(
+            "Tipo de coluna inválido. Utilize um dos valores: {}.",
+            VALID_COLUMN_KINDS.join(", ")
+        )));
+    };
 
-    // Check if column has any cards
-    let card_count = sqlx::query_scalar::<_, i64>(
-        "SELECT COUNT(*) FROM kanban_cards WHERE column_id = ? AND archived_at IS NULL",
+    let result = sqlx::query(
+        "UPDATE kanban_columns SET column_kind = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ? AND board_id = ?",
     )
+    .bind(normalized_kind)
     .bind(&id)
-    .fetch_one(&mut *tx)
+    .bind(&board_id)
+    .execute(&*pool)
     .await
-    .map_err(|e| format!("Falha ao contar cartões da coluna: {e}"))?;
+    .map_err(|e| CommandError::Database(format!("Falha ao atualizar tipo da coluna: {e}")))?;
 
-    if card_count > 0 {
-        return Err(format!(
-            "Não é possível excluir a coluna pois ela possui {} cartão(es). Mova ou exclua os cartões primeiro.",
-            card_count
-        ));
+    if result.rows_affected() == 0 {
+        return Err(CommandError::NotFound("Coluna não encontrada.".to_string()));
     }
 
-    // Delete the column
-    sqlx::query("DELETE FROM kanban_columns WHERE id = ?")
-        .bind(&id)
-        .execute(&mut *tx)
-        .await
-        .map_err(|e| format!("Falha ao excluir coluna: {e}"))?;
-
-    // Normalize positions of remaining columns
-    normalize_column_positions_tx(&mut tx, &board_id)
-        .await
-        .map_err(|e| format!("Falha ao normalizar posições das colunas: {e}"))?;
-
-    tx.commit()
-        .await
-        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
-
     Ok(())
 }
 
+/// Switching to `hard` on a column already above its WIP limit is not applied
+/// unless `confirm` is set; the response carries the count so the UI can warn.
+fn validate_wip_mode(mode: &str) -> Result<(), String> {
+    match mode {
+        "hard" | "soft" => Ok(()),
+        _ => Err("Modo WIP inválido. Utilize 'hard' ou 'soft'.".to_string()),
+    }
+}
+
 #[tauri::command]
-async fn load_cards(pool: State<'_, DbPool>, board_id: String) -> Result<Vec<Value>, String> {
-    sqlx::query(
-        "SELECT
-            c.id,
-            c.board_id,
-            c.column_id,
-            c.title,
-            c.description,
-            c.position,
-            c.priority,
-            c.due_date,
-            c.remind_at,
-            c.attachments AS legacy_attachments,
-            (
-                SELECT json_group_array(
-                    json_object(
-                        'id', att.id,
-                        'boardId', att.board_id,
-                        'cardId', att.card_id,
-                        'version', att.version,
-                        'filename', att.filename,
-                        'originalName', att.original_name,
-                        'mimeType', att.mime_type,
-                        'sizeBytes', att.size_bytes,
-                        'checksum', att.checksum,
-                        'storagePath', att.storage_path,
-                        'thumbnailPath', att.thumbnail_path,
-                        'createdAt', att.created_at,
-                        'updatedAt', att.updated_at
-                    )
-                )
-                FROM kanban_attachments att
-                WHERE att.card_id = c.id
-                ORDER BY att.created_at ASC, att.version ASC
-            ) AS attachments_json,
-            c.created_at,
-            c.updated_at,
-            c.archived_at,
-            (
-                SELECT json_group_array(
-                    json_object(
-                        'id', sub.id,
-                        'boardId', sub.board_id,
-                        'cardId', sub.card_id,
-                        'title', sub.title,
-                        'isCompleted', CASE WHEN sub.is_completed <> 0 THEN 1 ELSE 0 END,
-                        'position', sub.position,
-                        'createdAt', sub.created_at,
-                        'updatedAt', sub.updated_at
-                    )
-                )
-                FROM (
-                    SELECT st.id, st.board_id, st.card_id, st.title, st.is_completed, st.position, st.created_at, st.updated_at
-                    FROM kanban_subtasks st
-                    WHERE st.card_id = c.id
-                    ORDER BY st.position ASC, st.created_at ASC
-                ) sub
-            ) AS subtasks_json,
-            (
-                SELECT json_group_array(
-                    json_object(
-                        'id', t.id,
-                        'boardId', t.board_id,
-                        'label', t.label,
-                        'color', t.color,
-                        'createdAt', t.created_at,
-                        'updatedAt', t.updated_at
-                    )
-                )
-                FROM kanban_card_tags ct
-                JOIN kanban_tags t ON t.id = ct.tag_id
-                WHERE ct.card_id = c.id
-            ) AS tags_json
-        FROM kanban_cards c
-        WHERE c.board_id = ?
-        ORDER BY c.position ASC",
+async fn set_column_wip_mode(
+    pool: State<'_, DbPool>,
+    board_id: String,
+    column_id: String,
+    mode: String,
+    confirm: Option<bool>,
+) -> Result<Value, CommandError> {
+    let mode = mode.trim().to_lowercase();
+    validate_wip_mode(&mode).map_err(CommandError::Validation)?;
+
+    let column = sqlx::query_as::<_, (String, Option<i64>, String)>(
+        "SELECT board_id, wip_limit, wip_mode FROM kanban_columns WHERE id = ?",
     )
-    .bind(&board_id)
-    .try_map(map_card_row)
-    .fetch_all(&*pool)
+    .bind(&column_id)
+    .fetch_optional(&*pool)
     .await
-    .map_err(|e| {
-        log::error!("Failed to load cards: {e}");
-        e.to_string()
-    })
-}
+    .map_err(|e| CommandError::Database(format!("Falha ao carregar coluna: {e}")))?;
 
-#[tauri::command]
-async fn load_tags(pool: State<'_, DbPool>, board_id: String) -> Result<Vec<Value>, String> {
-    sqlx::query(
-        "SELECT id, board_id, label, color, created_at, updated_at FROM kanban_tags WHERE board_id = ? ORDER BY label COLLATE NOCASE ASC",
-    )
-    .bind(&board_id)
-    .try_map(map_tag_row)
-    .fetch_all(&*pool)
-    .await
-    .map_err(|e| {
-        log::error!("Failed to load tags: {e}");
-        e.to_string()
-    })
-}
+    let Some((column_board_id, wip_limit, current_mode)) = column else {
+        return Err(CommandError::NotFound("Coluna não encontrada.".to_string()));
+    };
 
-#[tauri::command]
-async fn create_tag(pool: State<'_, DbPool>, args: CreateTagArgs) -> Result<Value, String> {
-    let label = args.label.trim().to_string();
-    if label.is_empty() {
-        return Err("O nome da tag não pode ser vazio.".to_string());
+    if column_board_id != board_id {
+        return Err(CommandError::Validation(
+            "A coluna não pertence ao quadro informado.".to_string(),
+        ));
     }
-    validate_string_input(&label, 100, "Nome da tag")?;
-
-    let normalized_color = normalize_tag_color(args.color)?;
-
-    sqlx::query(
-        "INSERT INTO kanban_tags (id, board_id, label, color, created_at, updated_at) VALUES (?, ?, ?, ?, strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))",
-    )
-    .bind(&args.id)
-    .bind(&args.board_id)
-    .bind(&label)
-    .bind(normalized_color.as_deref())
-    .execute(&*pool)
-    .await
-    .map_err(|e| {
-        log::error!("Failed to create tag: {e}");
-        e.to_string()
-    })?;
 
-    sqlx::query(
-        "SELECT id, board_id, label, color, created_at, updated_at FROM kanban_tags WHERE id = ?",
+    let card_count = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM kanban_cards WHERE column_id = ? AND archived_at IS NULL",
     )
-    .bind(&args.id)
+    .bind(&column_id)
     .fetch_one(&*pool)
     .await
-    .map_err(|e| {
-        log::error!("Failed to load created tag: {e}");
-        e.to_string()
-    })
-    .and_then(|row| map_tag_row(row).map_err(|e| e.to_string()))
-}
-
-#[tauri::command]
-async fn update_tag(pool: State<'_, DbPool>, args: UpdateTagArgs) -> Result<Value, String> {
-    let mut builder = QueryBuilder::<Sqlite>::new(
-        "UPDATE kanban_tags SET updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
-    );
-    let mut has_changes = false;
-
-    if let Some(label) = args.label.as_ref() {
-        let trimmed = label.trim();
-        if trimmed.is_empty() {
-            return Err("O nome da tag não pode ser vazio.".to_string());
-        }
-        validate_string_input(trimmed, 100, "Nome da tag")?;
-        builder.push(", label = ");
-        builder.push_bind(trimmed);
-        has_changes = true;
-    }
-
-    let mut color_binding: Option<Option<String>> = None;
-    if let Some(color_payload) = args.color.clone() {
-        let normalized = normalize_tag_color(color_payload)?;
-        color_binding = Some(normalized);
-        has_changes = true;
-    }
-
-    if let Some(normalized_color) = color_binding.as_ref() {
-        builder.push(", color = ");
-        builder.push_bind(normalized_color.as_deref());
-    }
+    .map_err(|e| CommandError::Database(format!("Falha ao contar cartões da coluna: {e}")))?;
 
-    if has_changes {
-        builder.push(" WHERE id = ");
-        builder.push_bind(&args.id);
-        builder.push(" AND board_id = ");
-        builder.push_bind(&args.board_id);
-
-        let result = builder.build().execute(&*pool).await.map_err(|e| {
-            log::error!("Failed to update tag: {e}");
-            e.to_string()
-        })?;
+    let over_limit = wip_limit.is_some_and(|limit| card_count > limit);
+    let needs_confirmation =
+        mode == "hard" && current_mode != "hard" && over_limit && !confirm.unwrap_or(false);
 
-        if result.rows_affected() == 0 {
-            return Err("Tag não encontrada.".to_string());
-        }
-    } else {
-        // Nothing to update, but ensure tag exists
-        let exists = sqlx::query_scalar::<_, Option<i64>>(
-            "SELECT 1 FROM kanban_tags WHERE id = ? AND board_id = ? LIMIT 1",
+    if !needs_confirmation {
+        sqlx::query(
+            "UPDATE kanban_columns SET wip_mode = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
         )
-        .bind(&args.id)
-        .bind(&args.board_id)
-        .fetch_optional(&*pool)
-        .await
-        .map_err(|e| {
-            log::error!("Failed to validate tag existence: {e}");
-            e.to_string()
-        })?
-        .flatten()
-        .is_some();
-
-        if !exists {
-            return Err("Tag não encontrada.".to_string());
-        }
-    }
-
-    sqlx::query(
-        "SELECT id, board_id, label, color, created_at, updated_at FROM kanban_tags WHERE id = ?",
-    )
-    .bind(&args.id)
-    .fetch_one(&*pool)
-    .await
-    .map_err(|e| {
-        log::error!("Failed to load updated tag: {e}");
-        e.to_string()
-    })
-    .and_then(|row| map_tag_row(row).map_err(|e| e.to_string()))
-}
-
-#[tauri::command]
-async fn delete_tag(pool: State<'_, DbPool>, args: DeleteTagArgs) -> Result<(), String> {
-    let result = sqlx::query("DELETE FROM kanban_tags WHERE id = ? AND board_id = ?")
-        .bind(&args.id)
-        .bind(&args.board_id)
+        .bind(&mode)
+        .bind(&column_id)
         .execute(&*pool)
         .await
-        .map_err(|e| {
-            log::error!("Failed to delete tag: {e}");
-            e.to_string()
-        })?;
-
-    if result.rows_affected() == 0 {
-        return Err("Tag não encontrada.".to_string());
+        .map_err(|e| CommandError::Database(format!("Falha ao atualizar modo WIP da coluna: {e}")))?;
     }
 
-    Ok(())
+    Ok(json!({
+        "columnId": column_id,
+        "mode": if needs_confirmation { current_mode } else { mode },
+        "applied": !needs_confirmation,
+        "cardCount": card_count,
+        "wipLimit": wip_limit,
+        "overLimit": over_limit,
+    }))
 }
 
 #[tauri::command]
-async fn set_card_tags(
-    pool: State<'_, DbPool>,
-    args: SetCardTagsArgs,
-) -> Result<Vec<Value>, String> {
-    let mut tx = pool
-        .begin()
-        .await
-        .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
-
-    let existing_board =
-        sqlx::query_scalar::<_, Option<String>>("SELECT board_id FROM kanban_cards WHERE id = ?")
-            .bind(&args.card_id)
-            .fetch_one(&mut *tx)
-            .await
-            .map_err(|e| format!("Falha ao carregar cartão: {e}"))?
-            .ok_or_else(|| "Cartão não encontrado.".to_string())?;
-
-    if existing_board != args.board_id {
-        return Err("O cartão não pertence ao quadro informado.".to_string());
-    }
-
-    let tags = set_card_tags_tx(&mut tx, &args.card_id, &args.board_id, &args.tag_ids)
-        .await
-        .map_err(|e| format!("Falha ao atualizar tags do cartão: {e}"))?;
-
-    tx.commit()
-        .await
-        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
-
-    Ok(tags)
-}
-#[tauri::command]
-#[allow(clippy::too_many_arguments)]
-async fn create_card(
+async fn set_default_intake_column(
     pool: State<'_, DbPool>,
-    id: String,
     board_id: String,
     column_id: String,
-    mut title: String,
-    description: Option<String>,
-    position: i64,
-    priority: String,
-    due_date: Option<String>,
-    tag_ids: Option<Vec<String>>,
 ) -> Result<(), String> {
-    title = title.trim().to_string();
-    if title.is_empty() {
-        return Err("O título do cartão não pode ser vazio.".to_string());
-    }
-    validate_string_input(&title, 200, "Título do cartão")?;
-    validate_priority(&priority)?;
-
-    let normalized_description = normalize_optional_text(description);
-
     let mut tx = pool
         .begin()
         .await
         .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
 
-    let stored_board_id =
-        sqlx::query_scalar::<_, Option<String>>("SELECT board_id FROM kanban_columns WHERE id = ?")
+    let column_board_id =
+        sqlx::query_scalar::<_, String>("SELECT board_id FROM kanban_columns WHERE id = ?")
             .bind(&column_id)
-            .fetch_one(&mut *tx)
+            .fetch_optional(&mut *tx)
             .await
-            .map_err(|e| format!("Falha ao validar coluna informada: {e}"))?;
-
-    match stored_board_id {
-        Some(db_board_id) if db_board_id == board_id => {}
-        Some(_) => return Err("A coluna informada não pertence ao quadro selecionado.".to_string()),
-        None => return Err("Coluna não encontrada.".to_string()),
-    }
-
-    let max_position = sqlx::query_scalar::<_, Option<i64>>(
-        "SELECT MAX(position) FROM kanban_cards WHERE column_id = ?",
-    )
-    .bind(&column_id)
-    .fetch_one(&mut *tx)
-    .await
-    .map_err(|e| format!("Falha ao obter posição máxima dos cartões: {e}"))?
-    .unwrap_or(-1);
+            .map_err(|e| format!("Falha ao carregar coluna: {e}"))?
+            .ok_or_else(|| "Coluna não encontrada.".to_string())?;
 
-    let mut normalized_position = position;
-    if normalized_position < 0 || normalized_position > max_position + 1 {
-        normalized_position = max_position + 1;
+    if column_board_id != board_id {
+        return Err("A coluna não pertence ao quadro informado.".to_string());
     }
 
-    let duplicate = sqlx::query_scalar::<_, Option<i64>>(
-        "SELECT 1 FROM kanban_cards WHERE column_id = ? AND position = ? LIMIT 1",
+    sqlx::query(
+        "UPDATE kanban_columns SET is_default_intake = 0, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE board_id = ? AND is_default_intake = 1",
     )
-    .bind(&column_id)
-    .bind(normalized_position)
-    .fetch_optional(&mut *tx)
+    .bind(&board_id)
+    .execute(&mut *tx)
     .await
-    .map_err(|e| format!("Falha ao verificar posições duplicadas de cartões: {e}"))?;
-
-    if duplicate.is_some() {
-        return Err(format!(
-            "Já existe um cartão na posição {} desta coluna. Ajuste a ordem e tente novamente.",
-            normalized_position
-        ));
-    }
+    .map_err(|e| format!("Falha ao limpar coluna de entrada padrão: {e}"))?;
 
     sqlx::query(
-        "INSERT INTO kanban_cards (id, board_id, column_id, title, description, position, priority, due_date, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))",
+        "UPDATE kanban_columns SET is_default_intake = 1, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
     )
-    .bind(&id)
-    .bind(&board_id)
     .bind(&column_id)
-    .bind(&title)
-    .bind(normalized_description)
-    .bind(normalized_position)
-    .bind(&priority)
-    .bind(due_date.filter(|v| !v.is_empty()))
     .execute(&mut *tx)
     .await
-    .map_err(|e| format!("Falha ao criar cartão: {e}"))?;
-
-    normalize_card_positions_tx(&mut tx, &column_id)
-        .await
-        .map_err(|e| format!("Falha ao normalizar posições dos cartões: {e}"))?;
-
-    let tag_ids_vec = tag_ids.unwrap_or_default();
-    set_card_tags_tx(&mut tx, &id, &board_id, &tag_ids_vec)
-        .await
-        .map_err(|e| format!("Falha ao associar tags ao cartão: {e}"))?;
+    .map_err(|e| format!("Falha ao definir coluna de entrada padrão: {e}"))?;
 
     tx.commit()
         .await
@@ -3155,2268 +5295,10980 @@ async fn create_card(
 }
 
 #[tauri::command]
-async fn delete_card(pool: State<'_, DbPool>, id: String, board_id: String) -> Result<(), String> {
+async fn delete_column<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    id: String,
+    board_id: String,
+) -> Result<(), CommandError> {
     let mut tx = pool
         .begin()
         .await
-        .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
+        .map_err(|e| CommandError::Database(format!("Falha ao abrir transação: {e}")))?;
 
-    let card_record = sqlx::query_as::<_, (String, String)>(
-        "SELECT column_id, board_id FROM kanban_cards WHERE id = ?",
-    )
-    .bind(&id)
-    .fetch_optional(&mut *tx)
-    .await
-    .map_err(|e| format!("Falha ao carregar cartão: {e}"))?;
+    // Check if column exists and belongs to the board
+    let existing_board =
+        sqlx::query_scalar::<_, String>("SELECT board_id FROM kanban_columns WHERE id = ?")
+            .bind(&id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| CommandError::Database(format!("Falha ao carregar coluna: {e}")))?
+            .ok_or_else(|| CommandError::NotFound("Coluna não encontrada.".to_string()))?;
 
-    let Some((column_id, stored_board_id)) = card_record else {
-        return Err("Cartão não encontrado.".to_string());
-    };
+    if existing_board != board_id {
+        return Err(CommandError::Validation(
+            "A coluna não pertence ao quadro informado.".to_string(),
+        ));
+    }
 
-    if stored_board_id != board_id {
-        return Err("O cartão não pertence ao quadro informado.".to_string());
+    // Check if column has any cards
+    let card_count = count_cards_in_column(&mut tx, &id, &board_id, false)
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao contar cartões da coluna: {e}")))?;
+
+    if card_count > 0 {
+        return Err(CommandError::Conflict(format!(
+            "Não é possível excluir a coluna pois ela possui {} cartão(es). Mova ou exclua os cartões primeiro.",
+            card_count
+        )));
     }
 
-    sqlx::query("DELETE FROM kanban_cards WHERE id = ?")
+    // Delete the column
+    sqlx::query("DELETE FROM kanban_columns WHERE id = ?")
         .bind(&id)
         .execute(&mut *tx)
         .await
-        .map_err(|e| format!("Falha ao excluir cartão: {e}"))?;
+        .map_err(|e| CommandError::Database(format!("Falha ao excluir coluna: {e}")))?;
 
-    normalize_card_positions_tx(&mut tx, &column_id)
+    record_deletion_tx(&mut tx, "column", &id, &board_id)
         .await
-        .map_err(|e| format!("Falha ao normalizar posições dos cartões: {e}"))?;
+        .map_err(CommandError::Database)?;
+
+    // Normalize positions of remaining columns
+    normalize_column_positions_tx(&mut tx, &board_id)
+        .await
+        .map_err(|e| {
+            CommandError::Database(format!("Falha ao normalizar posições das colunas: {e}"))
+        })?;
 
     tx.commit()
         .await
-        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
+        .map_err(|e| CommandError::Database(format!("Falha ao confirmar transação: {e}")))?;
+
+    emit_board_changed(&app, &board_id, "column_deleted");
 
     Ok(())
 }
 
-// Validation functions
-fn validate_filename(filename: &str) -> Result<(), String> {
-    // Regex pattern: only alphanumeric, dash, underscore, dot
-    let filename_pattern = Regex::new(r"^[a-zA-Z0-9_-]+(\.[a-zA-Z0-9]+)?$")
-        .map_err(|e| format!("Regex compilation error: {e}"))?;
+const CARD_SELECT_SQL: &str = "SELECT
+        c.id,
+        c.board_id,
+        c.column_id,
+        c.title,
+        c.description,
+        c.position,
+        c.priority,
+        c.due_date,
+        c.remind_at,
+        c.remind_recurrence,
+        c.estimate_minutes,
+        c.attachments AS legacy_attachments,
+        (
+            SELECT json_group_array(
+                json_object(
+                    'id', att.id,
+                    'boardId', att.board_id,
+                    'cardId', att.card_id,
+                    'version', att.version,
+                    'filename', att.filename,
+                    'originalName', att.original_name,
+                    'mimeType', att.mime_type,
+                    'sizeBytes', att.size_bytes,
+                    'checksum', att.checksum,
+                    'storagePath', att.storage_path,
+                    'thumbnailPath', att.thumbnail_path,
+                    'isExternal', CASE WHEN att.is_external <> 0 THEN 1 ELSE 0 END,
+                    'createdAt', att.created_at,
+                    'updatedAt', att.updated_at
+                )
+            )
+            FROM kanban_attachments att
+            WHERE att.card_id = c.id
+            AND att.version = (SELECT MAX(latest.version) FROM kanban_attachments latest WHERE latest.id = att.id)
+            ORDER BY att.created_at ASC, att.version ASC
+        ) AS attachments_json,
+        c.created_at,
+        c.updated_at,
+        c.archived_at,
+        (
+            SELECT json_group_array(
+                json_object(
+                    'id', sub.id,
+                    'boardId', sub.board_id,
+                    'cardId', sub.card_id,
+                    'title', sub.title,
+                    'isCompleted', CASE WHEN sub.is_completed <> 0 THEN 1 ELSE 0 END,
+                    'position', sub.position,
+                    'createdAt', sub.created_at,
+                    'updatedAt', sub.updated_at
+                )
+            )
+            FROM (
+                SELECT st.id, st.board_id, st.card_id, st.title, st.is_completed, st.position, st.created_at, st.updated_at
+                FROM kanban_subtasks st
+                WHERE st.card_id = c.id
+                ORDER BY st.position ASC, st.created_at ASC
+            ) sub
+        ) AS subtasks_json,
+        (
+            SELECT json_group_array(
+                json_object(
+                    'id', tag.id,
+                    'boardId', tag.board_id,
+                    'label', tag.label,
+                    'color', tag.color,
+                    'position', tag.position,
+                    'createdAt', tag.created_at,
+                    'updatedAt', tag.updated_at
+                )
+            )
+            FROM (
+                SELECT t.id, t.board_id, t.label, t.color, t.position, t.created_at, t.updated_at
+                FROM kanban_card_tags ct
+                JOIN kanban_tags t ON t.id = ct.tag_id
+                WHERE ct.card_id = c.id
+                ORDER BY t.position ASC, t.label COLLATE NOCASE ASC
+            ) tag
+        ) AS tags_json
+    FROM kanban_cards c";
 
-    if filename.is_empty() {
-        return Err("Filename cannot be empty".to_string());
-    }
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn load_cards(
+    pool: State<'_, DbPool>,
+    board_id: String,
+    include_archived: Option<bool>,
+    priority: Option<Vec<String>>,
+    tag_ids: Option<Vec<String>>,
+    due_before: Option<String>,
+    overdue_only: Option<bool>,
+    sort: Option<String>,
+) -> Result<Vec<Value>, CommandError> {
+    let order_by = match sort.as_deref().unwrap_or("position") {
+        "position" => "c.position ASC",
+        "due_date" => "c.due_date IS NULL, julianday(c.due_date) ASC, c.position ASC",
+        "priority" => {
+            "CASE c.priority WHEN 'high' THEN 0 WHEN 'medium' THEN 1 WHEN 'low' THEN 2 ELSE 3 END ASC, c.position ASC"
+        }
+        "created_at" => "c.created_at ASC, c.position ASC",
+        "updated_at" => "c.updated_at DESC, c.position ASC",
+        other => {
+            return Err(CommandError::Validation(format!(
+                "Ordenação inválida: {other}. Utilize position, due_date, priority, created_at ou updated_at."
+            )));
+        }
+    };
 
-    if filename.len() > 100 {
-        return Err("Filename too long (max 100 characters)".to_string());
-    }
+    let mut builder = QueryBuilder::<Sqlite>::new(CARD_SELECT_SQL);
+    builder.push(" WHERE c.board_id = ");
+    builder.push_bind(&board_id);
 
-    if !filename_pattern.is_match(filename) {
-        return Err(
-            "Invalid filename: only alphanumeric characters, dashes, underscores, and dots allowed"
-                .to_string(),
-        );
+    if !include_archived.unwrap_or(false) {
+        builder.push(" AND c.archived_at IS NULL");
     }
 
-    Ok(())
-}
-
-fn validate_string_input(input: &str, max_len: usize, field_name: &str) -> Result<(), String> {
-    if input.len() > max_len {
-        return Err(format!("{field_name} too long (max {max_len} characters)"));
+    if let Some(priorities) = priority.filter(|values| !values.is_empty()) {
+        for value in &priorities {
+            validate_priority(value).map_err(CommandError::Validation)?;
+        }
+        builder.push(" AND c.priority IN (");
+        let mut separated = builder.separated(", ");
+        for value in priorities {
+            separated.push_bind(value);
+        }
+        separated.push_unseparated(")");
     }
-    Ok(())
-}
 
-fn validate_theme(theme: &str) -> Result<(), String> {
-    match theme {
-        "light" | "dark" | "system" => Ok(()),
-        _ => Err("Invalid theme: must be 'light', 'dark', or 'system'".to_string()),
+    // Cards must carry every listed tag, not just one of them
+    let tag_ids: BTreeSet<String> = tag_ids.unwrap_or_default().into_iter().collect();
+    if !tag_ids.is_empty() {
+        builder
+            .push(" AND c.id IN (SELECT ct.card_id FROM kanban_card_tags ct WHERE ct.tag_id IN (");
+        let tag_count = tag_ids.len() as i64;
+        let mut separated = builder.separated(", ");
+        for tag_id in tag_ids {
+            separated.push_bind(tag_id);
+        }
+        separated.push_unseparated(") GROUP BY ct.card_id HAVING COUNT(DISTINCT ct.tag_id) = ");
+        separated.push_bind_unseparated(tag_count);
+        separated.push_unseparated(")");
     }
-}
 
-// Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
-#[tauri::command]
-fn greet(name: &str) -> String {
-    // Input validation
-    if let Err(e) = validate_string_input(name, 100, "Name") {
-        log::warn!("Invalid greet input: {e}");
-        return format!("Error: {e}");
+    if let Some(due_before) = due_before.filter(|value| !value.trim().is_empty()) {
+        let due_before = parse_due_date(&due_before).ok_or_else(|| {
+            CommandError::Validation("Data limite de vencimento inválida.".to_string())
+        })?;
+        builder.push(" AND c.due_date IS NOT NULL AND julianday(c.due_date) < julianday(");
+        builder.push_bind(due_before.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string());
+        builder.push(")");
     }
 
-    log::info!("Greeting user: {name}");
-    format!("Hello, {name}! You've been greeted from Rust!")
-}
+    if overdue_only.unwrap_or(false) {
+        builder.push(" AND c.due_date IS NOT NULL AND julianday(c.due_date) < julianday('now')");
+    }
 
-// Preferences data structure
-// Only contains settings that should be persisted to disk
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AppPreferences {
-    pub theme: String,
-    #[serde(default = "default_transparency_enabled")]
-    pub transparency_enabled: bool,
-    #[serde(default)]
-    pub last_workspace_id: Option<String>,
-    // Add new persistent preferences here, e.g.:
-    // pub auto_save: bool,
-    // pub language: String,
-}
+    builder.push(" ORDER BY ");
+    builder.push(order_by);
 
-fn default_transparency_enabled() -> bool {
-    true
+    builder
+        .build()
+        .try_map(map_card_row)
+        .fetch_all(&*pool)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to load cards: {e}");
+            CommandError::from(e)
+        })
 }
 
-impl Default for AppPreferences {
-    fn default() -> Self {
-        Self {
-            theme: "system".to_string(),
-            transparency_enabled: default_transparency_enabled(),
-            last_workspace_id: None,
-            // Add defaults for new preferences here
-        }
-    }
-}
+#[tauri::command]
+async fn get_card(
+    pool: State<'_, DbPool>,
+    board_id: String,
+    card_id: String,
+) -> Result<Value, CommandError> {
+    let mut conn = pool
+        .acquire()
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao obter conexão com o banco: {e}")))?;
 
-fn get_preferences_path(app: &AppHandle) -> Result<PathBuf, String> {
-    let app_data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {e}"))?;
+    let card = fetch_card_by_id(&mut conn, &card_id)
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao carregar cartão: {e}")))?
+        .ok_or_else(|| CommandError::NotFound("Cartão não encontrado.".to_string()))?;
 
-    // Ensure the directory exists
-    std::fs::create_dir_all(&app_data_dir)
-        .map_err(|e| format!("Failed to create app data directory: {e}"))?;
+    if card.get("boardId").and_then(Value::as_str) != Some(board_id.as_str()) {
+        return Err(CommandError::Validation(
+            "O cartão não pertence ao quadro informado.".to_string(),
+        ));
+    }
 
-    Ok(app_data_dir.join("preferences.json"))
+    Ok(card)
 }
 
-// Shortcuts configuration stored separately from preferences
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub struct ShortcutsConfig {
-    /// Map from logical binding id (e.g. "open-command-palette") to a normalized
-    /// shortcut string representation (e.g. "mod+k").
-    #[serde(default)]
-    pub bindings: std::collections::HashMap<String, String>,
-}
+/// Moves a card to the end of `column_id`. The caller is responsible for
+/// normalizing the column the card came from.
+async fn append_card_to_column_tx(
+    tx: &mut Transaction<'_, Sqlite>,
+    card_id: &str,
+    column_id: &str,
+) -> Result<(), String> {
+    let max_position = sqlx::query_scalar::<_, Option<i64>>(
+        "SELECT MAX(position) FROM kanban_cards WHERE column_id = ? AND id <> ?",
+    )
+    .bind(column_id)
+    .bind(card_id)
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(|e| format!("Falha ao obter posição máxima dos cartões: {e}"))?
+    .unwrap_or(-1);
 
-fn get_shortcuts_path(app: &AppHandle) -> Result<PathBuf, String> {
-    let app_data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {e}"))?;
+    sqlx::query(
+        "UPDATE kanban_cards SET column_id = ?, position = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
+    )
+    .bind(column_id)
+    .bind(max_position + 1)
+    .bind(card_id)
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| format!("Falha ao mover cartão para coluna de destino: {e}"))?;
 
-    std::fs::create_dir_all(&app_data_dir)
-        .map_err(|e| format!("Failed to create app data directory: {e}"))?;
+    normalize_card_positions_tx(tx, column_id)
+        .await
+        .map_err(|e| format!("Falha ao normalizar posições dos cartões: {e}"))?;
 
-    Ok(app_data_dir.join("shortcuts.json"))
+    Ok(())
 }
 
 #[tauri::command]
-async fn load_preferences(app: AppHandle) -> Result<AppPreferences, String> {
-    log::debug!("Loading preferences from disk");
-    let prefs_path = get_preferences_path(&app)?;
-
-    if !prefs_path.exists() {
-        log::info!("Preferences file not found, using defaults");
-        return Ok(AppPreferences::default());
+async fn set_card_column_by_title(
+    pool: State<'_, DbPool>,
+    board_id: String,
+    card_id: String,
+    column_title: String,
+) -> Result<Value, String> {
+    let wanted = column_title.trim().to_lowercase();
+    if wanted.is_empty() {
+        return Err("O título da coluna não pode ser vazio.".to_string());
     }
 
-    let contents = std::fs::read_to_string(&prefs_path).map_err(|e| {
-        log::error!("Failed to read preferences file: {e}");
-        format!("Failed to read preferences file: {e}")
-    })?;
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
 
-    let preferences: AppPreferences = serde_json::from_str(&contents).map_err(|e| {
-        log::error!("Failed to parse preferences JSON: {e}");
-        format!("Failed to parse preferences: {e}")
-    })?;
+    let card = sqlx::query_as::<_, (String, String)>(
+        "SELECT board_id, column_id FROM kanban_cards WHERE id = ?",
+    )
+    .bind(&card_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| format!("Falha ao carregar cartão: {e}"))?;
 
-    log::info!("Successfully loaded preferences");
-    Ok(preferences)
-}
+    let Some((card_board_id, current_column_id)) = card else {
+        return Err("Cartão não encontrado.".to_string());
+    };
 
-#[tauri::command]
-async fn save_preferences(app: AppHandle, preferences: AppPreferences) -> Result<(), String> {
-    // Validate theme value
-    validate_theme(&preferences.theme)?;
+    if card_board_id != board_id {
+        return Err("O cartão não pertence ao quadro informado.".to_string());
+    }
 
-    log::debug!("Saving preferences to disk: {preferences:?}");
-    let prefs_path = get_preferences_path(&app)?;
+    let columns = sqlx::query_as::<_, (String, String)>(
+        "SELECT id, title FROM kanban_columns WHERE board_id = ?",
+    )
+    .bind(&board_id)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| format!("Falha ao carregar colunas: {e}"))?;
 
-    let json_content = serde_json::to_string_pretty(&preferences).map_err(|e| {
-        log::error!("Failed to serialize preferences: {e}");
-        format!("Failed to serialize preferences: {e}")
-    })?;
+    let matches: Vec<String> = columns
+        .into_iter()
+        .filter(|(_, title)| title.trim().to_lowercase() == wanted)
+        .map(|(id, _)| id)
+        .collect();
 
-    // Write to a temporary file first, then rename (atomic operation)
-    let temp_path = prefs_path.with_extension("tmp");
+    let target_column_id = match matches.as_slice() {
+        [id] => id.clone(),
+        [] => {
+            return Err(format!(
+                "Nenhuma coluna chamada \"{}\" neste quadro.",
+                column_title.trim()
+            ));
+        }
+        _ => {
+            return Err(format!(
+                "Mais de uma coluna chamada \"{}\" neste quadro.",
+                column_title.trim()
+            ));
+        }
+    };
 
-    std::fs::write(&temp_path, json_content).map_err(|e| {
-        log::error!("Failed to write preferences file: {e}");
-        format!("Failed to write preferences file: {e}")
-    })?;
+    append_card_to_column_tx(&mut tx, &card_id, &target_column_id).await?;
 
-    std::fs::rename(&temp_path, &prefs_path).map_err(|e| {
-        log::error!("Failed to finalize preferences file: {e}");
-        format!("Failed to finalize preferences file: {e}")
-    })?;
+    if current_column_id != target_column_id {
+        normalize_card_positions_tx(&mut tx, &current_column_id)
+            .await
+            .map_err(|e| format!("Falha ao normalizar posições dos cartões: {e}"))?;
+    }
 
-    log::info!("Successfully saved preferences to {prefs_path:?}");
-    Ok(())
+    let card = fetch_card_by_id(&mut tx, &card_id)
+        .await
+        .map_err(|e| format!("Falha ao carregar cartão: {e}"))?
+        .ok_or_else(|| "Cartão não encontrado.".to_string())?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
+
+    Ok(card)
 }
 
 #[tauri::command]
-async fn load_shortcuts(app: AppHandle) -> Result<ShortcutsConfig, String> {
-    log::debug!("Loading shortcuts configuration from disk");
-    let shortcuts_path = get_shortcuts_path(&app)?;
-
-    if !shortcuts_path.exists() {
-        log::info!("Shortcuts file not found, using defaults (empty config)");
-        return Ok(ShortcutsConfig::default());
-    }
-
-    let contents = std::fs::read_to_string(&shortcuts_path).map_err(|e| {
-        log::error!("Failed to read shortcuts file: {e}");
-        format!("Failed to read shortcuts file: {e}")
-    })?;
-
-    let config: ShortcutsConfig = serde_json::from_str(&contents).map_err(|e| {
-        log::error!("Failed to parse shortcuts JSON: {e}");
-        format!("Failed to parse shortcuts: {e}")
-    })?;
-
-    log::info!("Successfully loaded shortcuts configuration");
-    Ok(config)
+async fn find_orphaned_cards(
+    pool: State<'_, DbPool>,
+    board_id: String,
+) -> Result<Vec<Value>, String> {
+    let sql = format!(
+        "{CARD_SELECT_SQL} WHERE c.board_id = ? AND NOT EXISTS (SELECT 1 FROM kanban_columns col WHERE col.id = c.column_id) ORDER BY c.created_at ASC"
+    );
+    sqlx::query(&sql)
+        .bind(&board_id)
+        .try_map(map_card_row)
+        .fetch_all(&*pool)
+        .await
+        .map_err(|e| format!("Falha ao carregar cartões órfãos: {e}"))
 }
 
 #[tauri::command]
-async fn save_shortcuts(app: AppHandle, config: ShortcutsConfig) -> Result<(), String> {
-    log::debug!("Saving shortcuts configuration to disk: {config:?}");
-    let shortcuts_path = get_shortcuts_path(&app)?;
-
-    let json_content = serde_json::to_string_pretty(&config).map_err(|e| {
-        log::error!("Failed to serialize shortcuts config: {e}");
-        format!("Failed to serialize shortcuts config: {e}")
-    })?;
+async fn reassign_card_column(
+    pool: State<'_, DbPool>,
+    board_id: String,
+    card_id: String,
+    column_id: String,
+) -> Result<Value, String> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
 
-    let temp_path = shortcuts_path.with_extension("tmp");
+    let (card_board_id, source_column_id) = sqlx::query_as::<_, (String, String)>(
+        "SELECT board_id, column_id FROM kanban_cards WHERE id = ?",
+    )
+    .bind(&card_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| format!("Falha ao carregar cartão: {e}"))?
+    .ok_or_else(|| "Cartão não encontrado.".to_string())?;
 
-    std::fs::write(&temp_path, json_content).map_err(|e| {
-        log::error!("Failed to write shortcuts file: {e}");
-        format!("Failed to write shortcuts file: {e}")
-    })?;
+    if card_board_id != board_id {
+        return Err("O cartão não pertence ao quadro informado.".to_string());
+    }
 
-    std::fs::rename(&temp_path, &shortcuts_path).map_err(|e| {
-        log::error!("Failed to finalize shortcuts file: {e}");
-        format!("Failed to finalize shortcuts file: {e}")
-    })?;
+    let column_board_id =
+        sqlx::query_scalar::<_, String>("SELECT board_id FROM kanban_columns WHERE id = ?")
+            .bind(&column_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| format!("Falha ao carregar coluna: {e}"))?
+            .ok_or_else(|| "Coluna não encontrada.".to_string())?;
 
-    log::info!("Successfully saved shortcuts configuration to {shortcuts_path:?}");
-    Ok(())
-}
+    if column_board_id != board_id {
+        return Err("A coluna informada não pertence ao quadro selecionado.".to_string());
+    }
 
-#[tauri::command]
-async fn send_native_notification(
-    app: AppHandle,
-    title: String,
-    body: Option<String>,
-) -> Result<(), String> {
-    log::info!("Sending native notification: {title}");
+    append_card_to_column_tx(&mut tx, &card_id, &column_id).await?;
+    if source_column_id != column_id {
+        normalize_card_positions_tx(&mut tx, &source_column_id)
+            .await
+            .map_err(|e| format!("Falha ao normalizar posições dos cartões: {e}"))?;
+    }
 
-    #[cfg(not(mobile))]
-    {
-        use tauri_plugin_notification::NotificationExt;
+    let card = fetch_card_by_id(&mut tx, &card_id)
+        .await
+        .map_err(|e| format!("Falha ao carregar cartão: {e}"))?
+        .ok_or_else(|| "Cartão não encontrado.".to_string())?;
 
-        let mut notification = app.notification().builder().title(title);
+    tx.commit()
+        .await
+        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
 
-        if let Some(body_text) = body {
-            notification = notification.body(body_text);
-        }
+    Ok(card)
+}
 
-        match notification.show() {
-            Ok(_) => {
-                log::info!("Native notification sent successfully");
-                Ok(())
-            }
-            Err(e) => {
-                log::error!("Failed to send native notification: {e}");
-                Err(format!("Failed to send notification: {e}"))
-            }
-        }
-    }
+const INVALID_PRIORITY_FILTER_SQL: &str = "c.priority NOT IN ('none', 'low', 'medium', 'high')";
 
-    #[cfg(mobile)]
-    {
-        log::warn!("Native notifications not supported on mobile");
-        Err("Native notifications not supported on mobile".to_string())
-    }
+#[tauri::command]
+async fn find_invalid_priorities(
+    pool: State<'_, DbPool>,
+    board_id: String,
+) -> Result<Vec<Value>, String> {
+    let sql = format!(
+        "{CARD_SELECT_SQL} WHERE c.board_id = ? AND {INVALID_PRIORITY_FILTER_SQL} ORDER BY c.created_at ASC"
+    );
+    sqlx::query(&sql)
+        .bind(&board_id)
+        .try_map(map_card_row)
+        .fetch_all(&*pool)
+        .await
+        .map_err(|e| format!("Falha ao carregar cartões com prioridade inválida: {e}"))
 }
 
-// Recovery functions - simple pattern for saving JSON data to disk
-fn get_recovery_dir(app: &AppHandle) -> Result<PathBuf, String> {
-    let app_data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {e}"))?;
+#[tauri::command]
+async fn normalize_card_priorities(
+    pool: State<'_, DbPool>,
+    board_id: String,
+) -> Result<u64, String> {
+    let sql = format!(
+        "UPDATE kanban_cards AS c SET priority = 'none', updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE c.board_id = ? AND {INVALID_PRIORITY_FILTER_SQL}"
+    );
+    let result = sqlx::query(&sql)
+        .bind(&board_id)
+        .execute(&*pool)
+        .await
+        .map_err(|e| format!("Falha ao normalizar prioridades: {e}"))?;
 
-    let recovery_dir = app_data_dir.join("recovery");
+    Ok(result.rows_affected())
+}
 
-    // Ensure the recovery directory exists
-    std::fs::create_dir_all(&recovery_dir)
-        .map_err(|e| format!("Failed to create recovery directory: {e}"))?;
+#[tauri::command]
+async fn find_duplicate_card_titles(
+    pool: State<'_, DbPool>,
+    board_id: String,
+) -> Result<Vec<Value>, String> {
+    let rows = sqlx::query_as::<_, (String, String, String)>(
+        "SELECT column_id, MIN(TRIM(title)), json_group_array(id)
+        FROM (
+            SELECT id, column_id, title FROM kanban_cards
+            WHERE board_id = ? AND archived_at IS NULL
+            ORDER BY position ASC
+        )
+        GROUP BY column_id, LOWER(TRIM(title))
+        HAVING COUNT(*) > 1
+        ORDER BY column_id, LOWER(TRIM(title))",
+    )
+    .bind(&board_id)
+    .fetch_all(&*pool)
+    .await
+    .map_err(|e| format!("Falha ao procurar cartões duplicados: {e}"))?;
 
-    Ok(recovery_dir)
+    Ok(rows
+        .into_iter()
+        .map(|(column_id, title, card_ids_json)| {
+            let card_ids: Vec<String> = serde_json::from_str(&card_ids_json).unwrap_or_default();
+            json!({
+                "columnId": column_id,
+                "title": title,
+                "cardIds": card_ids,
+            })
+        })
+        .collect())
+}
+
+async fn fetch_card_by_id(
+    conn: &mut SqliteConnection,
+    card_id: &str,
+) -> Result<Option<Value>, sqlx::Error> {
+    let sql = format!("{CARD_SELECT_SQL} WHERE c.id = ?");
+    sqlx::query(&sql)
+        .bind(card_id)
+        .try_map(map_card_row)
+        .fetch_optional(&mut *conn)
+        .await
 }
 
 #[tauri::command]
-async fn save_emergency_data(app: AppHandle, filename: String, data: Value) -> Result<(), String> {
-    log::info!("Saving emergency data to file: {filename}");
+async fn load_tags(pool: State<'_, DbPool>, board_id: String) -> Result<Vec<Value>, String> {
+    sqlx::query(
+        "SELECT id, board_id, label, color, position, created_at, updated_at FROM kanban_tags WHERE board_id = ? ORDER BY position ASC, label COLLATE NOCASE ASC",
+    )
+    .bind(&board_id)
+    .try_map(map_tag_row)
+    .fetch_all(&*pool)
+    .await
+    .map_err(|e| {
+        log::error!("Failed to load tags: {e}");
+        e.to_string()
+    })
+}
 
-    // Validate filename with proper security checks
-    validate_filename(&filename)?;
+#[tauri::command]
+async fn get_tag_cloud(pool: State<'_, DbPool>, board_id: String) -> Result<Vec<Value>, String> {
+    let rows = sqlx::query(
+        "SELECT t.id, t.board_id, t.label, t.color, t.position, t.created_at, t.updated_at, COUNT(c.id) AS card_count
+         FROM kanban_tags t
+         LEFT JOIN kanban_card_tags ct ON ct.tag_id = t.id
+         LEFT JOIN kanban_cards c ON c.id = ct.card_id AND c.archived_at IS NULL
+         WHERE t.board_id = ?
+         GROUP BY t.id
+         ORDER BY card_count DESC, t.label COLLATE NOCASE ASC",
+    )
+    .bind(&board_id)
+    .fetch_all(&*pool)
+    .await
+    .map_err(|e| format!("Falha ao carregar nuvem de tags: {e}"))?;
 
-    // Validate data size (10MB limit)
-    let data_str = serde_json::to_string(&data)
-        .map_err(|e| format!("Failed to serialize data for size check: {e}"))?;
-    if data_str.len() > 10_485_760 {
-        return Err("Data too large (max 10MB)".to_string());
+    let mut tags = Vec::with_capacity(rows.len());
+    for row in rows {
+        let card_count: i64 = row.try_get("card_count").map_err(|e| e.to_string())?;
+        let mut value = map_tag_row(row).map_err(|e| format!("Falha ao mapear tag: {e}"))?;
+        value["cardCount"] = json!(card_count);
+        tags.push(value);
     }
 
-    let recovery_dir = get_recovery_dir(&app)?;
-    let file_path = recovery_dir.join(format!("{filename}.json"));
+    Ok(tags)
+}
 
-    let json_content = serde_json::to_string_pretty(&data).map_err(|e| {
-        log::error!("Failed to serialize emergency data: {e}");
-        format!("Failed to serialize data: {e}")
-    })?;
+#[tauri::command]
+async fn create_tag<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    args: CreateTagArgs,
+) -> Result<Value, String> {
+    let label = args.label.trim().to_string();
+    if label.is_empty() {
+        return Err("O nome da tag não pode ser vazio.".to_string());
+    }
+    validate_string_input(&label, 100, "Nome da tag")?;
 
-    // Write to a temporary file first, then rename (atomic operation)
-    let temp_path = file_path.with_extension("tmp");
+    let normalized_color = normalize_tag_color(args.color)?;
 
-    std::fs::write(&temp_path, json_content).map_err(|e| {
-        log::error!("Failed to write emergency data file: {e}");
-        format!("Failed to write data file: {e}")
+    sqlx::query(
+        "INSERT INTO kanban_tags (id, board_id, label, color, position, created_at, updated_at) VALUES (?, ?, ?, ?, (SELECT COALESCE(MAX(position), -1) + 1 FROM kanban_tags WHERE board_id = ?), strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))",
+    )
+    .bind(&args.id)
+    .bind(&args.board_id)
+    .bind(&label)
+    .bind(normalized_color.as_deref())
+    .bind(&args.board_id)
+    .execute(&*pool)
+    .await
+    .map_err(|e| {
+        log::error!("Failed to create tag: {e}");
+        e.to_string()
     })?;
 
-    std::fs::rename(&temp_path, &file_path).map_err(|e| {
-        log::error!("Failed to finalize emergency data file: {e}");
-        format!("Failed to finalize data file: {e}")
-    })?;
+    emit_board_changed(&app, &args.board_id, "tag_created");
 
-    log::info!("Successfully saved emergency data to {file_path:?}");
-    Ok(())
+    sqlx::query(
+        "SELECT id, board_id, label, color, position, created_at, updated_at FROM kanban_tags WHERE id = ?",
+    )
+    .bind(&args.id)
+    .fetch_one(&*pool)
+    .await
+    .map_err(|e| {
+        log::error!("Failed to load created tag: {e}");
+        e.to_string()
+    })
+    .and_then(|row| map_tag_row(row).map_err(|e| e.to_string()))
 }
 
 #[tauri::command]
-async fn load_emergency_data(app: AppHandle, filename: String) -> Result<Value, String> {
-    log::info!("Loading emergency data from file: {filename}");
+async fn create_tags<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    board_id: String,
+    labels: Vec<String>,
+    color: Option<String>,
+) -> Result<Vec<Value>, String> {
+    let normalized_color = normalize_tag_color(color)?;
 
-    // Validate filename with proper security checks
-    validate_filename(&filename)?;
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
 
-    let recovery_dir = get_recovery_dir(&app)?;
-    let file_path = recovery_dir.join(format!("{filename}.json"));
+    let mut known_labels: BTreeSet<String> =
+        sqlx::query_scalar::<_, String>("SELECT label FROM kanban_tags WHERE board_id = ?")
+            .bind(&board_id)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(|e| format!("Falha ao carregar tags existentes: {e}"))?
+            .into_iter()
+            .map(|label| label.to_lowercase())
+            .collect();
 
-    if !file_path.exists() {
-        log::info!("Recovery file not found: {file_path:?}");
-        return Err("File not found".to_string());
-    }
+    let mut created = Vec::new();
+    for label in &labels {
+        let label = label.trim();
+        if label.is_empty() || !known_labels.insert(label.to_lowercase()) {
+            continue;
+        }
+        validate_string_input(label, 100, "Nome da tag")?;
 
-    let contents = std::fs::read_to_string(&file_path).map_err(|e| {
-        log::error!("Failed to read recovery file: {e}");
-        format!("Failed to read file: {e}")
-    })?;
+        let id = Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO kanban_tags (id, board_id, label, color, position, created_at, updated_at) VALUES (?, ?, ?, ?, (SELECT COALESCE(MAX(position), -1) + 1 FROM kanban_tags WHERE board_id = ?), strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))",
+        )
+        .bind(&id)
+        .bind(&board_id)
+        .bind(label)
+        .bind(normalized_color.as_deref())
+        .bind(&board_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao criar tag: {e}"))?;
 
-    let data: Value = serde_json::from_str(&contents).map_err(|e| {
-        log::error!("Failed to parse recovery JSON: {e}");
-        format!("Failed to parse data: {e}")
-    })?;
+        let row = sqlx::query(
+            "SELECT id, board_id, label, color, position, created_at, updated_at FROM kanban_tags WHERE id = ?",
+        )
+        .bind(&id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao carregar tag criada: {e}"))?;
 
-    log::info!("Successfully loaded emergency data");
-    Ok(data)
+        created.push(map_tag_row(row).map_err(|e| format!("Falha ao mapear tag: {e}"))?);
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
+
+    if !created.is_empty() {
+        emit_board_changed(&app, &board_id, "tag_created");
+    }
+
+    Ok(created)
 }
 
 #[tauri::command]
-async fn cleanup_old_recovery_files(app: AppHandle) -> Result<u32, String> {
-    log::info!("Cleaning up old recovery files");
+async fn update_tag<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    args: UpdateTagArgs,
+) -> Result<Value, String> {
+    let mut builder = QueryBuilder::<Sqlite>::new(
+        "UPDATE kanban_tags SET updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+    );
+    let mut has_changes = false;
 
-    let recovery_dir = get_recovery_dir(&app)?;
-    let mut removed_count = 0;
+    if let Some(label) = args.label.as_ref() {
+        let trimmed = label.trim();
+        if trimmed.is_empty() {
+            return Err("O nome da tag não pode ser vazio.".to_string());
+        }
+        validate_string_input(trimmed, 100, "Nome da tag")?;
+        builder.push(", label = ");
+        builder.push_bind(trimmed);
+        has_changes = true;
+    }
 
-    // Calculate cutoff time (7 days ago)
-    let now = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map_err(|e| format!("Failed to get current time: {e}"))?
-        .as_secs();
-    let seven_days_ago = now - (7 * 24 * 60 * 60);
+    let mut color_binding: Option<Option<String>> = None;
+    if let Some(color_payload) = args.color.clone() {
+        let normalized = normalize_tag_color(color_payload)?;
+        color_binding = Some(normalized);
+        has_changes = true;
+    }
 
-    // Read directory and check each file
-    let entries = std::fs::read_dir(&recovery_dir).map_err(|e| {
-        log::error!("Failed to read recovery directory: {e}");
-        format!("Failed to read directory: {e}")
-    })?;
+    if let Some(normalized_color) = color_binding.as_ref() {
+        builder.push(", color = ");
+        builder.push_bind(normalized_color.as_deref());
+    }
 
-    for entry in entries {
-        let entry = match entry {
-            Ok(e) => e,
-            Err(e) => {
-                log::warn!("Failed to read directory entry: {e}");
-                continue;
-            }
-        };
+    if has_changes {
+        builder.push(" WHERE id = ");
+        builder.push_bind(&args.id);
+        builder.push(" AND board_id = ");
+        builder.push_bind(&args.board_id);
 
-        let path = entry.path();
+        let result = builder.build().execute(&*pool).await.map_err(|e| {
+            log::error!("Failed to update tag: {e}");
+            e.to_string()
+        })?;
 
-        // Only process JSON files
-        if path.extension().is_none_or(|ext| ext != "json") {
-            continue;
+        if result.rows_affected() == 0 {
+            return Err("Tag não encontrada.".to_string());
         }
 
-        // Check file modification time
-        let metadata = match std::fs::metadata(&path) {
-            Ok(m) => m,
-            Err(e) => {
-                log::warn!("Failed to get file metadata: {e}");
-                continue;
-            }
-        };
-
-        let modified = match metadata.modified() {
-            Ok(m) => m,
-            Err(e) => {
-                log::warn!("Failed to get file modification time: {e}");
-                continue;
-            }
-        };
-
-        let modified_secs = match modified.duration_since(UNIX_EPOCH) {
-            Ok(d) => d.as_secs(),
-            Err(e) => {
-                log::warn!("Failed to convert modification time: {e}");
-                continue;
-            }
-        };
+        emit_board_changed(&app, &args.board_id, "tag_updated");
+    } else {
+        // Nothing to update, but ensure tag exists
+        let exists = sqlx::query_scalar::<_, Option<i64>>(
+            "SELECT 1 FROM kanban_tags WHERE id = ? AND board_id = ? LIMIT 1",
+        )
+        .bind(&args.id)
+        .bind(&args.board_id)
+        .fetch_optional(&*pool)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to validate tag existence: {e}");
+            e.to_string()
+        })?
+        .flatten()
+        .is_some();
 
-        // Remove if older than 7 days
-        if modified_secs < seven_days_ago {
-            match std::fs::remove_file(&path) {
-                Ok(_) => {
-                    log::info!("Removed old recovery file: {path:?}");
-                    removed_count += 1;
-                }
-                Err(e) => {
-                    log::warn!("Failed to remove old recovery file: {e}");
-                }
-            }
+        if !exists {
+            return Err("Tag não encontrada.".to_string());
         }
     }
 
-    log::info!("Cleanup complete. Removed {removed_count} old recovery files");
-    Ok(removed_count)
+    sqlx::query(
+        "SELECT id, board_id, label, color, position, created_at, updated_at FROM kanban_tags WHERE id = ?",
+    )
+    .bind(&args.id)
+    .fetch_one(&*pool)
+    .await
+    .map_err(|e| {
+        log::error!("Failed to load updated tag: {e}");
+        e.to_string()
+    })
+    .and_then(|row| map_tag_row(row).map_err(|e| e.to_string()))
 }
 
-// Create the native menu system
-fn create_app_menu(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
-    log::info!("Setting up native menu system");
-
-    // Build the main application submenu
-    let app_submenu = SubmenuBuilder::new(app, "Modulo")
-        .item(&MenuItemBuilder::with_id("about", "About Modulo").build(app)?)
-        .separator()
-        .item(&MenuItemBuilder::with_id("check-updates", "Check for Updates...").build(app)?)
-        .separator()
-        .item(
-            &MenuItemBuilder::with_id("preferences", "Preferences...")
-                .accelerator("CmdOrCtrl+,")
-                .build(app)?,
-        )
-        .separator()
-        .item(&PredefinedMenuItem::hide(app, Some("Hide Modulo"))?)
-        .item(&PredefinedMenuItem::hide_others(app, None)?)
-        .item(&PredefinedMenuItem::show_all(app, None)?)
-        .separator()
-        .item(&PredefinedMenuItem::quit(app, Some("Quit Modulo"))?)
-        .build()?;
-
-    // Build the View submenu
-    let view_submenu = SubmenuBuilder::new(app, "View")
-        .item(
-            &MenuItemBuilder::with_id("toggle-left-sidebar", "Toggle Left Sidebar")
-                .accelerator("CmdOrCtrl+1")
-                .build(app)?,
-        )
-        .build()?;
+#[tauri::command]
+async fn delete_tag<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    args: DeleteTagArgs,
+) -> Result<(), String> {
+    let result = sqlx::query("DELETE FROM kanban_tags WHERE id = ? AND board_id = ?")
+        .bind(&args.id)
+        .bind(&args.board_id)
+        .execute(&*pool)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to delete tag: {e}");
+            e.to_string()
+        })?;
 
-    // Build the main menu with submenus
-    let menu = MenuBuilder::new(app)
-        .item(&app_submenu)
-        .item(&view_submenu)
-        .build()?;
+    if result.rows_affected() == 0 {
+        return Err("Tag não encontrada.".to_string());
+    }
 
-    // Set the menu for the app
-    app.set_menu(menu)?;
+    emit_board_changed(&app, &args.board_id, "tag_deleted");
 
-    log::info!("Native menu system initialized successfully");
     Ok(())
 }
 
-async fn ensure_notes_board_id_column(pool: &DbPool) -> Result<(), String> {
-    let column_exists = sqlx::query_scalar::<_, Option<i64>>(
-        "SELECT 1 FROM pragma_table_info('notes') WHERE name = 'board_id' LIMIT 1",
-    )
-    .fetch_optional(pool)
-    .await
-    .map_err(|e| format!("Failed to inspect notes schema: {e}"))?
-    .flatten()
-    .is_some();
+/// `ordered_ids` must list every tag of the board exactly once.
+#[tauri::command]
+async fn reorder_tags<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    board_id: String,
+    ordered_ids: Vec<String>,
+) -> Result<(), String> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
 
-    if !column_exists {
-        sqlx::query("ALTER TABLE notes ADD COLUMN board_id TEXT")
-            .execute(pool)
+    let mut existing_ids: Vec<String> =
+        sqlx::query_scalar("SELECT id FROM kanban_tags WHERE board_id = ?")
+            .bind(&board_id)
+            .fetch_all(&mut *tx)
             .await
-            .map_err(|e| format!("Failed to add board_id column to notes: {e}"))?;
+            .map_err(|e| format!("Falha ao carregar tags: {e}"))?;
+    existing_ids.sort();
 
-        // Set board_id to the first board for existing notes
+    let mut requested_ids = ordered_ids.clone();
+    requested_ids.sort();
+
+    if requested_ids != existing_ids {
+        return Err("A nova ordem deve conter cada tag do quadro exatamente uma vez.".to_string());
+    }
+
+    for (index, tag_id) in ordered_ids.iter().enumerate() {
         sqlx::query(
-            "UPDATE notes SET board_id = (SELECT id FROM kanban_boards LIMIT 1) WHERE board_id IS NULL"
+            "UPDATE kanban_tags SET position = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
         )
-        .execute(pool)
+        .bind(index as i64)
+        .bind(tag_id)
+        .execute(&mut *tx)
         .await
-        .map_err(|e| format!("Failed to update existing notes with board_id: {e}"))?;
+        .map_err(|e| format!("Falha ao reordenar tags: {e}"))?;
     }
 
-    // Create indexes for notes table (safe to run multiple times)
-    sqlx::query(
-        "CREATE INDEX IF NOT EXISTS idx_notes_board_updated ON notes(board_id, updated_at DESC)",
-    )
-    .execute(pool)
-    .await
-    .map_err(|e| format!("Failed to create idx_notes_board_updated: {e}"))?;
-
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_notes_board_pinned ON notes(board_id, pinned DESC, updated_at DESC)")
-        .execute(pool)
+    tx.commit()
         .await
-        .map_err(|e| format!("Failed to create idx_notes_board_pinned: {e}"))?;
+        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
+
+    emit_board_changed(&app, &board_id, "tags_reordered");
 
     Ok(())
 }
 
-async fn ensure_board_favorite_column(pool: &DbPool) -> Result<(), String> {
-    let column_exists = sqlx::query_scalar::<_, Option<i64>>(
-        "SELECT 1 FROM pragma_table_info('kanban_boards') WHERE name = 'is_favorite' LIMIT 1",
-    )
-    .fetch_optional(pool)
-    .await
-    .map_err(|e| format!("Failed to inspect kanban_boards schema: {e}"))?
-    .flatten()
-    .is_some();
-
-    if !column_exists {
-        sqlx::query("ALTER TABLE kanban_boards ADD COLUMN is_favorite INTEGER NOT NULL DEFAULT 0")
-            .execute(pool)
-            .await
-            .map_err(|e| format!("Failed to add is_favorite column to kanban_boards: {e}"))?;
-        sqlx::query("UPDATE kanban_boards SET is_favorite = 0 WHERE is_favorite IS NULL")
-            .execute(pool)
-            .await
-            .map_err(|e| format!("Failed to backfill is_favorite values in kanban_boards: {e}"))?;
-    } else {
-        sqlx::query("UPDATE kanban_boards SET is_favorite = 0 WHERE is_favorite IS NULL")
-            .execute(pool)
-            .await
-            .map_err(|e| format!("Failed to normalize is_favorite values in kanban_boards: {e}"))?;
+/// Moves every card from `source_tag_ids` onto `target_tag_id` and deletes the
+/// sources. Returns how many cards carried at least one source tag.
+#[tauri::command]
+async fn merge_tags<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    board_id: String,
+    source_tag_ids: Vec<String>,
+    target_tag_id: String,
+) -> Result<i64, String> {
+    let source_ids: Vec<String> = source_tag_ids
+        .into_iter()
+        .filter(|id| *id != target_tag_id)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    if source_ids.is_empty() {
+        return Err("Informe ao menos uma tag de origem diferente da tag de destino.".to_string());
     }
 
-    Ok(())
-}
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
 
-// ============================================================================
-// NOTES COMMANDS
-// ============================================================================
+    let mut builder =
+        QueryBuilder::<Sqlite>::new("SELECT COUNT(*) FROM kanban_tags WHERE board_id = ");
+    builder.push_bind(&board_id);
+    builder.push(" AND id IN (");
+    let mut separated = builder.separated(", ");
+    separated.push_bind(&target_tag_id);
+    for tag_id in &source_ids {
+        separated.push_bind(tag_id);
+    }
+    builder.push(")");
 
-#[derive(Debug, Deserialize)]
-struct CreateNoteArgs {
-    id: String,
-    board_id: String,
-    title: String,
-    #[serde(default)]
-    content: Option<String>,
-}
+    let found: i64 = builder
+        .build_query_scalar()
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao validar tags informadas: {e}"))?;
 
-#[derive(Debug, Deserialize)]
-struct UpdateNoteArgs {
-    id: String,
-    board_id: String,
-    #[serde(default)]
-    title: Option<String>,
-    #[serde(default)]
-    content: Option<String>,
-    #[serde(default)]
-    pinned: Option<bool>,
-}
+    if found != source_ids.len() as i64 + 1 {
+        return Err("Algumas tags informadas não existem neste quadro.".to_string());
+    }
 
-#[tauri::command]
-async fn load_notes(pool: State<'_, DbPool>, board_id: String) -> Result<Vec<Value>, String> {
-    let rows = sqlx::query(
-        "SELECT id, board_id, title, content, created_at, updated_at, archived_at, pinned, tags 
-         FROM notes 
-         WHERE board_id = ? AND archived_at IS NULL 
-         ORDER BY pinned DESC, updated_at DESC",
-    )
-    .bind(&board_id)
-    .fetch_all(&*pool)
-    .await
-    .map_err(|e| format!("Failed to load notes: {e}"))?;
+    let mut builder = QueryBuilder::<Sqlite>::new(
+        "SELECT COUNT(DISTINCT card_id) FROM kanban_card_tags WHERE tag_id IN (",
+    );
+    let mut separated = builder.separated(", ");
+    for tag_id in &source_ids {
+        separated.push_bind(tag_id);
+    }
+    builder.push(")");
 
-    let notes: Vec<Value> = rows
-        .iter()
-        .map(|row| {
-            json!({
-                "id": row.get::<String, _>("id"),
-                "boardId": row.get::<String, _>("board_id"),
-                "title": row.get::<String, _>("title"),
-                "content": row.get::<String, _>("content"),
-                "createdAt": row.get::<String, _>("created_at"),
-                "updatedAt": row.get::<String, _>("updated_at"),
-                "archivedAt": row.get::<Option<String>, _>("archived_at"),
-                "pinned": row.get::<i64, _>("pinned") != 0,
-                "tags": row.get::<Option<String>, _>("tags")
-                    .and_then(|s: String| serde_json::from_str::<Vec<String>>(&s).ok())
-                    .unwrap_or_default(),
-            })
-        })
-        .collect();
+    let retagged: i64 = builder
+        .build_query_scalar()
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao contar cartões das tags: {e}"))?;
 
-    Ok(notes)
-}
+    let mut builder = QueryBuilder::<Sqlite>::new(
+        "UPDATE kanban_cards SET updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') \
+         WHERE id IN (SELECT card_id FROM kanban_card_tags WHERE tag_id IN (",
+    );
+    let mut separated = builder.separated(", ");
+    for tag_id in &source_ids {
+        separated.push_bind(tag_id);
+    }
+    builder.push("))");
 
-#[tauri::command]
-async fn create_note(pool: State<'_, DbPool>, args: CreateNoteArgs) -> Result<Value, String> {
-    let content = args.content.unwrap_or_else(|| String::from(""));
+    builder
+        .build()
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao atualizar cartões das tags: {e}"))?;
 
-    sqlx::query("INSERT INTO notes (id, board_id, title, content) VALUES (?, ?, ?, ?)")
-        .bind(&args.id)
-        .bind(&args.board_id)
-        .bind(&args.title)
-        .bind(&content)
-        .execute(&*pool)
+    let mut builder = QueryBuilder::<Sqlite>::new(
+        "INSERT OR IGNORE INTO kanban_card_tags (card_id, tag_id) SELECT DISTINCT card_id, ",
+    );
+    builder.push_bind(&target_tag_id);
+    builder.push(" FROM kanban_card_tags WHERE tag_id IN (");
+    let mut separated = builder.separated(", ");
+    for tag_id in &source_ids {
+        separated.push_bind(tag_id);
+    }
+    builder.push(")");
+
+    builder
+        .build()
+        .execute(&mut *tx)
         .await
-        .map_err(|e| format!("Failed to create note: {e}"))?;
+        .map_err(|e| format!("Falha ao mover cartões para a tag de destino: {e}"))?;
 
-    let row = sqlx::query(
-        "SELECT id, board_id, title, content, created_at, updated_at, archived_at, pinned, tags 
-         FROM notes WHERE id = ? AND board_id = ?",
-    )
-    .bind(&args.id)
-    .bind(&args.board_id)
-    .fetch_one(&*pool)
-    .await
-    .map_err(|e| format!("Failed to fetch created note: {e}"))?;
+    let mut builder = QueryBuilder::<Sqlite>::new("DELETE FROM kanban_tags WHERE board_id = ");
+    builder.push_bind(&board_id);
+    builder.push(" AND id IN (");
+    let mut separated = builder.separated(", ");
+    for tag_id in &source_ids {
+        separated.push_bind(tag_id);
+    }
+    builder.push(")");
 
-    Ok(json!({
-        "id": row.get::<String, _>("id"),
-        "boardId": row.get::<String, _>("board_id"),
-        "title": row.get::<String, _>("title"),
-        "content": row.get::<String, _>("content"),
-        "createdAt": row.get::<String, _>("created_at"),
-        "updatedAt": row.get::<String, _>("updated_at"),
-        "archivedAt": row.get::<Option<String>, _>("archived_at"),
-        "pinned": row.get::<i64, _>("pinned") != 0,
-        "tags": row.get::<Option<String>, _>("tags")
-            .and_then(|s: String| serde_json::from_str::<Vec<String>>(&s).ok())
-            .unwrap_or_default(),
-    }))
+    builder
+        .build()
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao remover tags de origem: {e}"))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
+
+    emit_board_changed(&app, &board_id, "tags_merged");
+
+    Ok(retagged)
 }
 
-#[tauri::command]
-async fn update_note(pool: State<'_, DbPool>, args: UpdateNoteArgs) -> Result<(), String> {
-    let mut query_parts =
-        vec!["UPDATE notes SET updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')"];
-    let mut bindings: Vec<String> = vec![];
+/// Checks that the tag and every card belong to `board_id`, returning the
+/// deduplicated card ids.
+async fn validate_bulk_tag_targets_tx(
+    tx: &mut Transaction<'_, Sqlite>,
+    board_id: &str,
+    tag_id: &str,
+    card_ids: Vec<String>,
+) -> Result<Vec<String>, String> {
+    let tag_exists = sqlx::query_scalar::<_, i64>(
+        "SELECT 1 FROM kanban_tags WHERE id = ? AND board_id = ? LIMIT 1",
+    )
+    .bind(tag_id)
+    .bind(board_id)
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(|e| format!("Falha ao validar tag: {e}"))?
+    .is_some();
 
-    if let Some(ref title) = args.title {
-        query_parts.push("title = ?");
-        bindings.push(title.clone());
+    if !tag_exists {
+        return Err("Tag não encontrada.".to_string());
     }
 
-    if let Some(ref content) = args.content {
-        query_parts.push("content = ?");
-        bindings.push(content.clone());
+    let card_ids: Vec<String> = card_ids
+        .into_iter()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    if card_ids.is_empty() {
+        return Ok(card_ids);
     }
 
-    if let Some(pinned) = args.pinned {
-        query_parts.push("pinned = ?");
-        bindings.push(if pinned { "1" } else { "0" }.to_string());
+    let mut builder =
+        QueryBuilder::<Sqlite>::new("SELECT COUNT(*) FROM kanban_cards WHERE board_id = ");
+    builder.push_bind(board_id);
+    builder.push(" AND id IN (");
+    let mut separated = builder.separated(", ");
+    for card_id in &card_ids {
+        separated.push_bind(card_id);
     }
+    builder.push(")");
 
-    if bindings.is_empty() {
-        return Ok(());
+    let found: i64 = builder
+        .build_query_scalar()
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(|e| format!("Falha ao validar cartões informados: {e}"))?;
+
+    if found != card_ids.len() as i64 {
+        return Err("Alguns cartões informados não pertencem a este quadro.".to_string());
     }
 
-    query_parts.push("WHERE id = ? AND board_id = ?");
-    bindings.push(args.id.clone());
-    bindings.push(args.board_id.clone());
+    Ok(card_ids)
+}
 
-    let query_str = query_parts.join(", ").replace(", WHERE", " WHERE");
+/// Returns how many cards gained the tag.
+#[tauri::command]
+async fn add_tag_to_cards<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    board_id: String,
+    tag_id: String,
+    card_ids: Vec<String>,
+) -> Result<u64, String> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
 
-    let mut query = sqlx::query(&query_str);
-    for binding in bindings {
-        query = query.bind(binding);
+    let card_ids = validate_bulk_tag_targets_tx(&mut tx, &board_id, &tag_id, card_ids).await?;
+
+    let mut affected = 0;
+    for card_id in &card_ids {
+        let result =
+            sqlx::query("INSERT OR IGNORE INTO kanban_card_tags (card_id, tag_id) VALUES (?, ?)")
+                .bind(card_id)
+                .bind(&tag_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| format!("Falha ao adicionar tag ao cartão: {e}"))?;
+
+        if result.rows_affected() == 0 {
+            continue;
+        }
+
+        sqlx::query(
+            "UPDATE kanban_cards SET updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
+        )
+        .bind(card_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao atualizar cartão: {e}"))?;
+        affected += 1;
     }
 
-    query
-        .execute(&*pool)
+    tx.commit()
         .await
-        .map_err(|e| format!("Failed to update note: {e}"))?;
+        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
 
-    Ok(())
+    if affected > 0 {
+        emit_board_changed(&app, &board_id, "card_updated");
+    }
+
+    Ok(affected)
 }
 
+/// Returns how many cards lost the tag.
 #[tauri::command]
-async fn delete_note(pool: State<'_, DbPool>, id: String, board_id: String) -> Result<(), String> {
-    sqlx::query("DELETE FROM notes WHERE id = ? AND board_id = ?")
-        .bind(&id)
-        .bind(&board_id)
-        .execute(&*pool)
+async fn remove_tag_from_cards<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    board_id: String,
+    tag_id: String,
+    card_ids: Vec<String>,
+) -> Result<u64, String> {
+    let mut tx = pool
+        .begin()
         .await
-        .map_err(|e| format!("Failed to delete note: {e}"))?;
+        .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
 
-    Ok(())
-}
+    let card_ids = validate_bulk_tag_targets_tx(&mut tx, &board_id, &tag_id, card_ids).await?;
 
-#[tauri::command]
-async fn archive_note(pool: State<'_, DbPool>, id: String, board_id: String) -> Result<(), String> {
-    sqlx::query(
-        "UPDATE notes
-         SET archived_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now'),
-             updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
-         WHERE id = ? AND board_id = ?",
-    )
-    .bind(&id)
-    .bind(&board_id)
-    .execute(&*pool)
-    .await
-    .map_err(|e| format!("Failed to archive note: {e}"))?;
+    let mut affected = 0;
+    for card_id in &card_ids {
+        let result = sqlx::query("DELETE FROM kanban_card_tags WHERE card_id = ? AND tag_id = ?")
+            .bind(card_id)
+            .bind(&tag_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Falha ao remover tag do cartão: {e}"))?;
 
-    Ok(())
-}
+        if result.rows_affected() == 0 {
+            continue;
+        }
 
-// ============================================================================
-// HOME DASHBOARD COMMANDS
-// ============================================================================
+        sqlx::query(
+            "UPDATE kanban_cards SET updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
+        )
+        .bind(card_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao atualizar cartão: {e}"))?;
+        affected += 1;
+    }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct TaskStats {
-    pub total_projects: i64,
-    pub active_projects: i64,
-    pub tasks_today: i64,
-    pub tasks_this_week: i64,
-    pub completed_today: i64,
-    pub completed_this_week: i64,
-    pub overdue_tasks: i64,
+    tx.commit()
+        .await
+        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
+
+    if affected > 0 {
+        emit_board_changed(&app, &board_id, "card_updated");
+    }
+
+    Ok(affected)
 }
 
 #[tauri::command]
-async fn get_task_statistics(pool: State<'_, DbPool>) -> Result<TaskStats, String> {
-    let query = r#"
-        SELECT
-            COUNT(DISTINCT b.id) as total_projects,
-            COUNT(DISTINCT CASE WHEN b.archived_at IS NULL THEN b.id END) as active_projects,
-            COUNT(CASE WHEN date(t.due_date) = date('now') AND t.archived_at IS NULL THEN 1 END) as tasks_today,
-            COUNT(CASE WHEN t.due_date >= date('now', '-7 days') AND t.archived_at IS NULL THEN 1 END) as tasks_this_week,
-            COUNT(CASE WHEN t.archived_at IS NULL AND (
-                SELECT COUNT(*) FROM kanban_columns c2 WHERE c2.id = t.column_id AND (
-                    LOWER(c2.title) LIKE '%done%' OR
-                    LOWER(c2.title) LIKE '%complete%' OR
-                    LOWER(c2.title) LIKE '%finished%'
-                )
-            ) > 0 THEN 1 END) as completed_today,
-            COUNT(CASE WHEN t.archived_at IS NULL AND t.updated_at >= date('now', '-7 days') AND (
-                SELECT COUNT(*) FROM kanban_columns c2 WHERE c2.id = t.column_id AND (
-                    LOWER(c2.title) LIKE '%done%' OR
-                    LOWER(c2.title) LIKE '%complete%' OR
-                    LOWER(c2.title) LIKE '%finished%'
-                )
-            ) > 0 THEN 1 END) as completed_this_week,
-            COUNT(CASE WHEN t.due_date < datetime('now') AND t.archived_at IS NULL AND (
-                SELECT COUNT(*) FROM kanban_columns c2 WHERE c2.id = t.column_id AND (
-                    LOWER(c2.title) NOT LIKE '%done%' AND
-                    LOWER(c2.title) NOT LIKE '%complete%' AND
-                    LOWER(c2.title) NOT LIKE '%finished%'
-                )
-            ) > 0 THEN 1 END) as overdue_tasks
-        FROM kanban_boards b
-        LEFT JOIN kanban_columns col ON col.board_id = b.id
-        LEFT JOIN kanban_cards t ON t.column_id = col.id
-    "#;
+async fn set_card_tags<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    args: SetCardTagsArgs,
+) -> Result<Vec<Value>, String> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
 
-    let row = sqlx::query(query)
-        .fetch_one(&*pool)
+    let existing_board =
+        sqlx::query_scalar::<_, Option<String>>("SELECT board_id FROM kanban_cards WHERE id = ?")
+            .bind(&args.card_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| format!("Falha ao carregar cartão: {e}"))?
+            .ok_or_else(|| "Cartão não encontrado.".to_string())?;
+
+    if existing_board != args.board_id {
+        return Err("O cartão não pertence ao quadro informado.".to_string());
+    }
+
+    let tags = set_card_tags_tx(&mut tx, &args.card_id, &args.board_id, &args.tag_ids)
         .await
-        .map_err(|e| format!("Failed to get task statistics: {e}"))?;
+        .map_err(|e| format!("Falha ao atualizar tags do cartão: {e}"))?;
 
-    Ok(TaskStats {
-        total_projects: row.get::<i64, _>("total_projects"),
-        active_projects: row.get::<i64, _>("active_projects"),
-        tasks_today: row.get::<i64, _>("tasks_today"),
-        tasks_this_week: row.get::<i64, _>("tasks_this_week"),
-        completed_today: row.get::<i64, _>("completed_today"),
-        completed_this_week: row.get::<i64, _>("completed_this_week"),
-        overdue_tasks: row.get::<i64, _>("overdue_tasks"),
-    })
-}
+    tx.commit()
+        .await
+        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Activity {
-    pub id: String,
-    pub activity_type: String,
-    pub title: String,
-    pub board_name: String,
-    pub board_icon: Option<String>,
-    pub timestamp: String,
-    pub entity_id: String,
-    pub entity_type: String,
+    emit_board_changed(&app, &args.board_id, "card_tags_updated");
+
+    Ok(tags)
+}
+struct NewCardRecord {
+    id: String,
+    board_id: String,
+    column_id: String,
+    title: String,
+    description: Option<String>,
+    position: i64,
+    priority: String,
+    due_date: Option<String>,
+    estimate_minutes: Option<i64>,
+    tag_ids: Vec<String>,
 }
 
-#[tauri::command]
-async fn get_recent_activity(
-    pool: State<'_, DbPool>,
-    limit: Option<i32>,
-) -> Result<Vec<Activity>, String> {
-    let limit = limit.unwrap_or(10);
+/// Inserts a validated card and records its `card_created` activity in the same
+/// transaction, so every creation path shows up in the activity feed.
+async fn insert_card_tx(
+    tx: &mut Transaction<'_, Sqlite>,
+    card: &NewCardRecord,
+) -> Result<(), CommandError> {
+    let title = card.title.trim().to_string();
+    if title.is_empty() {
+        return Err(CommandError::Validation(
+            "O título do cartão não pode ser vazio.".to_string(),
+        ));
+    }
+    validate_string_input(&title, 200, "Título do cartão").map_err(CommandError::Validation)?;
+    validate_priority(&card.priority).map_err(CommandError::Validation)?;
+    validate_estimate_minutes(card.estimate_minutes).map_err(CommandError::Validation)?;
+    let due_date =
+        normalize_due_date(card.due_date.as_deref()).map_err(CommandError::Validation)?;
 
-    let query = r#"
-        SELECT
-            'card_created' as activity_type,
-            c.id as entity_id,
-            'card' as entity_type,
-            c.title,
-            b.title as board_name,
-            b.icon as board_icon,
-            c.created_at as timestamp
-        FROM kanban_cards c
-        JOIN kanban_boards b ON b.id = c.board_id
-        WHERE c.archived_at IS NULL
+    let normalized_description = normalize_optional_text(card.description.clone());
 
-        UNION ALL
+    let stored_board_id =
+        sqlx::query_scalar::<_, String>("SELECT board_id FROM kanban_columns WHERE id = ?")
+            .bind(&card.column_id)
+            .fetch_optional(&mut **tx)
+            .await
+            .map_err(|e| {
+                CommandError::Database(format!("Falha ao validar coluna informada: {e}"))
+            })?;
 
-        SELECT
-            'card_updated' as activity_type,
-            c.id as entity_id,
-            'card' as entity_type,
-            c.title,
-            b.title as board_name,
-            b.icon as board_icon,
-            c.updated_at as timestamp
-        FROM kanban_cards c
-        JOIN kanban_boards b ON b.id = c.board_id
-        WHERE c.archived_at IS NULL
+    match stored_board_id {
+        Some(db_board_id) if db_board_id == card.board_id => {}
+        Some(_) => {
+            return Err(CommandError::Validation(
+                "A coluna informada não pertence ao quadro selecionado.".to_string(),
+            ));
+        }
+        None => return Err(CommandError::NotFound("Coluna não encontrada.".to_string())),
+    }
 
-        UNION ALL
+    let max_position = sqlx::query_scalar::<_, Option<i64>>(
+        "SELECT MAX(position) FROM kanban_cards WHERE column_id = ?",
+    )
+    .bind(&card.column_id)
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(|e| CommandError::Database(format!("Falha ao obter posição máxima dos cartões: {e}")))?
+    .unwrap_or(-1);
 
-        SELECT
-            'board_created' as activity_type,
-            b.id as entity_id,
-            'board' as entity_type,
-            b.title,
-            b.title as board_name,
-            b.icon as board_icon,
-            b.created_at as timestamp
-        FROM kanban_boards b
-        WHERE b.archived_at IS NULL
+    let mut normalized_position = card.position;
+    if normalized_position < 0 || normalized_position > max_position + 1 {
+        normalized_position = max_position + 1;
+    }
 
-        ORDER BY timestamp DESC
-        LIMIT ?
-    "#;
+    let duplicate = sqlx::query_scalar::<_, Option<i64>>(
+        "SELECT 1 FROM kanban_cards WHERE column_id = ? AND position = ? LIMIT 1",
+    )
+    .bind(&card.column_id)
+    .bind(normalized_position)
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(|e| {
+        CommandError::Database(format!(
+            "Falha ao verificar posições duplicadas de cartões: {e}"
+        ))
+    })?;
 
-    let activities = sqlx::query(query)
-        .bind(limit)
-        .fetch_all(&*pool)
+    if duplicate.is_some() {
+        return Err(CommandError::Conflict(format!(
+            "Já existe um cartão na posição {} desta coluna. Ajuste a ordem e tente novamente.",
+            normalized_position
+        )));
+    }
+
+    sqlx::query(
+        "INSERT INTO kanban_cards (id, board_id, column_id, title, description, position, priority, due_date, estimate_minutes, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))",
+    )
+    .bind(&card.id)
+    .bind(&card.board_id)
+    .bind(&card.column_id)
+    .bind(&title)
+    .bind(normalized_description)
+    .bind(normalized_position)
+    .bind(&card.priority)
+    .bind(due_date)
+    .bind(card.estimate_minutes)
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| CommandError::Database(format!("Falha ao criar cartão: {e}")))?;
+
+    normalize_card_positions_tx(tx, &card.column_id)
         .await
-        .map_err(|e| format!("Failed to get recent activity: {e}"))?;
+        .map_err(|e| {
+            CommandError::Database(format!("Falha ao normalizar posições dos cartões: {e}"))
+        })?;
 
-    let mapped_activities: Vec<Activity> = activities
-        .into_iter()
-        .map(|row| {
-            let activity_type = row.get::<String, _>("activity_type");
-            let entity_id = row.get::<String, _>("entity_id");
-            let title = row.get::<String, _>("title");
-            let board_name = row.get::<String, _>("board_name");
-            let board_icon = row.get::<Option<String>, _>("board_icon");
-            let timestamp = row.get::<String, _>("timestamp");
-            let entity_type = row.get::<String, _>("entity_type");
-
-            Activity {
-                id: format!("{}-{}", activity_type, entity_id),
-                activity_type,
-                title,
-                board_name,
-                board_icon,
-                timestamp,
-                entity_id,
-                entity_type,
-            }
-        })
-        .collect();
+    set_card_tags_tx(tx, &card.id, &card.board_id, &card.tag_ids)
+        .await
+        .map_err(|e| CommandError::Validation(format!("Falha ao associar tags ao cartão: {e}")))?;
+
+    log_activity_tx(
+        tx,
+        &card.board_id,
+        "card",
+        &card.id,
+        "card_created",
+        &title,
+        Some(json!({ "columnId": card.column_id })),
+    )
+    .await
+    .map_err(CommandError::Database)?;
 
-    Ok(mapped_activities)
+    Ok(())
 }
 
 #[tauri::command]
-async fn get_favorite_boards(pool: State<'_, DbPool>) -> Result<Vec<Value>, String> {
-    let query = r#"
-        SELECT
-            b.id,
-            b.title,
-            b.icon,
-            b.emoji,
-            b.color,
-            b.created_at,
-            b.updated_at,
-            b.is_favorite,
-            COUNT(DISTINCT c.id) as total_cards,
-            COUNT(DISTINCT CASE WHEN c.archived_at IS NULL THEN c.id END) as active_cards
-        FROM kanban_boards b
-        LEFT JOIN kanban_columns col ON col.board_id = b.id
-        LEFT JOIN kanban_cards c ON c.column_id = col.id
-        WHERE b.is_favorite = 1 AND b.archived_at IS NULL
-        GROUP BY b.id
-        ORDER BY b.updated_at DESC
-    "#;
+#[allow(clippy::too_many_arguments)]
+async fn create_card<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    id: String,
+    board_id: String,
+    column_id: String,
+    title: String,
+    description: Option<String>,
+    position: i64,
+    priority: String,
+    due_date: Option<String>,
+    tag_ids: Option<Vec<String>>,
+    estimate_minutes: Option<i64>,
+) -> Result<(), CommandError> {
+    let record = NewCardRecord {
+        id,
+        board_id,
+        column_id,
+        title,
+        description,
+        position,
+        priority,
+        due_date,
+        estimate_minutes,
+        tag_ids: tag_ids.unwrap_or_default(),
+    };
 
-    let boards = sqlx::query(query)
-        .fetch_all(&*pool)
+    let mut tx = pool
+        .begin()
         .await
-        .map_err(|e| format!("Failed to get favorite boards: {e}"))?;
+        .map_err(|e| CommandError::Database(format!("Falha ao abrir transação: {e}")))?;
 
-    let mapped_boards: Vec<Value> = boards
-        .into_iter()
-        .map(|board| {
-            let id = board.get::<String, _>("id");
-            let title = board.get::<String, _>("title");
-            let icon = board.get::<String, _>("icon");
-            let emoji = board.get::<Option<String>, _>("emoji");
-            let color = board.get::<Option<String>, _>("color");
-            let created_at = board.get::<String, _>("created_at");
-            let updated_at = board.get::<String, _>("updated_at");
-            let is_favorite: i32 = board.get("is_favorite");
-            let total_cards: i64 = board.get("total_cards");
-            let active_cards: i64 = board.get("active_cards");
+    insert_card_tx(&mut tx, &record).await?;
 
-            json!({
-                "id": id,
-                "title": title,
-                "icon": icon,
-                "emoji": emoji,
-                "color": color,
-                "isFavorite": is_favorite != 0,
-                "createdAt": created_at,
-                "updatedAt": updated_at,
-                "totalCards": total_cards,
-                "activeCards": active_cards,
-            })
-        })
-        .collect();
+    tx.commit()
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao confirmar transação: {e}")))?;
 
-    Ok(mapped_boards)
+    emit_board_changed(&app, &record.board_id, "card_created");
+
+    Ok(())
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct TaskWithDeadline {
-    pub id: String,
-    pub title: String,
-    pub deadline: String,
-    pub board_name: String,
-    pub board_id: String,
-    pub is_overdue: bool,
-    pub days_until: i64,
+struct NewCard {
+    title: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    priority: Option<String>,
+    #[serde(default)]
+    due_date: Option<String>,
+    #[serde(default)]
+    estimate_minutes: Option<i64>,
+    #[serde(default)]
+    tag_ids: Option<Vec<String>>,
 }
 
+/// Inserts `cards` in order starting at `start_index`, pushing the existing cards
+/// at and after that index down. Returns the generated ids in insertion order.
 #[tauri::command]
-async fn get_upcoming_deadlines(
+async fn insert_cards_at<R: Runtime>(
+    app: AppHandle<R>,
     pool: State<'_, DbPool>,
-    days_ahead: Option<i32>,
-) -> Result<Vec<TaskWithDeadline>, String> {
-    let days_ahead = days_ahead.unwrap_or(7);
+    board_id: String,
+    column_id: String,
+    cards: Vec<NewCard>,
+    start_index: i64,
+) -> Result<Vec<String>, String> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
 
-    let query = r#"
-        SELECT
-            c.id,
-            c.title,
-            c.due_date,
-            b.title as board_name,
-            b.id as board_id
-        FROM kanban_cards c
-        JOIN kanban_columns col ON col.id = c.column_id
-        JOIN kanban_boards b ON b.id = col.board_id
-        WHERE c.due_date IS NOT NULL
-        AND c.archived_at IS NULL
-        AND date(c.due_date) <= date('now', '+' || ? || ' days')
-        AND b.archived_at IS NULL
-        ORDER BY c.due_date ASC
-    "#;
-
-    let tasks = sqlx::query(query)
-        .bind(days_ahead as i64)
-        .fetch_all(&*pool)
+    normalize_card_positions_tx(&mut tx, &column_id)
         .await
-        .map_err(|e| format!("Failed to get upcoming deadlines: {e}"))?;
+        .map_err(|e| format!("Falha ao normalizar posições dos cartões: {e}"))?;
 
-    let mapped_tasks: Vec<TaskWithDeadline> = tasks
-        .into_iter()
-        .map(|task| {
-            let id = task.get::<String, _>("id");
-            let title = task.get::<String, _>("title");
-            let due_date: String = task.get("due_date");
-            let board_name = task.get::<String, _>("board_name");
-            let board_id = task.get::<String, _>("board_id");
-            let is_overdue = due_date < chrono::Utc::now().to_rfc3339();
-
-            TaskWithDeadline {
-                id,
-                title,
-                deadline: due_date,
-                board_name,
-                board_id,
-                is_overdue,
-                days_until: 0, // Will be calculated below
-            }
-        })
-        .collect();
+    let card_count =
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM kanban_cards WHERE column_id = ?")
+            .bind(&column_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| format!("Falha ao contar cartões da coluna: {e}"))?;
+
+    let start = start_index.clamp(0, card_count);
+    let mut created_ids = Vec::with_capacity(cards.len());
 
-    // Calculate days until for each task
-    let mut mapped_tasks_with_days: Vec<TaskWithDeadline> = Vec::new();
-    for task in mapped_tasks {
-        let days_until = sqlx::query_scalar::<_, Option<i64>>(
-            "SELECT CAST(julianday(?) - julianday('now') AS INTEGER)",
+    for (offset, card) in cards.into_iter().enumerate() {
+        let position = start + offset as i64;
+
+        sqlx::query(
+            "UPDATE kanban_cards SET position = position + 1, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE column_id = ? AND position >= ?",
         )
-        .bind(&task.deadline)
-        .fetch_one(&*pool)
+        .bind(&column_id)
+        .bind(position)
+        .execute(&mut *tx)
         .await
-        .unwrap_or(None)
-        .unwrap_or(0);
+        .map_err(|e| format!("Falha ao deslocar cartões da coluna: {e}"))?;
+
+        let record = NewCardRecord {
+            id: Uuid::new_v4().to_string(),
+            board_id: board_id.clone(),
+            column_id: column_id.clone(),
+            title: card.title,
+            description: card.description,
+            position,
+            priority: card.priority.unwrap_or_else(|| "none".to_string()),
+            due_date: card.due_date,
+            estimate_minutes: card.estimate_minutes,
+            tag_ids: card.tag_ids.unwrap_or_default(),
+        };
 
-        mapped_tasks_with_days.push(TaskWithDeadline { days_until, ..task });
+        insert_card_tx(&mut tx, &record)
+            .await
+            .map_err(|e| e.to_string())?;
+        created_ids.push(record.id);
     }
 
-    Ok(mapped_tasks_with_days)
-}
+    tx.commit()
+        .await
+        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct SearchResult {
-    pub id: String,
-    pub title: String,
-    pub item_type: String,
-    pub board_id: String,
-    pub board_name: String,
-    pub description: Option<String>,
+    emit_board_changed(&app, &board_id, "card_created");
+
+    Ok(created_ids)
 }
 
+/// Copies a card with its subtasks and tags into `target_column_id` under a new id.
+/// Across boards, tags are matched by label on the target board and created when
+/// missing. Attachments are never copied.
 #[tauri::command]
-async fn global_search(
+async fn copy_card<R: Runtime>(
+    app: AppHandle<R>,
     pool: State<'_, DbPool>,
-    query: String,
-) -> Result<Vec<SearchResult>, String> {
-    let search_term = format!("%{}%", query.trim());
-    let mut results = Vec::new();
-
-    // Search in boards
-    let board_rows = sqlx::query(
-        r#"
-        SELECT
-            b.id,
-            b.title,
-            b.description,
-            b.title as board_name,
-            'board' as item_type,
-            b.id as board_id
-        FROM kanban_boards b
-        WHERE b.archived_at IS NULL
-        AND (b.title LIKE ? OR b.description LIKE ?)
-        ORDER BY b.title ASC
-        LIMIT 20
-        "#,
-    )
-    .bind(&search_term)
-    .bind(&search_term)
-    .fetch_all(&*pool)
-    .await
-    .map_err(|e| format!("Failed to search boards: {e}"))?;
+    source_card_id: String,
+    target_board_id: String,
+    target_column_id: String,
+    position: i64,
+) -> Result<Value, String> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
 
-    for row in board_rows {
-        results.push(SearchResult {
-            id: row.get("id"),
-            title: row.get("title"),
-            item_type: "board".to_string(),
-            board_id: row.get("board_id"),
-            board_name: row.get("board_name"),
-            description: row.get("description"),
-        });
-    }
+    type SourceCard = (
+        String,
+        String,
+        Option<String>,
+        String,
+        Option<String>,
+        Option<i64>,
+    );
+    let (source_board_id, title, description, priority, due_date, estimate_minutes) =
+        sqlx::query_as::<_, SourceCard>(
+            "SELECT board_id, title, description, priority, due_date, estimate_minutes FROM kanban_cards WHERE id = ?",
+        )
+        .bind(&source_card_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao carregar cartão: {e}"))?
+        .ok_or_else(|| "Cartão não encontrado.".to_string())?;
 
-    // Search in cards
-    let card_rows = sqlx::query(
-        r#"
-        SELECT
-            c.id,
-            c.title,
-            c.description,
-            b.title as board_name,
-            b.id as board_id,
-            'card' as item_type
-        FROM kanban_cards c
-        JOIN kanban_columns col ON col.id = c.column_id
-        JOIN kanban_boards b ON b.id = col.board_id
-        WHERE c.archived_at IS NULL
-        AND (c.title LIKE ? OR c.description LIKE ?)
-        ORDER BY c.updated_at DESC
-        LIMIT 50
-        "#,
-    )
-    .bind(&search_term)
-    .bind(&search_term)
-    .fetch_all(&*pool)
+    let source_tags = sqlx::query_as::<_, (String, String, Option<String>)>(
+        "SELECT t.id, t.label, t.color FROM kanban_card_tags ct JOIN kanban_tags t ON t.id = ct.tag_id WHERE ct.card_id = ?",
+    )
+    .bind(&source_card_id)
+    .fetch_all(&mut *tx)
     .await
-    .map_err(|e| format!("Failed to search cards: {e}"))?;
+    .map_err(|e| format!("Falha ao carregar tags do cartão: {e}"))?;
 
-    for row in card_rows {
-        results.push(SearchResult {
-            id: row.get("id"),
-            title: row.get("title"),
-            item_type: "card".to_string(),
-            board_id: row.get("board_id"),
-            board_name: row.get("board_name"),
-            description: row.get("description"),
-        });
-    }
+    let mut tag_ids = Vec::with_capacity(source_tags.len());
+    for (tag_id, label, color) in source_tags {
+        if source_board_id == target_board_id {
+            tag_ids.push(tag_id);
+            continue;
+        }
 
-    // Search in notes
-    let note_rows = sqlx::query(
-        r#"
-        SELECT
-            n.id,
-            n.title,
-            n.content as description,
-            b.title as board_name,
-            b.id as board_id,
-            'note' as item_type
-        FROM notes n
-        JOIN kanban_boards b ON b.id = n.board_id
-        WHERE n.archived_at IS NULL
-        AND (n.title LIKE ? OR n.content LIKE ?)
-        ORDER BY n.updated_at DESC
-        LIMIT 30
-        "#,
-    )
-    .bind(&search_term)
-    .bind(&search_term)
-    .fetch_all(&*pool)
-    .await
-    .map_err(|e| format!("Failed to search notes: {e}"))?;
+        let existing = sqlx::query_scalar::<_, String>(
+            "SELECT id FROM kanban_tags WHERE board_id = ? AND LOWER(label) = LOWER(?) LIMIT 1",
+        )
+        .bind(&target_board_id)
+        .bind(&label)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao carregar tags do quadro de destino: {e}"))?;
 
-    for row in note_rows {
-        results.push(SearchResult {
-            id: row.get("id"),
-            title: row.get("title"),
-            item_type: "note".to_string(),
-            board_id: row.get("board_id"),
-            board_name: row.get("board_name"),
-            description: row.get("description"),
-        });
+        let target_tag_id = match existing {
+            Some(id) => id,
+            None => {
+                let new_tag_id = Uuid::new_v4().to_string();
+                sqlx::query(
+                    "INSERT INTO kanban_tags (id, board_id, label, color, position, created_at, updated_at) VALUES (?, ?, ?, ?, (SELECT COALESCE(MAX(position), -1) + 1 FROM kanban_tags WHERE board_id = ?), strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))",
+                )
+                .bind(&new_tag_id)
+                .bind(&target_board_id)
+                .bind(&label)
+                .bind(color)
+                .bind(&target_board_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| format!("Falha ao criar tag no quadro de destino: {e}"))?;
+                new_tag_id
+            }
+        };
+        tag_ids.push(target_tag_id);
     }
 
-    Ok(results)
-}
+    normalize_card_positions_tx(&mut tx, &target_column_id)
+        .await
+        .map_err(|e| format!("Falha ao normalizar posições dos cartões: {e}"))?;
 
-#[tauri::command]
-async fn set_workspace_icon_path(
-    app: AppHandle,
-    pool: State<'_, DbPool>,
-    workspace_id: String,
-    icon_path: String,
-) -> Result<Value, String> {
-    let workspace_id = workspace_id.trim();
-    if workspace_id.is_empty() {
-        return Err("Identificador do workspace inválido.".to_string());
-    }
+    let card_count =
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM kanban_cards WHERE column_id = ?")
+            .bind(&target_column_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| format!("Falha ao contar cartões da coluna: {e}"))?;
 
-    let icon_path = icon_path.trim();
-    if icon_path.is_empty() {
-        return Err("Caminho do ícone inválido.".to_string());
-    }
+    let position = position.clamp(0, card_count);
 
-    // Get the existing icon to clean it up
-    let existing_icon: Option<String> =
-        sqlx::query_scalar("SELECT icon_path FROM workspaces WHERE id = ?")
-            .bind(workspace_id)
-            .fetch_optional(&*pool)
-            .await
-            .map_err(|e| format!("Falha ao carregar workspace: {e}"))?
-            .flatten();
+    sqlx::query(
+        "UPDATE kanban_cards SET position = position + 1, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE column_id = ? AND position >= ?",
+    )
+    .bind(&target_column_id)
+    .bind(position)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("Falha ao deslocar cartões da coluna: {e}"))?;
+
+    let new_card_id = Uuid::new_v4().to_string();
+    let record = NewCardRecord {
+        id: new_card_id.clone(),
+        board_id: target_board_id.clone(),
+        column_id: target_column_id,
+        title,
+        description,
+        position,
+        priority,
+        due_date,
+        estimate_minutes,
+        tag_ids,
+    };
+    insert_card_tx(&mut tx, &record)
+        .await
+        .map_err(|e| e.to_string())?;
 
-    // Update the icon_path in the database
-    let result = sqlx::query(
-        "UPDATE workspaces SET icon_path = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
+    let subtask_ids = sqlx::query_scalar::<_, String>(
+        "SELECT id FROM kanban_subtasks WHERE card_id = ? ORDER BY position ASC",
     )
-    .bind(icon_path)
-    .bind(workspace_id)
-    .execute(&*pool)
+    .bind(&source_card_id)
+    .fetch_all(&mut *tx)
     .await
-    .map_err(|e| {
-        log::error!("Failed to update workspace icon path for {workspace_id}: {e}");
-        e.to_string()
-    })?;
+    .map_err(|e| format!("Falha ao carregar subtarefas: {e}"))?;
 
-    if result.rows_affected() == 0 {
-        return Err("Workspace não encontrado.".to_string());
+    for source_subtask_id in subtask_ids {
+        sqlx::query(
+            "INSERT INTO kanban_subtasks (id, board_id, card_id, title, is_completed, position, created_at, updated_at)
+            SELECT ?, ?, ?, title, is_completed, position, strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+            FROM kanban_subtasks WHERE id = ?",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(&target_board_id)
+        .bind(&new_card_id)
+        .bind(&source_subtask_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao copiar subtarefa: {e}"))?;
     }
 
-    // Clean up the old icon file if it exists and is different
-    if let Some(previous) = existing_icon
-        && previous != icon_path
-    {
-        let _ = remove_workspace_icon_file(&app, &previous);
-    }
+    let card = fetch_card_by_id(&mut tx, &new_card_id)
+        .await
+        .map_err(|e| format!("Falha ao carregar cartão copiado: {e}"))?
+        .ok_or_else(|| "Cartão copiado não encontrado.".to_string())?;
 
-    // Return the updated workspace
-    sqlx::query("SELECT id, name, color, icon_path, created_at, updated_at, archived_at FROM workspaces WHERE id = ?")
-        .bind(workspace_id)
-        .try_map(map_workspace_row)
-        .fetch_one(&*pool)
+    tx.commit()
         .await
-        .map_err(|e| {
-            log::error!("Failed to load workspace after icon path update {workspace_id}: {e}");
-            e.to_string()
-        })
+        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
+
+    emit_board_changed(&app, &target_board_id, "card_created");
+
+    Ok(card)
 }
 
-#[cfg_attr(mobile, tauri::mobile_entry_point)]
-pub fn run() {
-    tauri::Builder::default()
-        .plugin(tauri_plugin_updater::Builder::new().build())
-        .plugin(tauri_plugin_process::init())
-        .plugin(tauri_plugin_notification::init())
-        .plugin(
-            tauri_plugin_log::Builder::new()
-                // Use Debug level in development, Info in production
-                .level(if cfg!(debug_assertions) {
-                    log::LevelFilter::Debug
-                } else {
-                    log::LevelFilter::Info
-                })
-                .targets([
-                    // Always log to stdout for development
-                    tauri_plugin_log::Target::new(tauri_plugin_log::TargetKind::Stdout),
-                    // Log to webview console for development
-                    tauri_plugin_log::Target::new(tauri_plugin_log::TargetKind::Webview),
-                    // Log to system logs on macOS (appears in Console.app)
-                    #[cfg(target_os = "macos")]
-                    tauri_plugin_log::Target::new(tauri_plugin_log::TargetKind::LogDir {
-                        file_name: None,
-                    }),
-                ])
-                .build(),
+/// Appends a card to `column_id`, or to the board's default intake column
+/// (falling back to its first enabled column) when none is given.
+#[tauri::command]
+async fn quick_add_card<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    board_id: String,
+    title: String,
+    column_id: Option<String>,
+) -> Result<Value, String> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
+
+    let column_id = match column_id.filter(|id| !id.trim().is_empty()) {
+        Some(column_id) => column_id,
+        None => sqlx::query_scalar::<_, String>(
+            "SELECT id FROM kanban_columns WHERE board_id = ? AND archived_at IS NULL AND (is_default_intake = 1 OR is_enabled = 1) ORDER BY is_default_intake DESC, position ASC LIMIT 1",
         )
-        .plugin(tauri_plugin_fs::init())
-        .plugin(tauri_plugin_persisted_scope::init())
-        .plugin(tauri_plugin_notification::init())
-        .plugin(tauri_plugin_dialog::init())
-        .plugin(tauri_plugin_clipboard_manager::init())
-        .plugin(tauri_plugin_opener::init())
-        .setup(|app| {
-            log::info!("🚀 Application starting up");
-            log::debug!(
-                "App handle initialized for package: {}",
-                app.package_info().name
-            );
+        .bind(&board_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao carregar coluna de entrada: {e}"))?
+        .ok_or_else(|| "Nenhuma coluna disponível para novos cartões.".to_string())?,
+    };
 
-            let handle = app.handle();
+    let record = NewCardRecord {
+        id: Uuid::new_v4().to_string(),
+        board_id,
+        column_id,
+        title,
+        description: None,
+        position: -1,
+        priority: "none".to_string(),
+        due_date: None,
+        estimate_minutes: None,
+        tag_ids: Vec::new(),
+    };
 
-            let pool =
-                tauri::async_runtime::block_on(establish_pool(handle)).map_err(|e| anyhow!(e))?;
+    insert_card_tx(&mut tx, &record)
+        .await
+        .map_err(|e| e.to_string())?;
 
-            tauri::async_runtime::block_on(initialize_schema(&pool)).map_err(|e| anyhow!(e))?;
+    let card = fetch_card_by_id(&mut tx, &record.id)
+        .await
+        .map_err(|e| format!("Falha ao carregar cartão: {e}"))?
+        .ok_or_else(|| "Cartão não encontrado.".to_string())?;
 
-            app.manage(pool);
+    tx.commit()
+        .await
+        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
 
-            // Set up native menu system
-            if let Err(e) = create_app_menu(app) {
-                log::error!("Failed to create app menu: {e}");
-                return Err(e);
-            }
+    emit_board_changed(&app, &record.board_id, "card_created");
 
-            // Set up menu event handlers
-            app.on_menu_event(move |app, event| {
-                log::debug!("Menu event received: {:?}", event.id());
+    Ok(card)
+}
 
-                match event.id().as_ref() {
-                    "about" => {
-                        log::info!("About menu item clicked");
-                        // Emit event to React for handling
-                        match app.emit("menu-about", ()) {
-                            Ok(_) => log::debug!("Successfully emitted menu-about event"),
-                            Err(e) => log::error!("Failed to emit menu-about event: {e}"),
-                        }
-                    }
-                    "check-updates" => {
-                        log::info!("Check for Updates menu item clicked");
-                        // Emit event to React for handling
-                        match app.emit("menu-check-updates", ()) {
-                            Ok(_) => log::debug!("Successfully emitted menu-check-updates event"),
-                            Err(e) => log::error!("Failed to emit menu-check-updates event: {e}"),
-                        }
-                    }
-                    "preferences" => {
-                        log::info!("Preferences menu item clicked");
-                        // Emit event to React for handling
-                        match app.emit("menu-preferences", ()) {
-                            Ok(_) => log::debug!("Successfully emitted menu-preferences event"),
-                            Err(e) => log::error!("Failed to emit menu-preferences event: {e}"),
-                        }
-                    }
-                    "toggle-left-sidebar" => {
-                        log::info!("Toggle Left Sidebar menu item clicked");
-                        // Emit event to React for handling
-                        match app.emit("menu-toggle-left-sidebar", ()) {
-                            Ok(_) => {
-                                log::debug!("Successfully emitted menu-toggle-left-sidebar event")
-                            }
-                            Err(e) => {
-                                log::error!("Failed to emit menu-toggle-left-sidebar event: {e}")
-                            }
-                        }
-                    }
-                    "toggle-right-sidebar" => {
-                        log::info!("Toggle Right Sidebar menu item clicked");
-                        // Emit event to React for handling
-                        match app.emit("menu-toggle-right-sidebar", ()) {
-                            Ok(_) => {
-                                log::debug!("Successfully emitted menu-toggle-right-sidebar event")
-                            }
-                            Err(e) => {
-                                log::error!("Failed to emit menu-toggle-right-sidebar event: {e}")
-                            }
-                        }
-                    }
-                    _ => {
-                        log::debug!("Unhandled menu event: {:?}", event.id());
-                    }
-                }
-            });
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SaveCardArgs {
+    id: String,
+    board_id: String,
+    column_id: String,
+    title: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    position: Option<i64>,
+    #[serde(default)]
+    priority: Option<String>,
+    #[serde(default)]
+    due_date: Option<String>,
+    #[serde(default)]
+    estimate_minutes: Option<i64>,
+    #[serde(default)]
+    tag_ids: Option<Vec<String>>,
+}
 
-            // Example of different log levels
-            log::trace!("This is a trace message (most verbose)");
-            log::debug!("This is a debug message (development only)");
-            log::info!("This is an info message (production)");
-            log::warn!("This is a warning message");
-            // log::error!("This is an error message");
+/// Creates the card when `id` is unknown and otherwise overwrites its fields with
+/// the given state through `update_card_tx`, so replaying the same call is
+/// idempotent. On update `position` is ignored unless the card changes column, in
+/// which case it is appended.
+#[tauri::command]
+async fn save_card<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    args: SaveCardArgs,
+) -> Result<Value, CommandError> {
+    let priority = args.priority.unwrap_or_else(|| "none".to_string());
 
-            Ok(())
-        })
-        .invoke_handler(tauri::generate_handler![
-            greet,
-            load_preferences,
-            save_preferences,
-            load_shortcuts,
-            save_shortcuts,
-            send_native_notification,
-            save_emergency_data,
-            load_emergency_data,
-            cleanup_old_recovery_files,
-            load_workspaces,
-            create_workspace,
-            update_workspace,
-            delete_workspace,
-            update_workspace_icon,
-            remove_workspace_icon,
-            save_cropped_workspace_icon,
-            set_workspace_icon_path,
-            get_workspace_icon_url,
-            load_boards,
-            create_board,
-            rename_board,
-            update_board_icon,
-            update_board_workspace,
-            delete_board,
-            load_columns,
-            create_column,
-            update_column,
-            delete_column,
-            move_column,
-            load_cards,
-            load_tags,
-            create_tag,
-            update_tag,
-            delete_tag,
-            set_card_tags,
-            create_subtask,
-            update_subtask,
-            delete_subtask,
-            create_card,
-            delete_card,
-            update_card,
-            move_card,
-            upload_image,
-            remove_image,
-            get_attachment_url,
-            open_attachment,
-            restore_attachment_version,
-            delete_attachment_version,
-            get_storage_stats,
-            clear_attachments,
-            reset_application_data,
-            import_application_data,
-            export_application_data,
-            load_notes,
-            create_note,
-            update_note,
-            delete_note,
-            archive_note,
-            get_task_statistics,
-            get_recent_activity,
-            get_favorite_boards,
-            get_upcoming_deadlines,
-            global_search
-        ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
-}
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao abrir transação: {e}")))?;
 
-#[derive(Debug, Serialize)]
-#[serde(rename_all = "camelCase")]
-struct UploadImageResponse {
-    success: bool,
-    file_path: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    attachment: Option<Value>,
-    error: Option<String>,
-}
+    let existing_column_id =
+        sqlx::query_scalar::<_, String>("SELECT column_id FROM kanban_cards WHERE id = ?")
+            .bind(&args.id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| CommandError::Database(format!("Falha ao carregar cartão: {e}")))?;
+    let change_kind = if existing_column_id.is_some() {
+        "card_updated"
+    } else {
+        "card_created"
+    };
 
-fn copy_workspace_icon(
-    app: &AppHandle,
-    workspace_id: &str,
-    file_path: &str,
-) -> Result<String, String> {
-    let app_data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to resolve app data directory: {e}"))?;
+    match existing_column_id {
+        None => {
+            let record = NewCardRecord {
+                id: args.id.clone(),
+                board_id: args.board_id.clone(),
+                column_id: args.column_id.clone(),
+                title: args.title,
+                description: args.description,
+                position: args.position.unwrap_or(-1),
+                priority,
+                due_date: args.due_date,
+                estimate_minutes: args.estimate_minutes,
+                tag_ids: args.tag_ids.unwrap_or_default(),
+            };
+            insert_card_tx(&mut tx, &record).await?;
+        }
+        Some(column_id_db) => {
+            let patch = UpdateCardArgs {
+                id: args.id.clone(),
+                board_id: args.board_id.clone(),
+                title: Some(args.title),
+                description: Some(args.description),
+                priority: Some(priority),
+                due_date: Some(args.due_date),
+                clear_due_date: None,
+                remind_at: None,
+                clear_remind_at: None,
+                remind_recurrence: None,
+                estimate_minutes: Some(args.estimate_minutes),
+            };
+            update_card_tx(&app, &mut tx, &patch).await?;
 
-    let icons_dir = app_data_dir.join(WORKSPACE_ICON_DIR);
-    fs::create_dir_all(&icons_dir)
-        .map_err(|e| format!("Failed to create workspace icon directory: {e}"))?;
+            if column_id_db != args.column_id {
+                let target_board_id = sqlx::query_scalar::<_, String>(
+                    "SELECT board_id FROM kanban_columns WHERE id = ?",
+                )
+                .bind(&args.column_id)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(|e| {
+                    CommandError::Database(format!("Falha ao validar coluna informada: {e}"))
+                })?
+                .ok_or_else(|| CommandError::NotFound("Coluna não encontrada.".to_string()))?;
+
+                if target_board_id != args.board_id {
+                    return Err(CommandError::Validation(
+                        "A coluna informada não pertence ao quadro selecionado.".to_string(),
+                    ));
+                }
 
-    let source_path = PathBuf::from(file_path);
-    if !source_path.exists() {
-        return Err("Arquivo selecionado não existe.".to_string());
+                append_card_to_column_tx(&mut tx, &args.id, &args.column_id)
+                    .await
+                    .map_err(CommandError::Database)?;
+                normalize_card_positions_tx(&mut tx, &column_id_db)
+                    .await
+                    .map_err(|e| {
+                        CommandError::Database(format!(
+                            "Falha ao normalizar posições dos cartões: {e}"
+                        ))
+                    })?;
+            }
+
+            if let Some(tag_ids) = args.tag_ids.as_ref() {
+                set_card_tags_tx(&mut tx, &args.id, &args.board_id, tag_ids)
+                    .await
+                    .map_err(|e| {
+                        CommandError::Database(format!("Falha ao associar tags ao cartão: {e}"))
+                    })?;
+            }
+        }
     }
 
-    let extension = source_path
-        .extension()
-        .and_then(|ext| ext.to_str())
-        .unwrap_or("")
-        .to_lowercase();
+    let card = fetch_card_by_id(&mut tx, &args.id)
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao carregar cartão: {e}")))?
+        .ok_or_else(|| CommandError::NotFound("Cartão não encontrado.".to_string()))?;
 
-    let mime_type = mime_guess::from_path(&source_path).first_or_octet_stream();
-    let is_image_by_mime = mime_type.essence_str().starts_with("image/");
-    let is_image_by_extension = matches!(
-        extension.as_str(),
-        "jpg" | "jpeg" | "png" | "gif" | "webp" | "svg" | "bmp" | "ico" | "tiff" | "tif"
-    );
+    tx.commit()
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao confirmar transação: {e}")))?;
 
-    if !is_image_by_mime && !is_image_by_extension {
-        return Err(format!(
-            "Arquivo selecionado não é uma imagem válida. MIME detectado: {}",
-            mime_type
+    emit_board_changed(&app, &args.board_id, change_kind);
+
+    Ok(card)
+}
+
+#[tauri::command]
+async fn delete_card<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    id: String,
+    board_id: String,
+) -> Result<(), CommandError> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao abrir transação: {e}")))?;
+
+    let card_record = sqlx::query_as::<_, (String, String, String)>(
+        "SELECT column_id, board_id, title FROM kanban_cards WHERE id = ?",
+    )
+    .bind(&id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| CommandError::Database(format!("Falha ao carregar cartão: {e}")))?;
+
+    let Some((column_id, stored_board_id, title)) = card_record else {
+        return Err(CommandError::NotFound("Cartão não encontrado.".to_string()));
+    };
+
+    if stored_board_id != board_id {
+        return Err(CommandError::Validation(
+            "O cartão não pertence ao quadro informado.".to_string(),
         ));
     }
 
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map_err(|e| format!("System time error: {e}"))?
-        .as_secs();
+    sqlx::query("DELETE FROM kanban_cards WHERE id = ?")
+        .bind(&id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao excluir cartão: {e}")))?;
 
-    let filename = if extension.is_empty() {
-        format!("{workspace_id}_{timestamp}")
-    } else {
-        format!("{workspace_id}_{timestamp}.{extension}")
+    record_deletion_tx(&mut tx, "card", &id, &board_id)
+        .await
+        .map_err(CommandError::Database)?;
+    log_activity_tx(
+        &mut tx,
+        &board_id,
+        "card",
+        &id,
+        "card_deleted",
+        &title,
+        Some(json!({ "columnId": column_id })),
+    )
+    .await
+    .map_err(CommandError::Database)?;
+
+    normalize_card_positions_tx(&mut tx, &column_id)
+        .await
+        .map_err(|e| {
+            CommandError::Database(format!("Falha ao normalizar posições dos cartões: {e}"))
+        })?;
+
+    tx.commit()
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao confirmar transação: {e}")))?;
+
+    emit_board_changed(&app, &board_id, "card_deleted");
+
+    Ok(())
+}
+
+#[tauri::command]
+async fn archive_card<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    id: String,
+    board_id: String,
+) -> Result<(), CommandError> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao abrir transação: {e}")))?;
+
+    let card_record = sqlx::query_as::<_, (String, String, String, Option<String>)>(
+        "SELECT column_id, board_id, title, archived_at FROM kanban_cards WHERE id = ?",
+    )
+    .bind(&id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| CommandError::Database(format!("Falha ao carregar cartão: {e}")))?;
+
+    let Some((column_id, stored_board_id, title, archived_at)) = card_record else {
+        return Err(CommandError::NotFound("Cartão não encontrado.".to_string()));
     };
 
-    let destination_path = icons_dir.join(&filename);
-    fs::copy(&source_path, &destination_path)
-        .map_err(|e| format!("Falha ao copiar arquivo de ícone: {e}"))?;
+    if stored_board_id != board_id {
+        return Err(CommandError::Validation(
+            "O cartão não pertence ao quadro informado.".to_string(),
+        ));
+    }
+
+    if archived_at.is_some() {
+        return Err(CommandError::Validation(
+            "O cartão já está arquivado.".to_string(),
+        ));
+    }
+
+    sqlx::query(
+        "UPDATE kanban_cards SET archived_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
+    )
+    .bind(&id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| CommandError::Database(format!("Falha ao arquivar cartão: {e}")))?;
+
+    normalize_card_positions_tx(&mut tx, &column_id)
+        .await
+        .map_err(|e| {
+            CommandError::Database(format!("Falha ao normalizar posições dos cartões: {e}"))
+        })?;
+
+    log_activity_tx(
+        &mut tx,
+        &board_id,
+        "card",
+        &id,
+        "card_archived",
+        &title,
+        Some(json!({ "columnId": column_id })),
+    )
+    .await
+    .map_err(CommandError::Database)?;
+
+    tx.commit()
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao confirmar transação: {e}")))?;
+
+    emit_board_changed(&app, &board_id, "card_archived");
+
+    Ok(())
+}
+
+#[tauri::command]
+async fn unarchive_card<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    id: String,
+    board_id: String,
+    column_id: String,
+    position: i64,
+) -> Result<(), CommandError> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao abrir transação: {e}")))?;
+
+    let card_record = sqlx::query_as::<_, (String, String, String, Option<String>)>(
+        "SELECT board_id, column_id, title, archived_at FROM kanban_cards WHERE id = ?",
+    )
+    .bind(&id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| CommandError::Database(format!("Falha ao carregar cartão: {e}")))?;
+
+    let Some((stored_board_id, source_column_id, title, archived_at)) = card_record else {
+        return Err(CommandError::NotFound("Cartão não encontrado.".to_string()));
+    };
+
+    if stored_board_id != board_id {
+        return Err(CommandError::Validation(
+            "O cartão não pertence ao quadro informado.".to_string(),
+        ));
+    }
+
+    if archived_at.is_none() {
+        return Err(CommandError::Validation(
+            "O cartão não está arquivado.".to_string(),
+        ));
+    }
+
+    let column_board_id =
+        sqlx::query_scalar::<_, String>("SELECT board_id FROM kanban_columns WHERE id = ?")
+            .bind(&column_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| CommandError::Database(format!("Falha ao carregar coluna: {e}")))?
+            .ok_or_else(|| CommandError::NotFound("Coluna não encontrada.".to_string()))?;
+
+    if column_board_id != board_id {
+        return Err(CommandError::Validation(
+            "A coluna informada não pertence ao quadro selecionado.".to_string(),
+        ));
+    }
+
+    // Archived cards keep their slots, matching normalize_card_positions_tx.
+    let mut card_ids = sqlx::query_as::<_, (String,)>(
+        "SELECT id FROM kanban_cards WHERE column_id = ? AND id <> ? ORDER BY position ASC, created_at ASC",
+    )
+    .bind(&column_id)
+    .bind(&id)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| CommandError::Database(format!("Falha ao carregar cartões da coluna: {e}")))?
+    .into_iter()
+    .map(|(card_id,)| card_id)
+    .collect::<Vec<_>>();
+
+    let insert_at = position.clamp(0, card_ids.len() as i64) as usize;
+    card_ids.insert(insert_at, id.clone());
+
+    sqlx::query(
+        "UPDATE kanban_cards SET archived_at = NULL, column_id = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
+    )
+    .bind(&column_id)
+    .bind(&id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| CommandError::Database(format!("Falha ao restaurar cartão: {e}")))?;
+
+    for (index, card_id) in card_ids.iter().enumerate() {
+        sqlx::query(
+            "UPDATE kanban_cards SET position = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
+        )
+        .bind(index as i64)
+        .bind(card_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao atualizar posição do cartão: {e}")))?;
+    }
+
+    if source_column_id != column_id {
+        normalize_card_positions_tx(&mut tx, &source_column_id)
+            .await
+            .map_err(|e| {
+                CommandError::Database(format!("Falha ao normalizar coluna de origem: {e}"))
+            })?;
+    }
+
+    log_activity_tx(
+        &mut tx,
+        &board_id,
+        "card",
+        &id,
+        "card_unarchived",
+        &title,
+        Some(json!({ "columnId": column_id })),
+    )
+    .await
+    .map_err(CommandError::Database)?;
+
+    tx.commit()
+        .await
+        .map_err(|e| CommandError::Database(format!("Falha ao confirmar transação: {e}")))?;
+
+    emit_board_changed(&app, &board_id, "card_unarchived");
+
+    Ok(())
+}
+
+/// Archives an active card or restores an archived one to the end of its column,
+/// returning that column's active cards so the view refreshes in one round trip.
+#[tauri::command]
+async fn toggle_card_archived<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    board_id: String,
+    card_id: String,
+) -> Result<Value, String> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
+
+    let card_record = sqlx::query_as::<_, (String, String, String, Option<String>)>(
+        "SELECT column_id, board_id, title, archived_at FROM kanban_cards WHERE id = ?",
+    )
+    .bind(&card_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| format!("Falha ao carregar cartão: {e}"))?;
+
+    let Some((column_id, stored_board_id, title, archived_at)) = card_record else {
+        return Err("Cartão não encontrado.".to_string());
+    };
+
+    if stored_board_id != board_id {
+        return Err("O cartão não pertence ao quadro informado.".to_string());
+    }
+
+    let archived = archived_at.is_none();
+    if archived {
+        sqlx::query(
+            "UPDATE kanban_cards SET archived_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
+        )
+        .bind(&card_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao arquivar cartão: {e}"))?;
+    } else {
+        sqlx::query(
+            "UPDATE kanban_cards SET archived_at = NULL, position = (SELECT COALESCE(MAX(position), -1) + 1 FROM kanban_cards WHERE column_id = ? AND id <> ?), updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
+        )
+        .bind(&column_id)
+        .bind(&card_id)
+        .bind(&card_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao restaurar cartão: {e}"))?;
+    }
+
+    normalize_card_positions_tx(&mut tx, &column_id)
+        .await
+        .map_err(|e| format!("Falha ao normalizar posições dos cartões: {e}"))?;
+
+    log_activity_tx(
+        &mut tx,
+        &board_id,
+        "card",
+        &card_id,
+        if archived {
+            "card_archived"
+        } else {
+            "card_unarchived"
+        },
+        &title,
+        Some(json!({ "columnId": column_id })),
+    )
+    .await?;
+
+    let sql = format!(
+        "{CARD_SELECT_SQL} WHERE c.column_id = ? AND c.archived_at IS NULL ORDER BY c.position ASC, c.created_at ASC"
+    );
+    let cards = sqlx::query(&sql)
+        .bind(&column_id)
+        .try_map(map_card_row)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao carregar cartões da coluna: {e}"))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
+
+    emit_board_changed(
+        &app,
+        &board_id,
+        if archived {
+            "card_archived"
+        } else {
+            "card_unarchived"
+        },
+    );
+
+    Ok(json!({
+        "columnId": column_id,
+        "archived": archived,
+        "cards": cards,
+    }))
+}
+
+/// Restores every archived card on the board, appending them after the other
+/// cards of their column in their previous order. Returns how many were restored.
+#[tauri::command]
+async fn unarchive_all_cards<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    board_id: String,
+) -> Result<u32, String> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
+
+    let archived_cards = sqlx::query_as::<_, (String, String)>(
+        "SELECT id, column_id FROM kanban_cards WHERE board_id = ? AND archived_at IS NOT NULL ORDER BY column_id ASC, position ASC, created_at ASC",
+    )
+    .bind(&board_id)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| format!("Falha ao carregar cartões arquivados: {e}"))?;
+
+    let mut next_positions: HashMap<String, i64> = HashMap::new();
+    for (card_id, column_id) in &archived_cards {
+        let position = match next_positions.get(column_id) {
+            Some(position) => *position,
+            None => sqlx::query_scalar::<_, i64>(
+                "SELECT COALESCE(MAX(position), -1) + 1 FROM kanban_cards WHERE column_id = ?",
+            )
+            .bind(column_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| format!("Falha ao calcular posição do cartão: {e}"))?,
+        };
+
+        sqlx::query(
+            "UPDATE kanban_cards SET archived_at = NULL, position = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
+        )
+        .bind(position)
+        .bind(card_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao restaurar cartão: {e}"))?;
+
+        next_positions.insert(column_id.clone(), position + 1);
+    }
+
+    for column_id in next_positions.keys() {
+        normalize_card_positions_tx(&mut tx, column_id)
+            .await
+            .map_err(|e| format!("Falha ao normalizar posições dos cartões: {e}"))?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
+
+    if !archived_cards.is_empty() {
+        emit_board_changed(&app, &board_id, "card_unarchived");
+    }
+
+    Ok(archived_cards.len() as u32)
+}
+
+#[tauri::command]
+async fn archive_cards_before<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    board_id: String,
+    before: String,
+    done_only: bool,
+) -> Result<u32, String> {
+    let before = normalize_sync_timestamp(&before)?;
+
+    let keywords = load_done_column_keywords(&app).await;
+    let done_filter = if done_only {
+        format!(" AND {}", done_column_predicate("col", &keywords))
+    } else {
+        String::new()
+    };
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
+
+    let select_sql = format!(
+        "SELECT c.id, c.column_id FROM kanban_cards c JOIN kanban_columns col ON col.id = c.column_id WHERE c.board_id = ? AND c.archived_at IS NULL AND c.updated_at < ?{done_filter}"
+    );
+    let mut statement = sqlx::query_as::<_, (String, String)>(&select_sql)
+        .bind(&board_id)
+        .bind(&before);
+    if done_only {
+        for pattern in done_keyword_patterns(&keywords) {
+            statement = statement.bind(pattern);
+        }
+    }
+
+    let cards = statement
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao carregar cartões para arquivar: {e}"))?;
+
+    let mut affected_columns = BTreeSet::new();
+    for (card_id, column_id) in &cards {
+        sqlx::query(
+            "UPDATE kanban_cards SET archived_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
+        )
+        .bind(card_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao arquivar cartão: {e}"))?;
+        affected_columns.insert(column_id.clone());
+    }
+
+    for column_id in &affected_columns {
+        normalize_card_positions_tx(&mut tx, column_id)
+            .await
+            .map_err(|e| format!("Falha ao normalizar posições dos cartões: {e}"))?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
+
+    Ok(cards.len() as u32)
+}
+
+#[tauri::command]
+async fn clear_cards_due_date(
+    pool: State<'_, DbPool>,
+    board_id: String,
+    card_ids: Vec<String>,
+    clear_remind_at: Option<bool>,
+) -> Result<(), String> {
+    let unique_ids: BTreeSet<String> = card_ids
+        .into_iter()
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+        .collect();
+
+    if unique_ids.is_empty() {
+        return Ok(());
+    }
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
+
+    let mut builder = QueryBuilder::<Sqlite>::new(
+        "UPDATE kanban_cards SET due_date = NULL, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+    );
+    if clear_remind_at.unwrap_or(false) {
+        builder.push(", remind_at = NULL");
+    }
+    builder.push(" WHERE board_id = ");
+    builder.push_bind(&board_id);
+    builder.push(" AND id IN (");
+    let mut separated = builder.separated(", ");
+    for card_id in &unique_ids {
+        separated.push_bind(card_id);
+    }
+    builder.push(")");
+
+    let result = builder
+        .build()
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao limpar datas de vencimento: {e}"))?;
+
+    // Dropping the transaction rolls the update back
+    if result.rows_affected() != unique_ids.len() as u64 {
+        return Err("Alguns cartões informados não existem neste quadro.".to_string());
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
+
+    Ok(())
+}
+
+// Validation functions
+fn validate_filename(filename: &str) -> Result<(), String> {
+    // Regex pattern: only alphanumeric, dash, underscore, dot
+    let filename_pattern = Regex::new(r"^[a-zA-Z0-9_-]+(\.[a-zA-Z0-9]+)?$")
+        .map_err(|e| format!("Regex compilation error: {e}"))?;
+
+    if filename.is_empty() {
+        return Err("Filename cannot be empty".to_string());
+    }
+
+    if filename.len() > 100 {
+        return Err("Filename too long (max 100 characters)".to_string());
+    }
+
+    if !filename_pattern.is_match(filename) {
+        return Err(
+            "Invalid filename: only alphanumeric characters, dashes, underscores, and dots allowed"
+                .to_string(),
+        );
+    }
+
+    Ok(())
+}
+
+fn validate_string_input(input: &str, max_len: usize, field_name: &str) -> Result<(), String> {
+    if input.len() > max_len {
+        return Err(format!("{field_name} too long (max {max_len} characters)"));
+    }
+    Ok(())
+}
+
+fn validate_theme(theme: &str) -> Result<(), String> {
+    match theme {
+        "light" | "dark" | "system" => Ok(()),
+        _ => Err("Invalid theme: must be 'light', 'dark', or 'system'".to_string()),
+    }
+}
+
+// Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
+#[tauri::command]
+fn greet(name: &str) -> String {
+    // Input validation
+    if let Err(e) = validate_string_input(name, 100, "Name") {
+        log::warn!("Invalid greet input: {e}");
+        return format!("Error: {e}");
+    }
+
+    log::info!("Greeting user: {name}");
+    format!("Hello, {name}! You've been greeted from Rust!")
+}
+
+#[tauri::command]
+async fn get_version_info<R: Runtime>(
+    pool: State<'_, DbPool>,
+    app: AppHandle<R>,
+) -> Result<Value, String> {
+    let schema_version = sqlx::query_scalar::<_, i64>("PRAGMA user_version")
+        .fetch_one(&*pool)
+        .await
+        .map_err(|e| format!("Failed to read schema version: {e}"))?;
+
+    let sqlite_version = sqlx::query_scalar::<_, String>("SELECT sqlite_version()")
+        .fetch_one(&*pool)
+        .await
+        .map_err(|e| format!("Failed to read SQLite version: {e}"))?;
+
+    let package_info = app.package_info();
+
+    Ok(json!({
+        "appName": package_info.name,
+        "appVersion": package_info.version.to_string(),
+        "schemaVersion": schema_version,
+        "sqliteVersion": sqlite_version,
+    }))
+}
+
+// Preferences data structure
+// Only contains settings that should be persisted to disk
+// Fields use the frontend's camelCase names; the aliases still read files written
+// before the rename.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppPreferences {
+    pub theme: String,
+    #[serde(
+        default = "default_transparency_enabled",
+        alias = "transparency_enabled"
+    )]
+    pub transparency_enabled: bool,
+    #[serde(default, alias = "last_workspace_id")]
+    pub last_workspace_id: Option<String>,
+    /// Board reopened on launch; updated through `touch_last_board`.
+    #[serde(default, alias = "last_board_id")]
+    pub last_board_id: Option<String>,
+    /// Column title fragments treated as "done" by the statistics queries.
+    #[serde(
+        default = "default_done_column_keywords",
+        alias = "done_column_keywords"
+    )]
+    pub done_column_keywords: Vec<String>,
+    /// Rejects column titles that repeat another title on the board, ignoring case.
+    #[serde(default, alias = "enforce_unique_column_titles")]
+    pub enforce_unique_column_titles: bool,
+    /// Completes a card's subtasks when it is moved into a `done` column.
+    #[serde(default, alias = "auto_complete_subtasks_on_done")]
+    pub auto_complete_subtasks_on_done: bool,
+    /// Largest file `upload_image` accepts; `DEFAULT_MAX_ATTACHMENT_BYTES` when unset.
+    #[serde(default, alias = "max_attachment_bytes")]
+    pub max_attachment_bytes: Option<u64>,
+    /// Extensions `upload_image` refuses, compared case-insensitively without the dot.
+    #[serde(default, alias = "blocked_extensions")]
+    pub blocked_extensions: Option<Vec<String>>,
+    // Add new persistent preferences here, e.g.:
+    // pub auto_save: bool,
+    // pub language: String,
+}
+
+const DEFAULT_MAX_ATTACHMENT_BYTES: u64 = 25 * 1024 * 1024;
+
+fn default_transparency_enabled() -> bool {
+    true
+}
+
+fn default_done_column_keywords() -> Vec<String> {
+    [
+        "done",
+        "complete",
+        "finished",
+        "concluído",
+        "concluido",
+        "feito",
+        "finalizado",
+    ]
+    .iter()
+    .map(|keyword| keyword.to_string())
+    .collect()
+}
+
+impl Default for AppPreferences {
+    fn default() -> Self {
+        Self {
+            theme: "system".to_string(),
+            transparency_enabled: default_transparency_enabled(),
+            last_workspace_id: None,
+            last_board_id: None,
+            done_column_keywords: default_done_column_keywords(),
+            enforce_unique_column_titles: false,
+            auto_complete_subtasks_on_done: false,
+            max_attachment_bytes: None,
+            blocked_extensions: None,
+            // Add defaults for new preferences here
+        }
+    }
+}
+
+fn get_preferences_path<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {e}"))?;
+
+    // Ensure the directory exists
+    std::fs::create_dir_all(&app_data_dir)
+        .map_err(|e| format!("Failed to create app data directory: {e}"))?;
+
+    Ok(app_data_dir.join("preferences.json"))
+}
+
+// Shortcuts configuration stored separately from preferences
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ShortcutsConfig {
+    /// Map from logical binding id (e.g. "open-command-palette") to a normalized
+    /// shortcut string representation (e.g. "mod+k").
+    #[serde(default)]
+    pub bindings: std::collections::HashMap<String, String>,
+}
+
+fn get_shortcuts_path<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {e}"))?;
+
+    std::fs::create_dir_all(&app_data_dir)
+        .map_err(|e| format!("Failed to create app data directory: {e}"))?;
+
+    Ok(app_data_dir.join("shortcuts.json"))
+}
+
+#[tauri::command]
+async fn load_preferences<R: Runtime>(app: AppHandle<R>) -> Result<AppPreferences, String> {
+    log::debug!("Loading preferences from disk");
+    let prefs_path = get_preferences_path(&app)?;
+
+    if !prefs_path.exists() {
+        log::info!("Preferences file not found, using defaults");
+        return Ok(AppPreferences::default());
+    }
+
+    let contents = std::fs::read_to_string(&prefs_path).map_err(|e| {
+        log::error!("Failed to read preferences file: {e}");
+        format!("Failed to read preferences file: {e}")
+    })?;
+
+    let preferences: AppPreferences = serde_json::from_str(&contents).map_err(|e| {
+        log::error!("Failed to parse preferences JSON: {e}");
+        format!("Failed to parse preferences: {e}")
+    })?;
+
+    log::info!("Successfully loaded preferences");
+    Ok(preferences)
+}
+
+#[tauri::command]
+async fn save_preferences<R: Runtime>(
+    app: AppHandle<R>,
+    preferences: AppPreferences,
+) -> Result<(), String> {
+    // Validate theme value
+    validate_theme(&preferences.theme)?;
+
+    log::debug!("Saving preferences to disk: {preferences:?}");
+    let prefs_path = get_preferences_path(&app)?;
+
+    let json_content = serde_json::to_string_pretty(&preferences).map_err(|e| {
+        log::error!("Failed to serialize preferences: {e}");
+        format!("Failed to serialize preferences: {e}")
+    })?;
+
+    // Write to a temporary file first, then rename (atomic operation)
+    let temp_path = prefs_path.with_extension("tmp");
+
+    std::fs::write(&temp_path, json_content).map_err(|e| {
+        log::error!("Failed to write preferences file: {e}");
+        format!("Failed to write preferences file: {e}")
+    })?;
+
+    std::fs::rename(&temp_path, &prefs_path).map_err(|e| {
+        log::error!("Failed to finalize preferences file: {e}");
+        format!("Failed to finalize preferences file: {e}")
+    })?;
+
+    log::info!("Successfully saved preferences to {prefs_path:?}");
+    Ok(())
+}
+
+/// Records the board the user last opened so it can be restored on launch.
+#[tauri::command]
+async fn touch_last_board<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    board_id: String,
+) -> Result<(), String> {
+    let board_id = board_id.trim().to_string();
+    if board_id.is_empty() {
+        return Err("O identificador do quadro não pode ser vazio.".to_string());
+    }
+
+    let exists = sqlx::query_scalar::<_, i64>("SELECT 1 FROM kanban_boards WHERE id = ? LIMIT 1")
+        .bind(&board_id)
+        .fetch_optional(&*pool)
+        .await
+        .map_err(|e| format!("Falha ao validar quadro: {e}"))?
+        .is_some();
+    if !exists {
+        return Err("Quadro não encontrado.".to_string());
+    }
+
+    let mut preferences = load_preferences(app.clone()).await?;
+    if preferences.last_board_id.as_deref() == Some(board_id.as_str()) {
+        return Ok(());
+    }
+
+    preferences.last_board_id = Some(board_id);
+    save_preferences(app, preferences).await
+}
+
+#[tauri::command]
+async fn set_done_column_keywords<R: Runtime>(
+    app: AppHandle<R>,
+    keywords: Vec<String>,
+) -> Result<Vec<String>, String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for keyword in &keywords {
+        let keyword = keyword.trim().to_lowercase();
+        if keyword.is_empty() || normalized.contains(&keyword) {
+            continue;
+        }
+        if keyword.chars().count() > 50 {
+            return Err(
+                "A palavra-chave de conclusão deve ter no máximo 50 caracteres.".to_string(),
+            );
+        }
+        normalized.push(keyword);
+    }
+
+    if normalized.is_empty() {
+        return Err("Informe ao menos uma palavra-chave de conclusão.".to_string());
+    }
+
+    let mut preferences = load_preferences(app.clone()).await?;
+    preferences.done_column_keywords = normalized.clone();
+    save_preferences(app, preferences).await?;
+
+    Ok(normalized)
+}
+
+#[cfg(target_os = "macos")]
+fn apply_window_transparency<R: Runtime>(
+    window: &tauri::WebviewWindow<R>,
+    enabled: bool,
+) -> Result<(), String> {
+    use tauri::window::{Effect, EffectState, EffectsBuilder};
+
+    // Mirrors the `windowEffects` entry in tauri.conf.json
+    let effects = enabled.then(|| {
+        EffectsBuilder::new()
+            .effect(Effect::HudWindow)
+            .state(EffectState::Active)
+            .radius(12.0)
+            .build()
+    });
+
+    window
+        .set_effects(effects)
+        .map_err(|e| format!("Failed to update window effects: {e}"))
+}
+
+#[cfg(not(target_os = "macos"))]
+fn apply_window_transparency<R: Runtime>(
+    _window: &tauri::WebviewWindow<R>,
+    _enabled: bool,
+) -> Result<(), String> {
+    Ok(())
+}
+
+#[tauri::command]
+async fn set_window_transparency<R: Runtime>(
+    app: AppHandle<R>,
+    enabled: bool,
+) -> Result<(), String> {
+    match app.get_webview_window("main") {
+        Some(window) => apply_window_transparency(&window, enabled)?,
+        None => log::warn!("Main window not found, transparency will apply on next launch"),
+    }
+
+    let mut preferences = load_preferences(app.clone()).await?;
+    preferences.transparency_enabled = enabled;
+    save_preferences(app, preferences).await
+}
+
+#[tauri::command]
+async fn load_shortcuts<R: Runtime>(app: AppHandle<R>) -> Result<ShortcutsConfig, String> {
+    log::debug!("Loading shortcuts configuration from disk");
+    let shortcuts_path = get_shortcuts_path(&app)?;
+
+    if !shortcuts_path.exists() {
+        log::info!("Shortcuts file not found, using defaults (empty config)");
+        return Ok(ShortcutsConfig::default());
+    }
+
+    let contents = std::fs::read_to_string(&shortcuts_path).map_err(|e| {
+        log::error!("Failed to read shortcuts file: {e}");
+        format!("Failed to read shortcuts file: {e}")
+    })?;
+
+    let config: ShortcutsConfig = serde_json::from_str(&contents).map_err(|e| {
+        log::error!("Failed to parse shortcuts JSON: {e}");
+        format!("Failed to parse shortcuts: {e}")
+    })?;
+
+    log::info!("Successfully loaded shortcuts configuration");
+    Ok(config)
+}
+
+#[tauri::command]
+async fn save_shortcuts<R: Runtime>(
+    app: AppHandle<R>,
+    config: ShortcutsConfig,
+) -> Result<(), String> {
+    log::debug!("Saving shortcuts configuration to disk: {config:?}");
+    let shortcuts_path = get_shortcuts_path(&app)?;
+
+    let json_content = serde_json::to_string_pretty(&config).map_err(|e| {
+        log::error!("Failed to serialize shortcuts config: {e}");
+        format!("Failed to serialize shortcuts config: {e}")
+    })?;
+
+    let temp_path = shortcuts_path.with_extension("tmp");
+
+    std::fs::write(&temp_path, json_content).map_err(|e| {
+        log::error!("Failed to write shortcuts file: {e}");
+        format!("Failed to write shortcuts file: {e}")
+    })?;
+
+    std::fs::rename(&temp_path, &shortcuts_path).map_err(|e| {
+        log::error!("Failed to finalize shortcuts file: {e}");
+        format!("Failed to finalize shortcuts file: {e}")
+    })?;
+
+    log::info!("Successfully saved shortcuts configuration to {shortcuts_path:?}");
+    Ok(())
+}
+
+#[tauri::command]
+async fn send_native_notification<R: Runtime>(
+    app: AppHandle<R>,
+    title: String,
+    body: Option<String>,
+) -> Result<(), String> {
+    log::info!("Sending native notification: {title}");
+
+    #[cfg(not(mobile))]
+    {
+        use tauri_plugin_notification::NotificationExt;
+
+        let mut notification = app.notification().builder().title(title);
+
+        if let Some(body_text) = body {
+            notification = notification.body(body_text);
+        }
+
+        match notification.show() {
+            Ok(_) => {
+                log::info!("Native notification sent successfully");
+                Ok(())
+            }
+            Err(e) => {
+                log::error!("Failed to send native notification: {e}");
+                Err(format!("Failed to send notification: {e}"))
+            }
+        }
+    }
+
+    #[cfg(mobile)]
+    {
+        log::warn!("Native notifications not supported on mobile");
+        Err("Native notifications not supported on mobile".to_string())
+    }
+}
+
+// Recovery functions - simple pattern for saving JSON data to disk
+fn get_recovery_dir<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {e}"))?;
+
+    let recovery_dir = app_data_dir.join("recovery");
+
+    // Ensure the recovery directory exists
+    std::fs::create_dir_all(&recovery_dir)
+        .map_err(|e| format!("Failed to create recovery directory: {e}"))?;
+
+    Ok(recovery_dir)
+}
+
+#[tauri::command]
+async fn save_emergency_data<R: Runtime>(
+    app: AppHandle<R>,
+    filename: String,
+    data: Value,
+) -> Result<(), String> {
+    log::info!("Saving emergency data to file: {filename}");
+
+    // Validate filename with proper security checks
+    validate_filename(&filename)?;
+
+    // Validate data size (10MB limit)
+    let data_str = serde_json::to_string(&data)
+        .map_err(|e| format!("Failed to serialize data for size check: {e}"))?;
+    if data_str.len() > 10_485_760 {
+        return Err("Data too large (max 10MB)".to_string());
+    }
+
+    let recovery_dir = get_recovery_dir(&app)?;
+    let file_path = recovery_dir.join(format!("{filename}.json"));
+
+    let json_content = serde_json::to_string_pretty(&data).map_err(|e| {
+        log::error!("Failed to serialize emergency data: {e}");
+        format!("Failed to serialize data: {e}")
+    })?;
+
+    // Write to a temporary file first, then rename (atomic operation)
+    let temp_path = file_path.with_extension("tmp");
+
+    std::fs::write(&temp_path, json_content).map_err(|e| {
+        log::error!("Failed to write emergency data file: {e}");
+        format!("Failed to write data file: {e}")
+    })?;
+
+    std::fs::rename(&temp_path, &file_path).map_err(|e| {
+        log::error!("Failed to finalize emergency data file: {e}");
+        format!("Failed to finalize data file: {e}")
+    })?;
+
+    log::info!("Successfully saved emergency data to {file_path:?}");
+    Ok(())
+}
+
+#[tauri::command]
+async fn load_emergency_data<R: Runtime>(
+    app: AppHandle<R>,
+    filename: String,
+) -> Result<Value, String> {
+    log::info!("Loading emergency data from file: {filename}");
+
+    // Validate filename with proper security checks
+    validate_filename(&filename)?;
+
+    let recovery_dir = get_recovery_dir(&app)?;
+    let file_path = recovery_dir.join(format!("{filename}.json"));
+
+    if !file_path.exists() {
+        log::info!("Recovery file not found: {file_path:?}");
+        return Err("File not found".to_string());
+    }
+
+    let contents = std::fs::read_to_string(&file_path).map_err(|e| {
+        log::error!("Failed to read recovery file: {e}");
+        format!("Failed to read file: {e}")
+    })?;
+
+    let data: Value = serde_json::from_str(&contents).map_err(|e| {
+        log::error!("Failed to parse recovery JSON: {e}");
+        format!("Failed to parse data: {e}")
+    })?;
+
+    log::info!("Successfully loaded emergency data");
+    Ok(data)
+}
+
+#[tauri::command]
+async fn backup_preferences<R: Runtime>(app: AppHandle<R>) -> Result<String, String> {
+    let preferences = load_preferences(app.clone()).await?;
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| format!("Failed to get current time: {e}"))?
+        .as_secs();
+    let filename = format!("preferences-{timestamp}.json");
+
+    let recovery_dir = get_recovery_dir(&app)?;
+    let file_path = recovery_dir.join(&filename);
+
+    let json_content = serde_json::to_string_pretty(&preferences).map_err(|e| {
+        log::error!("Failed to serialize preferences backup: {e}");
+        format!("Failed to serialize preferences: {e}")
+    })?;
+
+    std::fs::write(&file_path, json_content).map_err(|e| {
+        log::error!("Failed to write preferences backup: {e}");
+        format!("Failed to write preferences backup: {e}")
+    })?;
+
+    log::info!("Backed up preferences to {file_path:?}");
+    Ok(filename)
+}
+
+#[tauri::command]
+async fn restore_preferences<R: Runtime>(
+    app: AppHandle<R>,
+    filename: String,
+) -> Result<AppPreferences, String> {
+    validate_filename(&filename)?;
+
+    if !filename.starts_with("preferences-") || !filename.ends_with(".json") {
+        return Err("Invalid preferences backup filename".to_string());
+    }
+
+    let recovery_dir = get_recovery_dir(&app)?;
+    let file_path = recovery_dir.join(&filename);
+
+    if !file_path.exists() {
+        return Err("File not found".to_string());
+    }
+
+    let contents = std::fs::read_to_string(&file_path).map_err(|e| {
+        log::error!("Failed to read preferences backup: {e}");
+        format!("Failed to read file: {e}")
+    })?;
+
+    let preferences: AppPreferences = serde_json::from_str(&contents).map_err(|e| {
+        log::error!("Failed to parse preferences backup: {e}");
+        format!("Failed to parse preferences backup: {e}")
+    })?;
+
+    save_preferences(app, preferences.clone()).await?;
+
+    log::info!("Restored preferences from {file_path:?}");
+    Ok(preferences)
+}
+
+#[tauri::command]
+async fn cleanup_old_recovery_files<R: Runtime>(app: AppHandle<R>) -> Result<u32, String> {
+    log::info!("Cleaning up old recovery files");
+
+    let recovery_dir = get_recovery_dir(&app)?;
+    let mut removed_count = 0;
+
+    // Calculate cutoff time (7 days ago)
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| format!("Failed to get current time: {e}"))?
+        .as_secs();
+    let seven_days_ago = now - (7 * 24 * 60 * 60);
+
+    // Read directory and check each file
+    let entries = std::fs::read_dir(&recovery_dir).map_err(|e| {
+        log::error!("Failed to read recovery directory: {e}");
+        format!("Failed to read directory: {e}")
+    })?;
+
+    for entry in entries {
+        let entry = match entry {
+            Ok(e) => e,
+            Err(e) => {
+                log::warn!("Failed to read directory entry: {e}");
+                continue;
+            }
+        };
+
+        let path = entry.path();
+
+        // Only process JSON files
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+
+        // Check file modification time
+        let metadata = match std::fs::metadata(&path) {
+            Ok(m) => m,
+            Err(e) => {
+                log::warn!("Failed to get file metadata: {e}");
+                continue;
+            }
+        };
+
+        let modified = match metadata.modified() {
+            Ok(m) => m,
+            Err(e) => {
+                log::warn!("Failed to get file modification time: {e}");
+                continue;
+            }
+        };
+
+        let modified_secs = match modified.duration_since(UNIX_EPOCH) {
+            Ok(d) => d.as_secs(),
+            Err(e) => {
+                log::warn!("Failed to convert modification time: {e}");
+                continue;
+            }
+        };
+
+        // Remove if older than 7 days
+        if modified_secs < seven_days_ago {
+            match std::fs::remove_file(&path) {
+                Ok(_) => {
+                    log::info!("Removed old recovery file: {path:?}");
+                    removed_count += 1;
+                }
+                Err(e) => {
+                    log::warn!("Failed to remove old recovery file: {e}");
+                }
+            }
+        }
+    }
+
+    log::info!("Cleanup complete. Removed {removed_count} old recovery files");
+    Ok(removed_count)
+}
+
+// Create the native menu system
+fn create_app_menu(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
+    log::info!("Setting up native menu system");
+
+    // Build the main application submenu
+    let app_submenu = SubmenuBuilder::new(app, "Modulo")
+        .item(&MenuItemBuilder::with_id("about", "About Modulo").build(app)?)
+        .separator()
+        .item(&MenuItemBuilder::with_id("check-updates", "Check for Updates...").build(app)?)
+        .separator()
+        .item(
+            &MenuItemBuilder::with_id("preferences", "Preferences...")
+                .accelerator("CmdOrCtrl+,")
+                .build(app)?,
+        )
+        .separator()
+        .item(&PredefinedMenuItem::hide(app, Some("Hide Modulo"))?)
+        .item(&PredefinedMenuItem::hide_others(app, None)?)
+        .item(&PredefinedMenuItem::show_all(app, None)?)
+        .separator()
+        .item(&PredefinedMenuItem::quit(app, Some("Quit Modulo"))?)
+        .build()?;
+
+    // Build the View submenu
+    let view_submenu = SubmenuBuilder::new(app, "View")
+        .item(
+            &MenuItemBuilder::with_id("toggle-left-sidebar", "Toggle Left Sidebar")
+                .accelerator("CmdOrCtrl+1")
+                .build(app)?,
+        )
+        .build()?;
+
+    // Build the main menu with submenus
+    let menu = MenuBuilder::new(app)
+        .item(&app_submenu)
+        .item(&view_submenu)
+        .build()?;
+
+    // Set the menu for the app
+    app.set_menu(menu)?;
+
+    log::info!("Native menu system initialized successfully");
+    Ok(())
+}
+
+async fn ensure_notes_board_id_column(pool: &DbPool) -> Result<(), String> {
+    let column_exists = sqlx::query_scalar::<_, Option<i64>>(
+        "SELECT 1 FROM pragma_table_info('notes') WHERE name = 'board_id' LIMIT 1",
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| format!("Failed to inspect notes schema: {e}"))?
+    .flatten()
+    .is_some();
+
+    if !column_exists {
+        sqlx::query("ALTER TABLE notes ADD COLUMN board_id TEXT")
+            .execute(pool)
+            .await
+            .map_err(|e| format!("Failed to add board_id column to notes: {e}"))?;
+
+        // Set board_id to the first board for existing notes
+        sqlx::query(
+            "UPDATE notes SET board_id = (SELECT id FROM kanban_boards LIMIT 1) WHERE board_id IS NULL"
+        )
+        .execute(pool)
+        .await
+        .map_err(|e| format!("Failed to update existing notes with board_id: {e}"))?;
+    }
+
+    // Create indexes for notes table (safe to run multiple times)
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_notes_board_updated ON notes(board_id, updated_at DESC)",
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Failed to create idx_notes_board_updated: {e}"))?;
+
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_notes_board_pinned ON notes(board_id, pinned DESC, updated_at DESC)")
+        .execute(pool)
+        .await
+        .map_err(|e| format!("Failed to create idx_notes_board_pinned: {e}"))?;
+
+    Ok(())
+}
+
+async fn ensure_board_favorite_column(pool: &DbPool) -> Result<(), String> {
+    let column_exists = sqlx::query_scalar::<_, Option<i64>>(
+        "SELECT 1 FROM pragma_table_info('kanban_boards') WHERE name = 'is_favorite' LIMIT 1",
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| format!("Failed to inspect kanban_boards schema: {e}"))?
+    .flatten()
+    .is_some();
+
+    if !column_exists {
+        sqlx::query("ALTER TABLE kanban_boards ADD COLUMN is_favorite INTEGER NOT NULL DEFAULT 0")
+            .execute(pool)
+            .await
+            .map_err(|e| format!("Failed to add is_favorite column to kanban_boards: {e}"))?;
+        sqlx::query("UPDATE kanban_boards SET is_favorite = 0 WHERE is_favorite IS NULL")
+            .execute(pool)
+            .await
+            .map_err(|e| format!("Failed to backfill is_favorite values in kanban_boards: {e}"))?;
+    } else {
+        sqlx::query("UPDATE kanban_boards SET is_favorite = 0 WHERE is_favorite IS NULL")
+            .execute(pool)
+            .await
+            .map_err(|e| format!("Failed to normalize is_favorite values in kanban_boards: {e}"))?;
+    }
+
+    Ok(())
+}
+
+async fn ensure_attachment_external_column(pool: &DbPool) -> Result<(), String> {
+    let column_exists = sqlx::query_scalar::<_, Option<i64>>(
+        "SELECT 1 FROM pragma_table_info('kanban_attachments') WHERE name = 'is_external' LIMIT 1",
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| format!("Failed to inspect kanban_attachments schema: {e}"))?
+    .flatten()
+    .is_some();
+
+    if !column_exists {
+        sqlx::query(
+            "ALTER TABLE kanban_attachments ADD COLUMN is_external INTEGER NOT NULL DEFAULT 0",
+        )
+        .execute(pool)
+        .await
+        .map_err(|e| format!("Failed to add is_external column to kanban_attachments: {e}"))?;
+    }
+
+    Ok(())
+}
+
+async fn ensure_card_remind_recurrence_column(pool: &DbPool) -> Result<(), String> {
+    let column_exists = sqlx::query_scalar::<_, Option<i64>>(
+        "SELECT 1 FROM pragma_table_info('kanban_cards') WHERE name = 'remind_recurrence' LIMIT 1",
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| format!("Failed to inspect kanban_cards schema: {e}"))?
+    .flatten()
+    .is_some();
+
+    if !column_exists {
+        sqlx::query("ALTER TABLE kanban_cards ADD COLUMN remind_recurrence TEXT")
+            .execute(pool)
+            .await
+            .map_err(|e| format!("Failed to add remind_recurrence column to kanban_cards: {e}"))?;
+    }
+
+    Ok(())
+}
+
+async fn ensure_tag_position_column(pool: &DbPool) -> Result<(), String> {
+    let column_exists = sqlx::query_scalar::<_, Option<i64>>(
+        "SELECT 1 FROM pragma_table_info('kanban_tags') WHERE name = 'position' LIMIT 1",
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| format!("Failed to inspect kanban_tags schema: {e}"))?
+    .flatten()
+    .is_some();
+
+    if !column_exists {
+        sqlx::query("ALTER TABLE kanban_tags ADD COLUMN position INTEGER NOT NULL DEFAULT 0")
+            .execute(pool)
+            .await
+            .map_err(|e| format!("Failed to add position column to kanban_tags: {e}"))?;
+
+        sqlx::query(
+            "UPDATE kanban_tags SET position = (SELECT COUNT(*) FROM kanban_tags earlier WHERE earlier.board_id = kanban_tags.board_id AND (earlier.label < kanban_tags.label COLLATE NOCASE OR (earlier.label = kanban_tags.label COLLATE NOCASE AND earlier.id < kanban_tags.id)))",
+        )
+        .execute(pool)
+        .await
+        .map_err(|e| format!("Failed to backfill position values in kanban_tags: {e}"))?;
+    }
+
+    Ok(())
+}
+
+async fn ensure_workspace_position_column(pool: &DbPool) -> Result<(), String> {
+    let column_exists = sqlx::query_scalar::<_, Option<i64>>(
+        "SELECT 1 FROM pragma_table_info('workspaces') WHERE name = 'position' LIMIT 1",
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| format!("Failed to inspect workspaces schema: {e}"))?
+    .flatten()
+    .is_some();
+
+    if !column_exists {
+        sqlx::query("ALTER TABLE workspaces ADD COLUMN position INTEGER NOT NULL DEFAULT 0")
+            .execute(pool)
+            .await
+            .map_err(|e| format!("Failed to add position column to workspaces: {e}"))?;
+
+        sqlx::query(
+            "UPDATE workspaces SET position = (SELECT COUNT(*) FROM workspaces earlier WHERE earlier.created_at < workspaces.created_at OR (earlier.created_at = workspaces.created_at AND earlier.id < workspaces.id))",
+        )
+        .execute(pool)
+        .await
+        .map_err(|e| format!("Failed to backfill position values in workspaces: {e}"))?;
+    }
+
+    Ok(())
+}
+
+async fn ensure_column_wip_mode_column(pool: &DbPool) -> Result<(), String> {
+    let column_exists = sqlx::query_scalar::<_, Option<i64>>(
+        "SELECT 1 FROM pragma_table_info('kanban_columns') WHERE name = 'wip_mode' LIMIT 1",
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| format!("Failed to inspect kanban_columns schema: {e}"))?
+    .flatten()
+    .is_some();
+
+    if !column_exists {
+        sqlx::query("ALTER TABLE kanban_columns ADD COLUMN wip_mode TEXT NOT NULL DEFAULT 'hard'")
+            .execute(pool)
+            .await
+            .map_err(|e| format!("Failed to add wip_mode column to kanban_columns: {e}"))?;
+    }
+
+    Ok(())
+}
+
+async fn ensure_column_kind_column(pool: &DbPool) -> Result<(), String> {
+    let column_exists = sqlx::query_scalar::<_, Option<i64>>(
+        "SELECT 1 FROM pragma_table_info('kanban_columns') WHERE name = 'column_kind' LIMIT 1",
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| format!("Failed to inspect kanban_columns schema: {e}"))?
+    .flatten()
+    .is_some();
+
+    if !column_exists {
+        sqlx::query("ALTER TABLE kanban_columns ADD COLUMN column_kind TEXT")
+            .execute(pool)
+            .await
+            .map_err(|e| format!("Failed to add column_kind column to kanban_columns: {e}"))?;
+    }
+
+    Ok(())
+}
+
+async fn ensure_column_intake_flag(pool: &DbPool) -> Result<(), String> {
+    let column_exists = sqlx::query_scalar::<_, Option<i64>>(
+        "SELECT 1 FROM pragma_table_info('kanban_columns') WHERE name = 'is_default_intake' LIMIT 1",
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| format!("Failed to inspect kanban_columns schema: {e}"))?
+    .flatten()
+    .is_some();
+
+    if !column_exists {
+        sqlx::query(
+            "ALTER TABLE kanban_columns ADD COLUMN is_default_intake INTEGER NOT NULL DEFAULT 0",
+        )
+        .execute(pool)
+        .await
+        .map_err(|e| format!("Failed to add is_default_intake column to kanban_columns: {e}"))?;
+    }
+
+    sqlx::query(
+        "CREATE UNIQUE INDEX IF NOT EXISTS idx_kanban_columns_default_intake ON kanban_columns(board_id) WHERE is_default_intake = 1",
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Failed to create default intake index: {e}"))?;
+
+    Ok(())
+}
+
+/// The original activity table cascaded from boards and cards, which erased the
+/// very deletions the feed should show. Nothing wrote to it, so it is rebuilt.
+async fn ensure_activity_log_table(pool: &DbPool) -> Result<(), String> {
+    let has_entity_type = sqlx::query_scalar::<_, Option<i64>>(
+        "SELECT 1 FROM pragma_table_info('kanban_activity') WHERE name = 'entity_type' LIMIT 1",
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| format!("Failed to inspect kanban_activity schema: {e}"))?
+    .flatten()
+    .is_some();
+
+    if has_entity_type {
+        return Ok(());
+    }
+
+    for statement in [
+        "DROP TABLE IF EXISTS kanban_activity",
+        "CREATE TABLE kanban_activity (
+            id TEXT PRIMARY KEY,
+            board_id TEXT NOT NULL,
+            entity_type TEXT NOT NULL,
+            entity_id TEXT NOT NULL,
+            activity_type TEXT NOT NULL,
+            title TEXT NOT NULL,
+            meta TEXT,
+            created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+        )",
+        "CREATE INDEX IF NOT EXISTS idx_activity_board_created ON kanban_activity(board_id, created_at DESC)",
+    ] {
+        sqlx::query(statement)
+            .execute(pool)
+            .await
+            .map_err(|e| format!("Failed to rebuild kanban_activity: {e}"))?;
+    }
+
+    Ok(())
+}
+
+/// Serves per-card and per-board timelines from `load_entity_activity`.
+async fn ensure_activity_entity_index(pool: &DbPool) -> Result<(), String> {
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_activity_entity ON kanban_activity(entity_type, entity_id, created_at)",
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Failed to create idx_activity_entity: {e}"))?;
+
+    Ok(())
+}
+
+/// Card triggers drop a board's cached stats on every card write, so a present
+/// row is always current and a missing one means it must be recomputed.
+async fn ensure_board_stats_cache(pool: &DbPool) -> Result<(), String> {
+    for statement in [
+        "CREATE TABLE IF NOT EXISTS board_stats_cache (
+            board_id TEXT PRIMARY KEY REFERENCES kanban_boards(id) ON DELETE CASCADE,
+            total_cards INTEGER NOT NULL,
+            active_cards INTEGER NOT NULL,
+            updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+        )",
+        "CREATE TRIGGER IF NOT EXISTS trg_board_stats_card_insert AFTER INSERT ON kanban_cards
+        BEGIN
+            DELETE FROM board_stats_cache WHERE board_id = NEW.board_id;
+        END",
+        "CREATE TRIGGER IF NOT EXISTS trg_board_stats_card_update AFTER UPDATE ON kanban_cards
+        BEGIN
+            DELETE FROM board_stats_cache WHERE board_id IN (OLD.board_id, NEW.board_id);
+        END",
+        "CREATE TRIGGER IF NOT EXISTS trg_board_stats_card_delete AFTER DELETE ON kanban_cards
+        BEGIN
+            DELETE FROM board_stats_cache WHERE board_id = OLD.board_id;
+        END",
+    ] {
+        sqlx::query(statement)
+            .execute(pool)
+            .await
+            .map_err(|e| format!("Failed to set up board stats cache: {e}"))?;
+    }
+
+    Ok(())
+}
+
+/// Builds the `kanban_fts` index and its sync triggers. `kanban_fts_rows` maps each
+/// entity to its FTS rowid, since the triggers cannot look rows up through the
+/// UNINDEXED columns without scanning the whole index. When the bundled SQLite
+/// lacks FTS5 the index is skipped and `global_search` keeps using `LIKE`.
+async fn ensure_search_index(pool: &DbPool) -> Result<(), String> {
+    let rows_table_exists = sqlx::query_scalar::<_, i64>(
+        "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'kanban_fts_rows' LIMIT 1",
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| format!("Failed to inspect search index: {e}"))?
+    .is_some();
+
+    // Indexes built before the rowid mapping existed are rebuilt from scratch.
+    if !rows_table_exists {
+        for statement in [
+            "DROP TRIGGER IF EXISTS trg_fts_board_insert",
+            "DROP TRIGGER IF EXISTS trg_fts_board_update",
+            "DROP TRIGGER IF EXISTS trg_fts_board_delete",
+            "DROP TRIGGER IF EXISTS trg_fts_card_insert",
+            "DROP TRIGGER IF EXISTS trg_fts_card_update",
+            "DROP TRIGGER IF EXISTS trg_fts_card_delete",
+            "DROP TRIGGER IF EXISTS trg_fts_note_insert",
+            "DROP TRIGGER IF EXISTS trg_fts_note_update",
+            "DROP TRIGGER IF EXISTS trg_fts_note_delete",
+            "DROP TABLE IF EXISTS kanban_fts",
+        ] {
+            sqlx::query(statement)
+                .execute(pool)
+                .await
+                .map_err(|e| format!("Failed to drop outdated search index: {e}"))?;
+        }
+    }
+
+    let already_exists = search_index_available(pool).await;
+
+    if !already_exists
+        && let Err(e) = sqlx::query(
+            "CREATE VIRTUAL TABLE kanban_fts USING fts5(entity_type UNINDEXED, entity_id UNINDEXED, title, body)",
+        )
+        .execute(pool)
+        .await
+    {
+        log::warn!("FTS5 unavailable, search will fall back to LIKE: {e}");
+        return Ok(());
+    }
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS kanban_fts_rows (
+            fts_rowid INTEGER PRIMARY KEY,
+            entity_type TEXT NOT NULL,
+            entity_id TEXT NOT NULL,
+            UNIQUE (entity_type, entity_id)
+        )",
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Failed to create search index row map: {e}"))?;
+
+    for statement in [
+        "CREATE TRIGGER IF NOT EXISTS trg_fts_board_insert AFTER INSERT ON kanban_boards
+        BEGIN
+            INSERT INTO kanban_fts_rows (entity_type, entity_id) VALUES ('board', NEW.id);
+            INSERT INTO kanban_fts (rowid, entity_type, entity_id, title, body)
+            VALUES ((SELECT fts_rowid FROM kanban_fts_rows WHERE entity_type = 'board' AND entity_id = NEW.id), 'board', NEW.id, NEW.title, COALESCE(NEW.description, ''));
+        END",
+        "CREATE TRIGGER IF NOT EXISTS trg_fts_board_update AFTER UPDATE OF title, description ON kanban_boards
+        BEGIN
+            UPDATE kanban_fts SET title = NEW.title, body = COALESCE(NEW.description, '')
+            WHERE rowid = (SELECT fts_rowid FROM kanban_fts_rows WHERE entity_type = 'board' AND entity_id = OLD.id);
+        END",
+        "CREATE TRIGGER IF NOT EXISTS trg_fts_board_delete AFTER DELETE ON kanban_boards
+        BEGIN
+            DELETE FROM kanban_fts WHERE rowid = (SELECT fts_rowid FROM kanban_fts_rows WHERE entity_type = 'board' AND entity_id = OLD.id);
+            DELETE FROM kanban_fts_rows WHERE entity_type = 'board' AND entity_id = OLD.id;
+        END",
+        "CREATE TRIGGER IF NOT EXISTS trg_fts_card_insert AFTER INSERT ON kanban_cards
+        BEGIN
+            INSERT INTO kanban_fts_rows (entity_type, entity_id) VALUES ('card', NEW.id);
+            INSERT INTO kanban_fts (rowid, entity_type, entity_id, title, body)
+            VALUES ((SELECT fts_rowid FROM kanban_fts_rows WHERE entity_type = 'card' AND entity_id = NEW.id), 'card', NEW.id, NEW.title, COALESCE(NEW.description, ''));
+        END",
+        "CREATE TRIGGER IF NOT EXISTS trg_fts_card_update AFTER UPDATE OF title, description ON kanban_cards
+        BEGIN
+            UPDATE kanban_fts SET title = NEW.title, body = COALESCE(NEW.description, '')
+            WHERE rowid = (SELECT fts_rowid FROM kanban_fts_rows WHERE entity_type = 'card' AND entity_id = OLD.id);
+        END",
+        "CREATE TRIGGER IF NOT EXISTS trg_fts_card_delete AFTER DELETE ON kanban_cards
+        BEGIN
+            DELETE FROM kanban_fts WHERE rowid = (SELECT fts_rowid FROM kanban_fts_rows WHERE entity_type = 'card' AND entity_id = OLD.id);
+            DELETE FROM kanban_fts_rows WHERE entity_type = 'card' AND entity_id = OLD.id;
+        END",
+        "CREATE TRIGGER IF NOT EXISTS trg_fts_note_insert AFTER INSERT ON notes
+        BEGIN
+            INSERT INTO kanban_fts_rows (entity_type, entity_id) VALUES ('note', NEW.id);
+            INSERT INTO kanban_fts (rowid, entity_type, entity_id, title, body)
+            VALUES ((SELECT fts_rowid FROM kanban_fts_rows WHERE entity_type = 'note' AND entity_id = NEW.id), 'note', NEW.id, NEW.title, NEW.content);
+        END",
+        "CREATE TRIGGER IF NOT EXISTS trg_fts_note_update AFTER UPDATE OF title, content ON notes
+        BEGIN
+            UPDATE kanban_fts SET title = NEW.title, body = NEW.content
+            WHERE rowid = (SELECT fts_rowid FROM kanban_fts_rows WHERE entity_type = 'note' AND entity_id = OLD.id);
+        END",
+        "CREATE TRIGGER IF NOT EXISTS trg_fts_note_delete AFTER DELETE ON notes
+        BEGIN
+            DELETE FROM kanban_fts WHERE rowid = (SELECT fts_rowid FROM kanban_fts_rows WHERE entity_type = 'note' AND entity_id = OLD.id);
+            DELETE FROM kanban_fts_rows WHERE entity_type = 'note' AND entity_id = OLD.id;
+        END",
+    ] {
+        sqlx::query(statement)
+            .execute(pool)
+            .await
+            .map_err(|e| format!("Failed to set up search index triggers: {e}"))?;
+    }
+
+    if !already_exists {
+        for statement in [
+            "INSERT INTO kanban_fts_rows (entity_type, entity_id)
+            SELECT 'board', id FROM kanban_boards
+            UNION ALL SELECT 'card', id FROM kanban_cards
+            UNION ALL SELECT 'note', id FROM notes",
+            "INSERT INTO kanban_fts (rowid, entity_type, entity_id, title, body)
+            SELECT r.fts_rowid, 'board', b.id, b.title, COALESCE(b.description, '')
+            FROM kanban_boards b JOIN kanban_fts_rows r ON r.entity_type = 'board' AND r.entity_id = b.id",
+            "INSERT INTO kanban_fts (rowid, entity_type, entity_id, title, body)
+            SELECT r.fts_rowid, 'card', c.id, c.title, COALESCE(c.description, '')
+            FROM kanban_cards c JOIN kanban_fts_rows r ON r.entity_type = 'card' AND r.entity_id = c.id",
+            "INSERT INTO kanban_fts (rowid, entity_type, entity_id, title, body)
+            SELECT r.fts_rowid, 'note', n.id, n.title, n.content
+            FROM notes n JOIN kanban_fts_rows r ON r.entity_type = 'note' AND r.entity_id = n.id",
+        ] {
+            sqlx::query(statement)
+                .execute(pool)
+                .await
+                .map_err(|e| format!("Failed to populate search index: {e}"))?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn search_index_available(pool: &DbPool) -> bool {
+    sqlx::query_scalar::<_, i64>(
+        "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'kanban_fts' LIMIT 1",
+    )
+    .fetch_optional(pool)
+    .await
+    .ok()
+    .flatten()
+    .is_some()
+}
+
+// ============================================================================
+// NOTES COMMANDS
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+struct CreateNoteArgs {
+    id: String,
+    board_id: String,
+    title: String,
+    #[serde(default)]
+    content: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UpdateNoteArgs {
+    id: String,
+    board_id: String,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    content: Option<String>,
+    #[serde(default)]
+    pinned: Option<bool>,
+}
+
+fn map_note_row(row: SqliteRow) -> Result<Value, sqlx::Error> {
+    Ok(json!({
+        "id": row.try_get::<String, _>("id")?,
+        "boardId": row.try_get::<String, _>("board_id")?,
+        "title": row.try_get::<String, _>("title")?,
+        "content": row.try_get::<String, _>("content")?,
+        "createdAt": row.try_get::<String, _>("created_at")?,
+        "updatedAt": row.try_get::<String, _>("updated_at")?,
+        "archivedAt": row.try_get::<Option<String>, _>("archived_at")?,
+        "pinned": row.try_get::<i64, _>("pinned")? != 0,
+        "tags": row
+            .try_get::<Option<String>, _>("tags")?
+            .and_then(|s| serde_json::from_str::<Vec<String>>(&s).ok())
+            .unwrap_or_default(),
+    }))
+}
+
+#[tauri::command]
+async fn load_notes(pool: State<'_, DbPool>, board_id: String) -> Result<Vec<Value>, String> {
+    let rows = sqlx::query(
+        "SELECT id, board_id, title, content, created_at, updated_at, archived_at, pinned, tags 
+         FROM notes 
+         WHERE board_id = ? AND archived_at IS NULL 
+         ORDER BY pinned DESC, updated_at DESC",
+    )
+    .bind(&board_id)
+    .fetch_all(&*pool)
+    .await
+    .map_err(|e| format!("Failed to load notes: {e}"))?;
+
+    let notes = rows
+        .into_iter()
+        .map(map_note_row)
+        .collect::<Result<Vec<Value>, _>>()
+        .map_err(|e| format!("Failed to map notes: {e}"))?;
+
+    Ok(notes)
+}
+
+#[tauri::command]
+async fn create_note(pool: State<'_, DbPool>, args: CreateNoteArgs) -> Result<Value, String> {
+    let content = args.content.unwrap_or_else(|| String::from(""));
+
+    sqlx::query("INSERT INTO notes (id, board_id, title, content) VALUES (?, ?, ?, ?)")
+        .bind(&args.id)
+        .bind(&args.board_id)
+        .bind(&args.title)
+        .bind(&content)
+        .execute(&*pool)
+        .await
+        .map_err(|e| format!("Failed to create note: {e}"))?;
+
+    let row = sqlx::query(
+        "SELECT id, board_id, title, content, created_at, updated_at, archived_at, pinned, tags 
+         FROM notes WHERE id = ? AND board_id = ?",
+    )
+    .bind(&args.id)
+    .bind(&args.board_id)
+    .fetch_one(&*pool)
+    .await
+    .map_err(|e| format!("Failed to fetch created note: {e}"))?;
+
+    map_note_row(row).map_err(|e| format!("Failed to map note: {e}"))
+}
+
+#[tauri::command]
+async fn update_note(pool: State<'_, DbPool>, args: UpdateNoteArgs) -> Result<(), String> {
+    let mut query_parts =
+        vec!["UPDATE notes SET updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')"];
+    let mut bindings: Vec<String> = vec![];
+
+    if let Some(ref title) = args.title {
+        let trimmed = title.trim();
+        if trimmed.is_empty() {
+            return Err("O título da nota não pode ser vazio.".to_string());
+        }
+        validate_string_input(trimmed, 200, "Título da nota")?;
+        query_parts.push("title = ?");
+        bindings.push(trimmed.to_string());
+    }
+
+    if let Some(ref content) = args.content {
+        let trimmed = content.trim();
+        validate_string_input(trimmed, 100_000, "Conteúdo da nota")?;
+        query_parts.push("content = ?");
+        bindings.push(trimmed.to_string());
+    }
+
+    if let Some(pinned) = args.pinned {
+        query_parts.push("pinned = ?");
+        bindings.push(if pinned { "1" } else { "0" }.to_string());
+    }
+
+    if bindings.is_empty() {
+        return Ok(());
+    }
+
+    query_parts.push("WHERE id = ? AND board_id = ?");
+    bindings.push(args.id.clone());
+    bindings.push(args.board_id.clone());
+
+    let query_str = query_parts.join(", ").replace(", WHERE", " WHERE");
+
+    let mut query = sqlx::query(&query_str);
+    for binding in bindings {
+        query = query.bind(binding);
+    }
+
+    query
+        .execute(&*pool)
+        .await
+        .map_err(|e| format!("Failed to update note: {e}"))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+async fn set_note_tags(
+    pool: State<'_, DbPool>,
+    note_id: String,
+    board_id: String,
+    tags: Vec<String>,
+) -> Result<Value, String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in &tags {
+        let tag = tag.trim();
+        if tag.is_empty() || normalized.iter().any(|existing| existing == tag) {
+            continue;
+        }
+        if tag.chars().count() > 50 {
+            return Err(format!(
+                "A etiqueta \"{tag}\" excede o limite de 50 caracteres."
+            ));
+        }
+        normalized.push(tag.to_string());
+    }
+
+    let tags_json = serde_json::to_string(&normalized)
+        .map_err(|e| format!("Falha ao serializar etiquetas da nota: {e}"))?;
+
+    let result = sqlx::query(
+        "UPDATE notes SET tags = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ? AND board_id = ?",
+    )
+    .bind(&tags_json)
+    .bind(&note_id)
+    .bind(&board_id)
+    .execute(&*pool)
+    .await
+    .map_err(|e| format!("Falha ao atualizar etiquetas da nota: {e}"))?;
+
+    if result.rows_affected() == 0 {
+        return Err("Nota não encontrada.".to_string());
+    }
+
+    sqlx::query(
+        "SELECT id, board_id, title, content, created_at, updated_at, archived_at, pinned, tags FROM notes WHERE id = ?",
+    )
+    .bind(&note_id)
+    .try_map(map_note_row)
+    .fetch_one(&*pool)
+    .await
+    .map_err(|e| format!("Falha ao carregar nota: {e}"))
+}
+
+#[tauri::command]
+async fn delete_note(pool: State<'_, DbPool>, id: String, board_id: String) -> Result<(), String> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to begin transaction: {e}"))?;
+
+    let result = sqlx::query("DELETE FROM notes WHERE id = ? AND board_id = ?")
+        .bind(&id)
+        .bind(&board_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to delete note: {e}"))?;
+
+    if result.rows_affected() > 0 {
+        record_deletion_tx(&mut tx, "note", &id, &board_id).await?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {e}"))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+async fn archive_note(pool: State<'_, DbPool>, id: String, board_id: String) -> Result<(), String> {
+    sqlx::query(
+        "UPDATE notes
+         SET archived_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now'),
+             updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+         WHERE id = ? AND board_id = ?",
+    )
+    .bind(&id)
+    .bind(&board_id)
+    .execute(&*pool)
+    .await
+    .map_err(|e| format!("Failed to archive note: {e}"))?;
+
+    Ok(())
+}
+
+// ============================================================================
+// SYNC COMMANDS
+// ============================================================================
+
+/// Parses an RFC 3339 timestamp into the `strftime('%Y-%m-%dT%H:%M:%fZ')` layout
+/// used by the database so it can be compared against `updated_at` as text.
+fn normalize_sync_timestamp(value: &str) -> Result<String, String> {
+    DateTime::parse_from_rfc3339(value.trim())
+        .map(|dt| {
+            dt.with_timezone(&Utc)
+                .format("%Y-%m-%dT%H:%M:%S%.3fZ")
+                .to_string()
+        })
+        .map_err(|_| "Data inválida. Utilize o formato ISO 8601 (RFC 3339).".to_string())
+}
+
+/// Tombstones older than this are pruned at startup; clients that have been
+/// offline longer should fall back to a full reload.
+const DELETION_RETENTION_DAYS: i64 = 30;
+
+async fn record_deletion_tx(
+    tx: &mut Transaction<'_, Sqlite>,
+    entity_type: &str,
+    entity_id: &str,
+    board_id: &str,
+) -> Result<(), String> {
+    sqlx::query(
+        "INSERT INTO deletions (entity_type, entity_id, board_id, deleted_at) VALUES (?, ?, ?, strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))",
+    )
+    .bind(entity_type)
+    .bind(entity_id)
+    .bind(board_id)
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| format!("Falha ao registrar exclusão: {e}"))?;
+
+    Ok(())
+}
+
+async fn log_activity_tx(
+    tx: &mut Transaction<'_, Sqlite>,
+    board_id: &str,
+    entity_type: &str,
+    entity_id: &str,
+    activity_type: &str,
+    title: &str,
+    meta: Option<Value>,
+) -> Result<(), String> {
+    sqlx::query(
+        "INSERT INTO kanban_activity (id, board_id, entity_type, entity_id, activity_type, title, meta, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))",
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(board_id)
+    .bind(entity_type)
+    .bind(entity_id)
+    .bind(activity_type)
+    .bind(title)
+    .bind(meta.map(|value| value.to_string()))
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| format!("Falha ao registrar atividade: {e}"))?;
+
+    Ok(())
+}
+
+async fn prune_deletions(pool: &DbPool) -> Result<(), String> {
+    sqlx::query(
+        "DELETE FROM deletions WHERE deleted_at < strftime('%Y-%m-%dT%H:%M:%fZ', 'now', ?)",
+    )
+    .bind(format!("-{DELETION_RETENTION_DAYS} days"))
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Failed to prune deletion tombstones: {e}"))?;
+
+    Ok(())
+}
+
+fn map_deletion_row(row: SqliteRow) -> Result<Value, sqlx::Error> {
+    Ok(json!({
+        "entityType": row.try_get::<String, _>("entity_type")?,
+        "entityId": row.try_get::<String, _>("entity_id")?,
+        "boardId": row.try_get::<String, _>("board_id")?,
+        "deletedAt": row.try_get::<String, _>("deleted_at")?,
+    }))
+}
+
+#[tauri::command]
+async fn get_deletions_since(pool: State<'_, DbPool>, since: String) -> Result<Vec<Value>, String> {
+    let since = normalize_sync_timestamp(&since)?;
+
+    sqlx::query(
+        "SELECT entity_type, entity_id, board_id, deleted_at FROM deletions WHERE deleted_at > ? ORDER BY deleted_at ASC",
+    )
+    .bind(&since)
+    .try_map(map_deletion_row)
+    .fetch_all(&*pool)
+    .await
+    .map_err(|e| format!("Falha ao carregar exclusões: {e}"))
+}
+
+#[tauri::command]
+async fn get_board_changes_since(
+    pool: State<'_, DbPool>,
+    board_id: String,
+    since: String,
+) -> Result<Value, String> {
+    let since = normalize_sync_timestamp(&since)?;
+
+    let card_sql = format!(
+        "{CARD_SELECT_SQL} WHERE c.board_id = ? AND c.updated_at > ? ORDER BY c.updated_at ASC"
+    );
+    let cards = sqlx::query(&card_sql)
+        .bind(&board_id)
+        .bind(&since)
+        .try_map(map_card_row)
+        .fetch_all(&*pool)
+        .await
+        .map_err(|e| format!("Falha ao carregar cartões alterados: {e}"))?;
+
+    let columns = sqlx::query(
+        "SELECT id, board_id, title, position, color, icon, is_enabled, wip_limit, wip_mode, is_default_intake, column_kind, created_at, updated_at, archived_at FROM kanban_columns WHERE board_id = ? AND updated_at > ? ORDER BY updated_at ASC",
+    )
+    .bind(&board_id)
+    .bind(&since)
+    .try_map(map_column_row)
+    .fetch_all(&*pool)
+    .await
+    .map_err(|e| format!("Falha ao carregar colunas alteradas: {e}"))?;
+
+    let notes = sqlx::query(
+        "SELECT id, board_id, title, content, created_at, updated_at, archived_at, pinned, tags FROM notes WHERE board_id = ? AND updated_at > ? ORDER BY updated_at ASC",
+    )
+    .bind(&board_id)
+    .bind(&since)
+    .try_map(map_note_row)
+    .fetch_all(&*pool)
+    .await
+    .map_err(|e| format!("Falha ao carregar notas alteradas: {e}"))?;
+
+    let deleted = sqlx::query(
+        "SELECT entity_type, entity_id, board_id, deleted_at FROM deletions WHERE board_id = ? AND deleted_at > ? ORDER BY deleted_at ASC",
+    )
+    .bind(&board_id)
+    .bind(&since)
+    .try_map(map_deletion_row)
+    .fetch_all(&*pool)
+    .await
+    .map_err(|e| format!("Falha ao carregar exclusões: {e}"))?;
+
+    Ok(json!({
+        "since": since,
+        "cards": cards,
+        "columns": columns,
+        "notes": notes,
+        "deleted": deleted,
+    }))
+}
+
+// ============================================================================
+// BOARD EXPORT COMMANDS
+// ============================================================================
+
+/// Version of the JSON board export document; bump on incompatible changes.
+const BOARD_EXPORT_SCHEMA_VERSION: i64 = 1;
+
+async fn load_export_board(pool: &DbPool, board_id: &str) -> Result<Value, String> {
+    sqlx::query("SELECT id, workspace_id, title, description, icon, emoji, color, created_at, updated_at, archived_at FROM kanban_boards WHERE id = ?")
+        .bind(board_id)
+        .try_map(map_board_row)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| format!("Falha ao carregar quadro: {e}"))?
+        .ok_or_else(|| "Quadro não encontrado.".to_string())
+}
+
+async fn load_export_columns(pool: &DbPool, board_id: &str) -> Result<Vec<Value>, String> {
+    sqlx::query("SELECT id, board_id, title, position, color, icon, is_enabled, wip_limit, wip_mode, is_default_intake, column_kind, created_at, updated_at, archived_at FROM kanban_columns WHERE board_id = ? ORDER BY position ASC")
+        .bind(board_id)
+        .try_map(map_column_row)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| format!("Falha ao carregar colunas: {e}"))
+}
+
+async fn load_export_tags(pool: &DbPool, board_id: &str) -> Result<Vec<Value>, String> {
+    sqlx::query(
+        "SELECT id, board_id, label, color, position, created_at, updated_at FROM kanban_tags WHERE board_id = ? ORDER BY position ASC, label COLLATE NOCASE ASC",
+    )
+    .bind(board_id)
+    .try_map(map_tag_row)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Falha ao carregar tags: {e}"))
+}
+
+/// Includes archived cards so an export round-trips the full column history.
+async fn load_export_column_cards(pool: &DbPool, column_id: &str) -> Result<Vec<Value>, String> {
+    let sql = format!(
+        "{CARD_SELECT_SQL} WHERE c.column_id = ? ORDER BY c.position ASC, c.created_at ASC"
+    );
+    sqlx::query(&sql)
+        .bind(column_id)
+        .try_map(map_card_row)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| format!("Falha ao carregar cartões: {e}"))
+}
+
+async fn load_export_notes(pool: &DbPool, board_id: &str) -> Result<Vec<Value>, String> {
+    sqlx::query(
+        "SELECT id, board_id, title, content, created_at, updated_at, archived_at, pinned, tags FROM notes WHERE board_id = ? ORDER BY created_at ASC",
+    )
+    .bind(board_id)
+    .try_map(map_note_row)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Falha ao carregar notas: {e}"))
+}
+
+/// Replaces a card's embedded tag objects with sorted `tagIds` and reduces
+/// attachments to a storage reference, so the document never inlines files.
+fn export_card_document(mut card: Value) -> Value {
+    let mut tag_ids: Vec<String> = card
+        .get("tags")
+        .and_then(Value::as_array)
+        .map(|tags| {
+            tags.iter()
+                .filter_map(|tag| tag.get("id").and_then(Value::as_str).map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+    tag_ids.sort();
+
+    let attachments: Vec<Value> = card
+        .get("attachments")
+        .and_then(Value::as_array)
+        .map(|attachments| {
+            attachments
+                .iter()
+                .map(|attachment| {
+                    json!({
+                        "id": attachment.get("id").cloned().unwrap_or(Value::Null),
+                        "originalName": attachment.get("originalName").cloned().unwrap_or(Value::Null),
+                        "mimeType": attachment.get("mimeType").cloned().unwrap_or(Value::Null),
+                        "storagePath": attachment.get("storagePath").cloned().unwrap_or(Value::Null),
+                        "checksum": attachment.get("checksum").cloned().unwrap_or(Value::Null),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if let Some(object) = card.as_object_mut() {
+        object.remove("tags");
+        object.insert("tagIds".to_string(), json!(tag_ids));
+        object.insert("attachments".to_string(), json!(attachments));
+    }
+
+    card
+}
+
+/// Serializes one board as a versioned document. Columns and cards follow their
+/// positions and no export timestamp is included, so repeated exports diff cleanly.
+#[tauri::command]
+async fn export_board(pool: State<'_, DbPool>, board_id: String) -> Result<Value, String> {
+    let board = load_export_board(&pool, &board_id).await?;
+    let columns = load_export_columns(&pool, &board_id).await?;
+    let tags = load_export_tags(&pool, &board_id).await?;
+
+    let mut cards = Vec::new();
+    for column in &columns {
+        let Some(column_id) = column.get("id").and_then(Value::as_str) else {
+            continue;
+        };
+        for card in load_export_column_cards(&pool, column_id).await? {
+            cards.push(export_card_document(card));
+        }
+    }
+
+    let notes = load_export_notes(&pool, &board_id).await?;
+
+    Ok(json!({
+        "schemaVersion": BOARD_EXPORT_SCHEMA_VERSION,
+        "board": board,
+        "columns": columns,
+        "tags": tags,
+        "cards": cards,
+        "notes": notes,
+    }))
+}
+
+/// Reads `field` of an exported entity as a non-empty string.
+fn import_string_field<'a>(entity: &'a Value, field: &str) -> Option<&'a str> {
+    entity
+        .get(field)
+        .and_then(Value::as_str)
+        .filter(|value| !value.trim().is_empty())
+}
+
+fn import_section<'a>(
+    document: &'a Value,
+    name: &str,
+    required: bool,
+    problems: &mut Vec<String>,
+) -> &'a [Value] {
+    match document.get(name) {
+        Some(Value::Array(items)) => items,
+        Some(_) => {
+            problems.push(format!("O campo {name} deve ser uma lista."));
+            &[]
+        }
+        None => {
+            if required {
+                problems.push(format!("Campo obrigatório ausente: {name}."));
+            }
+            &[]
+        }
+    }
+}
+
+/// Collects every structural problem in a board document without touching the
+/// database. An empty list means the document can be imported as-is.
+fn collect_import_problems(document: &Value) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    if !document.is_object() {
+        problems.push("O documento de importação deve ser um objeto JSON.".to_string());
+        return problems;
+    }
+
+    match document.get("schemaVersion").and_then(Value::as_i64) {
+        Some(version) if (1..=BOARD_EXPORT_SCHEMA_VERSION).contains(&version) => {}
+        Some(version) => problems.push(format!(
+            "Versão de esquema {version} não suportada (máximo {BOARD_EXPORT_SCHEMA_VERSION})."
+        )),
+        None => problems.push("Campo obrigatório ausente: schemaVersion.".to_string()),
+    }
+
+    match document.get("board") {
+        Some(board) if board.is_object() => match import_string_field(board, "title") {
+            Some(title) if validate_string_input(title.trim(), 200, "").is_err() => {
+                problems.push("O título do quadro excede 200 caracteres.".to_string());
+            }
+            Some(_) => {}
+            None => problems.push("O quadro não possui título.".to_string()),
+        },
+        _ => problems.push("Campo obrigatório ausente: board.".to_string()),
+    }
+
+    let columns = import_section(document, "columns", true, &mut problems);
+    let tags = import_section(document, "tags", true, &mut problems);
+    let cards = import_section(document, "cards", true, &mut problems);
+    let notes = import_section(document, "notes", false, &mut problems);
+
+    let mut column_ids = BTreeSet::new();
+    for (index, column) in columns.iter().enumerate() {
+        match import_string_field(column, "id") {
+            Some(id) if !column_ids.insert(id) => {
+                problems.push(format!("Coluna {id} aparece mais de uma vez."));
+            }
+            Some(_) => {}
+            None => problems.push(format!("A coluna na posição {index} não possui id.")),
+        }
+        match import_string_field(column, "title") {
+            Some(title) if validate_string_input(title.trim(), 200, "").is_err() => problems.push(
+                format!("O título da coluna na posição {index} excede 200 caracteres."),
+            ),
+            Some(_) => {}
+            None => problems.push(format!("A coluna na posição {index} não possui título.")),
+        }
+        if let Some(mode) = column.get("wipMode").and_then(Value::as_str)
+            && validate_wip_mode(mode).is_err()
+        {
+            problems.push(format!(
+                "A coluna na posição {index} possui modo WIP inválido: {mode}."
+            ));
+        }
+    }
+
+    let mut tag_ids = BTreeSet::new();
+    for (index, tag) in tags.iter().enumerate() {
+        match import_string_field(tag, "id") {
+            Some(id) if !tag_ids.insert(id) => {
+                problems.push(format!("Tag {id} aparece mais de uma vez."));
+            }
+            Some(_) => {}
+            None => problems.push(format!("A tag na posição {index} não possui id.")),
+        }
+        match import_string_field(tag, "label") {
+            Some(label) if validate_string_input(label.trim(), 100, "").is_err() => problems.push(
+                format!("O nome da tag na posição {index} excede 100 caracteres."),
+            ),
+            Some(_) => {}
+            None => problems.push(format!("A tag na posição {index} não possui nome.")),
+        }
+    }
+
+    let mut card_ids = BTreeSet::new();
+    for (index, card) in cards.iter().enumerate() {
+        let label = match import_string_field(card, "id") {
+            Some(id) => {
+                if !card_ids.insert(id) {
+                    problems.push(format!("Cartão {id} aparece mais de uma vez."));
+                }
+                format!("O cartão {id}")
+            }
+            None => {
+                problems.push(format!("O cartão na posição {index} não possui id."));
+                format!("O cartão na posição {index}")
+            }
+        };
+
+        match import_string_field(card, "title") {
+            Some(title) if validate_string_input(title.trim(), 200, "").is_err() => {
+                problems.push(format!("{label} possui título com mais de 200 caracteres."));
+            }
+            Some(_) => {}
+            None => problems.push(format!("{label} não possui título.")),
+        }
+
+        match import_string_field(card, "columnId") {
+            Some(column_id) if !column_ids.contains(column_id) => problems.push(format!(
+                "{label} referencia a coluna {column_id}, que não existe no documento."
+            )),
+            Some(_) => {}
+            None => problems.push(format!("{label} não possui coluna.")),
+        }
+
+        if let Some(priority) = card.get("priority").and_then(Value::as_str)
+            && validate_priority(priority).is_err()
+        {
+            problems.push(format!("{label} possui prioridade inválida: {priority}."));
+        }
+
+        if let Some(due_date) = card.get("dueDate").and_then(Value::as_str)
+            && normalize_due_date(Some(due_date)).is_err()
+        {
+            problems.push(format!(
+                "{label} possui data de vencimento inválida: {due_date}."
+            ));
+        }
+
+        if let Some(remind_at) = card.get("remindAt").and_then(Value::as_str)
+            && normalize_due_date(Some(remind_at)).is_err()
+        {
+            problems.push(format!(
+                "{label} possui data de lembrete inválida: {remind_at}."
+            ));
+        }
+
+        if let Some(recurrence) = card.get("remindRecurrence").and_then(Value::as_str)
+            && validate_remind_recurrence(recurrence).is_err()
+        {
+            problems.push(format!(
+                "{label} possui recorrência de lembrete inválida: {recurrence}."
+            ));
+        }
+
+        for tag_id in card
+            .get("tagIds")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+        {
+            if !tag_ids.contains(tag_id) {
+                problems.push(format!(
+                    "{label} referencia a tag {tag_id}, que não existe no documento."
+                ));
+            }
+        }
+
+        for (subtask_index, subtask) in card
+            .get("subtasks")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .enumerate()
+        {
+            match import_string_field(subtask, "title") {
+                Some(title) if validate_string_input(title.trim(), 200, "").is_err() => {
+                    problems.push(format!(
+                        "{label} possui uma subtarefa com título acima de 200 caracteres na posição {subtask_index}."
+                    ));
+                }
+                Some(_) => {}
+                None => problems.push(format!(
+                    "{label} possui uma subtarefa sem título na posição {subtask_index}."
+                )),
+            }
+        }
+    }
+
+    for (index, note) in notes.iter().enumerate() {
+        if import_string_field(note, "title").is_none() {
+            problems.push(format!("A nota na posição {index} não possui título."));
+        }
+    }
+
+    problems
+}
+
+/// Dry run for board imports: reports problems and entity counts without writing anything.
+#[tauri::command]
+fn validate_import(document: Value) -> Result<Value, String> {
+    let problems = collect_import_problems(&document);
+
+    let count = |name: &str| {
+        document
+            .get(name)
+            .and_then(Value::as_array)
+            .map_or(0, Vec::len)
+    };
+    let subtask_count: usize = document
+        .get("cards")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|card| card.get("subtasks").and_then(Value::as_array))
+        .map(Vec::len)
+        .sum();
+
+    Ok(json!({
+        "ok": problems.is_empty(),
+        "problems": problems,
+        "summary": {
+            "boardTitle": document.get("board").and_then(|board| board.get("title")).cloned().unwrap_or(Value::Null),
+            "columns": count("columns"),
+            "tags": count("tags"),
+            "cards": count("cards"),
+            "subtasks": subtask_count,
+            "notes": count("notes"),
+        },
+    }))
+}
+
+/// Exported flags may be booleans or 0/1 integers depending on their source row.
+fn import_flag(entity: &Value, field: &str, default: bool) -> bool {
+    match entity.get(field) {
+        Some(Value::Bool(flag)) => *flag,
+        Some(value) => value.as_i64().map_or(default, |flag| flag != 0),
+        None => default,
+    }
+}
+
+fn import_optional_string(entity: &Value, field: &str) -> Option<String> {
+    entity
+        .get(field)
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+/// Imports a board document under fresh ids. Everything runs in one transaction,
+/// so any failure leaves no partial board behind. Returns the new board id.
+#[tauri::command]
+async fn import_board(
+    pool: State<'_, DbPool>,
+    document: Value,
+    target_workspace_id: String,
+) -> Result<String, String> {
+    let problems = collect_import_problems(&document);
+    if !problems.is_empty() {
+        return Err(problems.join(" "));
+    }
+
+    let board = &document["board"];
+    let title = board["title"]
+        .as_str()
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+    validate_string_input(&title, 200, "Nome do quadro")?;
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
+
+    let workspace_exists =
+        sqlx::query_scalar::<_, Option<i64>>("SELECT 1 FROM workspaces WHERE id = ? LIMIT 1")
+            .bind(&target_workspace_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| format!("Falha ao verificar workspace: {e}"))?
+            .is_some();
+
+    if !workspace_exists {
+        return Err("Workspace não encontrado.".to_string());
+    }
+
+    let new_board_id = Uuid::new_v4().to_string();
+    sqlx::query(
+        "INSERT INTO kanban_boards (id, workspace_id, title, description, icon, emoji, color, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))",
+    )
+    .bind(&new_board_id)
+    .bind(&target_workspace_id)
+    .bind(&title)
+    .bind(import_optional_string(board, "description"))
+    .bind(import_optional_string(board, "icon"))
+    .bind(import_optional_string(board, "emoji"))
+    .bind(import_optional_string(board, "color"))
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("Falha ao importar quadro: {e}"))?;
+
+    let mut column_map: HashMap<String, String> = HashMap::new();
+    for (index, column) in document["columns"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .enumerate()
+    {
+        let new_column_id = Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO kanban_columns (id, board_id, title, position, color, icon, is_enabled, wip_limit, wip_mode, is_default_intake, column_kind, created_at, updated_at, archived_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), ?)",
+        )
+        .bind(&new_column_id)
+        .bind(&new_board_id)
+        .bind(column["title"].as_str().unwrap_or_default().trim())
+        .bind(column["position"].as_i64().unwrap_or(index as i64))
+        .bind(import_optional_string(column, "color"))
+        .bind(import_optional_string(column, "icon"))
+        .bind(import_flag(column, "isEnabled", true))
+        .bind(column["wipLimit"].as_i64())
+        .bind(column["wipMode"].as_str().unwrap_or("hard"))
+        .bind(import_flag(column, "isDefaultIntake", false))
+        .bind(
+            column["columnKind"]
+                .as_str()
+                .filter(|kind| VALID_COLUMN_KINDS.contains(kind)),
+        )
+        .bind(import_optional_string(column, "archivedAt"))
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao importar coluna: {e}"))?;
+
+        if let Some(old_column_id) = column["id"].as_str() {
+            column_map.insert(old_column_id.to_string(), new_column_id);
+        }
+    }
+
+    let mut tag_map: HashMap<String, String> = HashMap::new();
+    for tag in document["tags"].as_array().into_iter().flatten() {
+        let new_tag_id = Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO kanban_tags (id, board_id, label, color, position, created_at, updated_at) VALUES (?, ?, ?, ?, (SELECT COALESCE(MAX(position), -1) + 1 FROM kanban_tags WHERE board_id = ?), strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))",
+        )
+        .bind(&new_tag_id)
+        .bind(&new_board_id)
+        .bind(tag["label"].as_str().unwrap_or_default().trim())
+        .bind(import_optional_string(tag, "color"))
+        .bind(&new_board_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao importar tag: {e}"))?;
+
+        if let Some(old_tag_id) = tag["id"].as_str() {
+            tag_map.insert(old_tag_id.to_string(), new_tag_id);
+        }
+    }
+
+    for (index, card) in document["cards"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .enumerate()
+    {
+        let new_column_id = card["columnId"]
+            .as_str()
+            .and_then(|column_id| column_map.get(column_id))
+            .ok_or_else(|| "Coluna do cartão não encontrada no documento.".to_string())?;
+        let new_card_id = Uuid::new_v4().to_string();
+
+        sqlx::query(
+            "INSERT INTO kanban_cards (id, board_id, column_id, title, description, position, priority, due_date, remind_at, remind_recurrence, estimate_minutes, created_at, updated_at, archived_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), ?)",
+        )
+        .bind(&new_card_id)
+        .bind(&new_board_id)
+        .bind(new_column_id)
+        .bind(card["title"].as_str().unwrap_or_default().trim())
+        .bind(import_optional_string(card, "description"))
+        .bind(card["position"].as_i64().unwrap_or(index as i64))
+        .bind(card["priority"].as_str().unwrap_or("none"))
+        .bind(normalize_due_date(card["dueDate"].as_str())?)
+        .bind(normalize_due_date(card["remindAt"].as_str())?)
+        .bind(import_optional_string(card, "remindRecurrence"))
+        .bind(card["estimateMinutes"].as_i64())
+        .bind(import_optional_string(card, "archivedAt"))
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao importar cartão: {e}"))?;
+
+        for (subtask_index, subtask) in card["subtasks"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .enumerate()
+        {
+            sqlx::query(
+                "INSERT INTO kanban_subtasks (id, board_id, card_id, title, is_completed, position, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))",
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(&new_board_id)
+            .bind(&new_card_id)
+            .bind(subtask["title"].as_str().unwrap_or_default().trim())
+            .bind(import_flag(subtask, "isCompleted", false))
+            .bind(subtask_index as i64)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Falha ao importar subtarefa: {e}"))?;
+        }
+
+        for old_tag_id in card["tagIds"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+        {
+            let Some(new_tag_id) = tag_map.get(old_tag_id) else {
+                continue;
+            };
+            sqlx::query("INSERT OR IGNORE INTO kanban_card_tags (card_id, tag_id) VALUES (?, ?)")
+                .bind(&new_card_id)
+                .bind(new_tag_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| format!("Falha ao associar tag ao cartão: {e}"))?;
+        }
+    }
+
+    for note in document["notes"].as_array().into_iter().flatten() {
+        let tags = note["tags"]
+            .as_array()
+            .map(|tags| Value::Array(tags.clone()).to_string());
+        sqlx::query(
+            "INSERT INTO notes (id, board_id, title, content, pinned, tags, created_at, updated_at, archived_at) VALUES (?, ?, ?, ?, ?, ?, strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), ?)",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(&new_board_id)
+        .bind(note["title"].as_str().unwrap_or_default().trim())
+        .bind(note["content"].as_str().unwrap_or_default())
+        .bind(import_flag(note, "pinned", false))
+        .bind(tags)
+        .bind(import_optional_string(note, "archivedAt"))
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao importar nota: {e}"))?;
+    }
+
+    normalize_column_positions_tx(&mut tx, &new_board_id)
+        .await
+        .map_err(|e| format!("Falha ao normalizar posições das colunas: {e}"))?;
+
+    for new_column_id in column_map.values() {
+        normalize_card_positions_tx(&mut tx, new_column_id)
+            .await
+            .map_err(|e| format!("Falha ao normalizar posições dos cartões: {e}"))?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
+
+    Ok(new_board_id)
+}
+
+/// Streams the board export to `destination` one column of cards at a time, in the
+/// same card format as `export_board`. Writes to a temporary file first so a failed
+/// export never leaves a partial file.
+#[tauri::command]
+async fn export_board_to_file(
+    pool: State<'_, DbPool>,
+    board_id: String,
+    destination: String,
+) -> Result<Value, String> {
+    use std::io::{BufWriter, Write};
+
+    let destination_path = PathBuf::from(&destination);
+    if destination.trim().is_empty() || destination_path.file_name().is_none() {
+        return Err("Invalid export destination".to_string());
+    }
+
+    if let Some(parent) = destination_path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create export directory {parent:?}: {e}"))?;
+    }
+
+    let board = load_export_board(&pool, &board_id).await?;
+    let columns = load_export_columns(&pool, &board_id).await?;
+    let tags = load_export_tags(&pool, &board_id).await?;
+
+    let temp_path = PathBuf::from(format!("{destination}.tmp"));
+    let file = File::create(&temp_path)
+        .map_err(|e| format!("Failed to create export file {temp_path:?}: {e}"))?;
+    let mut writer = BufWriter::new(file);
+
+    let write_err = |e: std::io::Error| format!("Failed to write export file: {e}");
+    let json_err = |e: serde_json::Error| format!("Failed to serialize export: {e}");
+
+    let result: Result<usize, String> = async {
+        write!(
+            writer,
+            "{{\"schemaVersion\":{BOARD_EXPORT_SCHEMA_VERSION},\"board\":"
+        )
+        .map_err(write_err)?;
+        serde_json::to_writer(&mut writer, &board).map_err(json_err)?;
+        writer.write_all(b",\"columns\":").map_err(write_err)?;
+        serde_json::to_writer(&mut writer, &columns).map_err(json_err)?;
+        writer.write_all(b",\"tags\":").map_err(write_err)?;
+        serde_json::to_writer(&mut writer, &tags).map_err(json_err)?;
+        writer.write_all(b",\"cards\":[").map_err(write_err)?;
+
+        let mut card_count = 0usize;
+        for column in &columns {
+            let Some(column_id) = column.get("id").and_then(Value::as_str) else {
+                continue;
+            };
+            for card in load_export_column_cards(&pool, column_id).await? {
+                if card_count > 0 {
+                    writer.write_all(b",").map_err(write_err)?;
+                }
+                serde_json::to_writer(&mut writer, &export_card_document(card))
+                    .map_err(json_err)?;
+                card_count += 1;
+            }
+        }
+
+        writer.write_all(b"],\"notes\":").map_err(write_err)?;
+        let notes = load_export_notes(&pool, &board_id).await?;
+        serde_json::to_writer(&mut writer, &notes).map_err(json_err)?;
+        writer.write_all(b"}").map_err(write_err)?;
+        writer.flush().map_err(write_err)?;
+
+        Ok(card_count)
+    }
+    .await;
+
+    let card_count = match result {
+        Ok(card_count) => card_count,
+        Err(e) => {
+            let _ = fs::remove_file(&temp_path);
+            return Err(e);
+        }
+    };
+
+    drop(writer);
+    fs::rename(&temp_path, &destination_path).map_err(|e| {
+        let _ = fs::remove_file(&temp_path);
+        format!("Failed to finalize export file: {e}")
+    })?;
+
+    let bytes = fs::metadata(&destination_path)
+        .map_err(|e| format!("Failed to read export file metadata: {e}"))?
+        .len();
+
+    Ok(json!({
+        "path": destination_path.to_string_lossy(),
+        "bytes": bytes,
+        "cardCount": card_count,
+    }))
+}
+
+fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[tauri::command]
+async fn export_board_html(pool: State<'_, DbPool>, board_id: String) -> Result<String, String> {
+    let board = load_export_board(&pool, &board_id).await?;
+    let columns = load_export_columns(&pool, &board_id).await?;
+
+    let card_sql = format!(
+        "{CARD_SELECT_SQL} WHERE c.board_id = ? AND c.archived_at IS NULL ORDER BY c.position ASC"
+    );
+    let cards = sqlx::query(&card_sql)
+        .bind(&board_id)
+        .try_map(map_card_row)
+        .fetch_all(&*pool)
+        .await
+        .map_err(|e| format!("Falha ao carregar cartões: {e}"))?;
+
+    let text = |value: &Value, key: &str| -> String {
+        value
+            .get(key)
+            .and_then(Value::as_str)
+            .map(escape_html)
+            .unwrap_or_default()
+    };
+
+    let board_title = text(&board, "title");
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"pt-BR\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!("<title>{board_title}</title>\n"));
+    html.push_str(
+        "<style>\nbody{font-family:-apple-system,BlinkMacSystemFont,'Segoe UI',sans-serif;margin:24px;color:#111}\n.columns{display:flex;gap:16px;align-items:flex-start}\n.column{flex:1;min-width:180px;border:1px solid #ddd;border-radius:8px;padding:12px}\n.card{border:1px solid #eee;border-radius:6px;padding:8px;margin-top:8px;break-inside:avoid}\n.meta{font-size:12px;color:#555}\n.tag{display:inline-block;font-size:11px;border-radius:4px;padding:1px 6px;margin-right:4px;background:#f0f0f0}\n</style>\n</head>\n<body>\n",
+    );
+    html.push_str(&format!("<h1>{board_title}</h1>\n"));
+
+    let description = text(&board, "description");
+    if !description.is_empty() {
+        html.push_str(&format!("<p>{description}</p>\n"));
+    }
+
+    html.push_str("<div class=\"columns\">\n");
+    for column in &columns {
+        let column_id = column.get("id").and_then(Value::as_str).unwrap_or_default();
+        html.push_str(&format!(
+            "<section class=\"column\">\n<h2>{}</h2>\n",
+            text(column, "title")
+        ));
+
+        for card in cards
+            .iter()
+            .filter(|card| card.get("columnId").and_then(Value::as_str) == Some(column_id))
+        {
+            html.push_str(&format!(
+                "<article class=\"card\">\n<h3>{}</h3>\n",
+                text(card, "title")
+            ));
+
+            let card_description = text(card, "description");
+            if !card_description.is_empty() {
+                html.push_str(&format!("<p>{card_description}</p>\n"));
+            }
+
+            let mut meta = vec![format!("Prioridade: {}", text(card, "priority"))];
+            let due_date = text(card, "dueDate");
+            if !due_date.is_empty() {
+                meta.push(format!("Vencimento: {due_date}"));
+            }
+            html.push_str(&format!("<div class=\"meta\">{}</div>\n", meta.join(" · ")));
+
+            if let Some(tags) = card.get("tags").and_then(Value::as_array)
+                && !tags.is_empty()
+            {
+                html.push_str("<div>");
+                for tag in tags {
+                    html.push_str(&format!(
+                        "<span class=\"tag\">{}</span>",
+                        text(tag, "label")
+                    ));
+                }
+                html.push_str("</div>\n");
+            }
+
+            html.push_str("</article>\n");
+        }
+
+        html.push_str("</section>\n");
+    }
+    html.push_str("</div>\n</body>\n</html>\n");
+
+    Ok(html)
+}
+
+#[tauri::command]
+async fn export_board_markdown(
+    pool: State<'_, DbPool>,
+    board_id: String,
+    include_disabled: Option<bool>,
+) -> Result<String, String> {
+    let board = load_export_board(&pool, &board_id).await?;
+    let columns = load_export_columns(&pool, &board_id).await?;
+
+    let card_sql = format!(
+        "{CARD_SELECT_SQL} WHERE c.board_id = ? AND c.archived_at IS NULL ORDER BY c.position ASC"
+    );
+    let cards = sqlx::query(&card_sql)
+        .bind(&board_id)
+        .try_map(map_card_row)
+        .fetch_all(&*pool)
+        .await
+        .map_err(|e| format!("Falha ao carregar cartões: {e}"))?;
+
+    // Markdown is line oriented, so embedded newlines would break list nesting.
+    let text = |value: &Value, key: &str| -> String {
+        value
+            .get(key)
+            .and_then(Value::as_str)
+            .map(|raw| raw.split_whitespace().collect::<Vec<_>>().join(" "))
+            .unwrap_or_default()
+    };
+
+    let include_disabled = include_disabled.unwrap_or(false);
+    let mut markdown = format!("# {}\n", text(&board, "title"));
+
+    let description = text(&board, "description");
+    if !description.is_empty() {
+        markdown.push_str(&format!("\n{description}\n"));
+    }
+
+    for column in &columns {
+        let is_enabled = column
+            .get("isEnabled")
+            .and_then(Value::as_bool)
+            .unwrap_or(true);
+        if !is_enabled && !include_disabled {
+            continue;
+        }
+
+        let column_id = column.get("id").and_then(Value::as_str).unwrap_or_default();
+        markdown.push_str(&format!("\n## {}\n\n", text(column, "title")));
+
+        for card in cards
+            .iter()
+            .filter(|card| card.get("columnId").and_then(Value::as_str) == Some(column_id))
+        {
+            let mut line = format!("- **{}**", text(card, "title"));
+            line.push_str(&format!(" · Prioridade: {}", text(card, "priority")));
+
+            let due_date = text(card, "dueDate");
+            if !due_date.is_empty() {
+                line.push_str(&format!(" · Vencimento: {due_date}"));
+            }
+
+            for tag in card
+                .get("tags")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                let label = text(tag, "label").replace(' ', "-");
+                if !label.is_empty() {
+                    line.push_str(&format!(" #{label}"));
+                }
+            }
+
+            markdown.push_str(&line);
+            markdown.push('\n');
+
+            for subtask in card
+                .get("subtasks")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                let completed = match subtask.get("isCompleted") {
+                    Some(Value::Bool(flag)) => *flag,
+                    Some(value) => value.as_i64().is_some_and(|flag| flag != 0),
+                    None => false,
+                };
+                let mark = if completed { 'x' } else { ' ' };
+                markdown.push_str(&format!("  - [{mark}] {}\n", text(subtask, "title")));
+            }
+        }
+    }
+
+    Ok(markdown)
+}
+
+/// One row per card, ordered by column then card position, for spreadsheets.
+#[tauri::command]
+async fn export_cards_csv(
+    pool: State<'_, DbPool>,
+    board_id: String,
+    include_archived: Option<bool>,
+) -> Result<String, String> {
+    load_export_board(&pool, &board_id).await?;
+
+    let archived_filter = if include_archived.unwrap_or(false) {
+        ""
+    } else {
+        " AND c.archived_at IS NULL"
+    };
+    let sql = format!(
+        r#"
+        SELECT
+            col.title AS column_title,
+            c.title,
+            c.priority,
+            c.due_date,
+            c.created_at,
+            (SELECT COUNT(*) FROM kanban_subtasks s WHERE s.card_id = c.id AND s.is_completed = 1) AS completed_subtasks,
+            (SELECT COUNT(*) FROM kanban_subtasks s WHERE s.card_id = c.id) AS total_subtasks,
+            (
+                SELECT GROUP_CONCAT(label, ', ') FROM (
+                    SELECT t.label FROM kanban_card_tags ct
+                    JOIN kanban_tags t ON t.id = ct.tag_id
+                    WHERE ct.card_id = c.id
+                    ORDER BY t.label COLLATE NOCASE ASC
+                )
+            ) AS tag_labels
+        FROM kanban_cards c
+        JOIN kanban_columns col ON col.id = c.column_id
+        WHERE c.board_id = ?{archived_filter}
+        ORDER BY col.position ASC, c.position ASC
+        "#
+    );
+
+    let rows = sqlx::query(&sql)
+        .bind(&board_id)
+        .fetch_all(&*pool)
+        .await
+        .map_err(|e| format!("Falha ao carregar cartões: {e}"))?;
+
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer
+        .write_record([
+            "Coluna",
+            "Cartão",
+            "Prioridade",
+            "Vencimento",
+            "Criado em",
+            "Subtarefas",
+            "Tags",
+        ])
+        .map_err(|e| format!("Falha ao gerar CSV: {e}"))?;
+
+    for row in rows {
+        let column_title: String = row.try_get("column_title").map_err(|e| e.to_string())?;
+        let title: String = row.try_get("title").map_err(|e| e.to_string())?;
+        let priority: String = row.try_get("priority").map_err(|e| e.to_string())?;
+        let due_date: Option<String> = row.try_get("due_date").map_err(|e| e.to_string())?;
+        let created_at: String = row.try_get("created_at").map_err(|e| e.to_string())?;
+        let completed: i64 = row
+            .try_get("completed_subtasks")
+            .map_err(|e| e.to_string())?;
+        let total: i64 = row.try_get("total_subtasks").map_err(|e| e.to_string())?;
+        let tag_labels: Option<String> = row.try_get("tag_labels").map_err(|e| e.to_string())?;
+
+        writer
+            .write_record([
+                column_title,
+                title,
+                priority,
+                due_date.unwrap_or_default(),
+                created_at,
+                format!("{completed}/{total}"),
+                tag_labels.unwrap_or_default(),
+            ])
+            .map_err(|e| format!("Falha ao gerar CSV: {e}"))?;
+    }
+
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| format!("Falha ao gerar CSV: {e}"))?;
+    String::from_utf8(bytes).map_err(|e| format!("Falha ao gerar CSV: {e}"))
+}
+
+/// Renders Markdown (e.g. a card description) to sanitized HTML. `ammonia`
+/// drops scripts, event handler attributes and `javascript:` URLs; task-list
+/// checkboxes survive, but any `<input>` is forced to a disabled checkbox.
+#[tauri::command]
+fn render_markdown(content: String) -> String {
+    let options = pulldown_cmark::Options::ENABLE_TABLES
+        | pulldown_cmark::Options::ENABLE_STRIKETHROUGH
+        | pulldown_cmark::Options::ENABLE_TASKLISTS;
+    let parser = pulldown_cmark::Parser::new_ext(&content, options);
+
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(&mut html, parser);
+
+    ammonia::Builder::default()
+        .add_tags(["input"])
+        .add_tag_attributes("input", ["checked"])
+        .set_tag_attribute_value("input", "type", "checkbox")
+        .set_tag_attribute_value("input", "disabled", "")
+        .clean(&html)
+        .to_string()
+}
+
+// ============================================================================
+// HOME DASHBOARD COMMANDS
+// ============================================================================
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskStats {
+    pub total_projects: i64,
+    pub active_projects: i64,
+    pub tasks_today: i64,
+    pub tasks_this_week: i64,
+    pub completed_today: i64,
+    pub completed_this_week: i64,
+    pub overdue_tasks: i64,
+}
+
+async fn load_done_column_keywords<R: Runtime>(app: &AppHandle<R>) -> Vec<String> {
+    let keywords = load_preferences(app.clone())
+        .await
+        .map(|preferences| preferences.done_column_keywords)
+        .unwrap_or_else(|e| {
+            log::warn!("Falling back to default done keywords: {e}");
+            default_done_column_keywords()
+        });
+
+    keywords
+        .iter()
+        .map(|keyword| keyword.trim().to_lowercase())
+        .filter(|keyword| !keyword.is_empty())
+        .collect()
+}
+
+/// SQL predicate matching "done" columns of `column_alias` by title. Contains one
+/// placeholder per keyword; bind `done_keyword_patterns` in the same order.
+fn done_column_predicate(column_alias: &str, keywords: &[String]) -> String {
+    let title_heuristic = if keywords.is_empty() {
+        "0".to_string()
+    } else {
+        let clauses: Vec<String> = keywords
+            .iter()
+            .map(|_| format!("LOWER({column_alias}.title) LIKE ? ESCAPE '\\'"))
+            .collect();
+        format!("({})", clauses.join(" OR "))
+    };
+
+    // Boards that classify any column rely on column_kind alone; titles are only
+    // consulted for boards that have not been classified yet.
+    format!(
+        "(CASE WHEN EXISTS (SELECT 1 FROM kanban_columns kind_col WHERE kind_col.board_id = {column_alias}.board_id AND kind_col.column_kind IS NOT NULL) THEN COALESCE({column_alias}.column_kind = 'done', 0) ELSE {title_heuristic} END)"
+    )
+}
+
+fn done_keyword_patterns(keywords: &[String]) -> Vec<String> {
+    keywords
+        .iter()
+        .map(|keyword| {
+            let escaped = keyword
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_");
+            format!("%{escaped}%")
+        })
+        .collect()
+}
+
+#[tauri::command]
+async fn get_task_statistics<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+) -> Result<TaskStats, String> {
+    let keywords = load_done_column_keywords(&app).await;
+    let done_predicate = done_column_predicate("c2", &keywords);
+    let patterns = done_keyword_patterns(&keywords);
+
+    let query = format!(
+        r#"
+        SELECT
+            COUNT(DISTINCT b.id) as total_projects,
+            COUNT(DISTINCT CASE WHEN b.archived_at IS NULL THEN b.id END) as active_projects,
+            COUNT(CASE WHEN date(t.due_date) = date('now') AND t.archived_at IS NULL THEN 1 END) as tasks_today,
+            COUNT(CASE WHEN t.due_date >= date('now', '-7 days') AND t.archived_at IS NULL THEN 1 END) as tasks_this_week,
+            COUNT(CASE WHEN t.archived_at IS NULL AND (
+                SELECT COUNT(*) FROM kanban_columns c2 WHERE c2.id = t.column_id AND {done_predicate}
+            ) > 0 THEN 1 END) as completed_today,
+            COUNT(CASE WHEN t.archived_at IS NULL AND t.updated_at >= date('now', '-7 days') AND (
+                SELECT COUNT(*) FROM kanban_columns c2 WHERE c2.id = t.column_id AND {done_predicate}
+            ) > 0 THEN 1 END) as completed_this_week,
+            COUNT(CASE WHEN t.due_date < datetime('now') AND t.archived_at IS NULL AND (
+                SELECT COUNT(*) FROM kanban_columns c2 WHERE c2.id = t.column_id AND NOT {done_predicate}
+            ) > 0 THEN 1 END) as overdue_tasks
+        FROM kanban_boards b
+        LEFT JOIN kanban_columns col ON col.board_id = b.id
+        LEFT JOIN kanban_cards t ON t.column_id = col.id
+    "#
+    );
+
+    let mut statement = sqlx::query(&query);
+    for _ in 0..3 {
+        for pattern in &patterns {
+            statement = statement.bind(pattern);
+        }
+    }
+
+    let row = statement
+        .fetch_one(&*pool)
+        .await
+        .map_err(|e| format!("Failed to get task statistics: {e}"))?;
+
+    Ok(TaskStats {
+        total_projects: row.get::<i64, _>("total_projects"),
+        active_projects: row.get::<i64, _>("active_projects"),
+        tasks_today: row.get::<i64, _>("tasks_today"),
+        tasks_this_week: row.get::<i64, _>("tasks_this_week"),
+        completed_today: row.get::<i64, _>("completed_today"),
+        completed_this_week: row.get::<i64, _>("completed_this_week"),
+        overdue_tasks: row.get::<i64, _>("overdue_tasks"),
+    })
+}
+
+#[tauri::command]
+async fn get_overdue_by_priority<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+) -> Result<Value, String> {
+    let keywords = load_done_column_keywords(&app).await;
+    let done_predicate = done_column_predicate("col", &keywords);
+
+    let query = format!(
+        r#"
+        SELECT t.priority, COUNT(*)
+        FROM kanban_cards t
+        JOIN kanban_columns col ON col.id = t.column_id
+        WHERE t.due_date < datetime('now') AND t.archived_at IS NULL AND NOT {done_predicate}
+        GROUP BY t.priority
+    "#
+    );
+
+    let mut statement = sqlx::query_as::<_, (String, i64)>(&query);
+    for pattern in done_keyword_patterns(&keywords) {
+        statement = statement.bind(pattern);
+    }
+
+    let rows = statement
+        .fetch_all(&*pool)
+        .await
+        .map_err(|e| format!("Failed to count overdue tasks: {e}"))?;
+
+    let mut counts = json!({ "none": 0, "low": 0, "medium": 0, "high": 0 });
+    let mut total = 0;
+    for (priority, count) in rows {
+        counts[priority.as_str()] = json!(count);
+        total += count;
+    }
+    counts["total"] = json!(total);
+
+    Ok(counts)
+}
+
+/// Per-board counts for the sidebar. "Due soon" covers cards due today or tomorrow
+/// that are not already overdue; cards in done columns count toward neither.
+#[tauri::command]
+async fn get_sidebar_badges<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+) -> Result<Vec<Value>, String> {
+    let keywords = load_done_column_keywords(&app).await;
+    let done_predicate = done_column_predicate("col", &keywords);
+
+    let query = format!(
+        r#"
+        SELECT
+            b.id,
+            COUNT(CASE WHEN NOT c.is_done AND julianday(c.due_date) < julianday('now') THEN 1 END) as overdue_count,
+            COUNT(CASE WHEN NOT c.is_done AND julianday(c.due_date) >= julianday('now')
+                AND date(c.due_date) <= date('now', '+1 day') THEN 1 END) as due_soon_count,
+            COUNT(c.id) as total_active
+        FROM kanban_boards b
+        LEFT JOIN (
+            SELECT t.id, t.board_id, t.due_date, {done_predicate} as is_done
+            FROM kanban_cards t
+            JOIN kanban_columns col ON col.id = t.column_id
+            WHERE t.archived_at IS NULL
+        ) c ON c.board_id = b.id
+        WHERE b.archived_at IS NULL AND {BOARD_WORKSPACE_ACTIVE_SQL}
+        GROUP BY b.id
+        ORDER BY b.created_at ASC
+    "#
+    );
+
+    let mut statement = sqlx::query_as::<_, (String, i64, i64, i64)>(&query);
+    for pattern in done_keyword_patterns(&keywords) {
+        statement = statement.bind(pattern);
+    }
+
+    let rows = statement
+        .fetch_all(&*pool)
+        .await
+        .map_err(|e| format!("Failed to load sidebar badges: {e}"))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(board_id, overdue_count, due_soon_count, total_active)| {
+            json!({
+                "boardId": board_id,
+                "overdueCount": overdue_count,
+                "dueSoonCount": due_soon_count,
+                "totalActive": total_active,
+            })
+        })
+        .collect())
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Activity {
+    pub id: String,
+    pub activity_type: String,
+    pub title: String,
+    pub board_name: String,
+    pub board_icon: Option<String>,
+    pub timestamp: String,
+    pub entity_id: String,
+    pub entity_type: String,
+    pub meta: Option<Value>,
+}
+
+fn map_activity_row(row: SqliteRow) -> Activity {
+    Activity {
+        id: row.get("id"),
+        activity_type: row.get("activity_type"),
+        title: row.get("title"),
+        board_name: row.get("board_name"),
+        board_icon: row.get("board_icon"),
+        timestamp: row.get("timestamp"),
+        entity_id: row.get("entity_id"),
+        entity_type: row.get("entity_type"),
+        meta: row
+            .get::<Option<String>, _>("meta")
+            .as_deref()
+            .and_then(|raw| serde_json::from_str::<Value>(raw).ok()),
+    }
+}
+
+#[tauri::command]
+async fn get_recent_activity(
+    pool: State<'_, DbPool>,
+    limit: Option<i32>,
+) -> Result<Vec<Activity>, String> {
+    let limit = limit.unwrap_or(10);
+
+    // Deleted boards leave no row to join, so board activity falls back to its own title.
+    let query = r#"
+        SELECT
+            a.id,
+            a.activity_type,
+            a.entity_id,
+            a.entity_type,
+            a.title,
+            a.meta,
+            COALESCE(b.title, CASE WHEN a.entity_type = 'board' THEN a.title END, '') as board_name,
+            b.icon as board_icon,
+            a.created_at as timestamp
+        FROM kanban_activity a
+        LEFT JOIN kanban_boards b ON b.id = a.board_id
+        ORDER BY a.created_at DESC
+        LIMIT ?
+    "#;
+
+    let activities = sqlx::query(query)
+        .bind(limit)
+        .fetch_all(&*pool)
+        .await
+        .map_err(|e| format!("Failed to get recent activity: {e}"))?;
+
+    let mapped_activities: Vec<Activity> = activities.into_iter().map(map_activity_row).collect();
+
+    Ok(mapped_activities)
+}
+
+const DEFAULT_ACTIVITY_LIMIT: i64 = 100;
+const MAX_ACTIVITY_LIMIT: i64 = 1000;
+
+/// The most recent `limit` log entries for one entity, returned oldest first.
+async fn load_entity_activity(
+    pool: &DbPool,
+    entity_type: &str,
+    entity_id: &str,
+    limit: Option<i64>,
+) -> Result<Vec<Activity>, String> {
+    let limit = limit
+        .unwrap_or(DEFAULT_ACTIVITY_LIMIT)
+        .clamp(1, MAX_ACTIVITY_LIMIT);
+
+    let query = r#"
+        SELECT * FROM (
+            SELECT
+                a.id,
+                a.activity_type,
+                a.entity_id,
+                a.entity_type,
+                a.title,
+                a.meta,
+                COALESCE(b.title, CASE WHEN a.entity_type = 'board' THEN a.title END, '') as board_name,
+                b.icon as board_icon,
+                a.created_at as timestamp,
+                a.rowid as seq
+            FROM kanban_activity a
+            LEFT JOIN kanban_boards b ON b.id = a.board_id
+            WHERE a.entity_type = ? AND a.entity_id = ?
+            ORDER BY a.created_at DESC, a.rowid DESC
+            LIMIT ?
+        )
+        ORDER BY timestamp ASC, seq ASC
+    "#;
+
+    let rows = sqlx::query(query)
+        .bind(entity_type)
+        .bind(entity_id)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| format!("Falha ao carregar atividades: {e}"))?;
+
+    Ok(rows.into_iter().map(map_activity_row).collect())
+}
+
+/// Chronological timeline of a single card or board from the activity log.
+#[tauri::command]
+async fn get_activity_for_entity(
+    pool: State<'_, DbPool>,
+    entity_type: String,
+    entity_id: String,
+    limit: Option<i64>,
+) -> Result<Vec<Activity>, String> {
+    if !matches!(entity_type.as_str(), "card" | "board") {
+        return Err("Tipo de entidade inválido. Utilize 'card' ou 'board'.".to_string());
+    }
+
+    load_entity_activity(&pool, &entity_type, &entity_id, limit).await
+}
+
+/// A card's history, oldest first. With `limit`, only the most recent entries
+/// are kept.
+#[tauri::command]
+async fn get_card_activity(
+    pool: State<'_, DbPool>,
+    card_id: String,
+    board_id: String,
+    limit: Option<i64>,
+) -> Result<Vec<Activity>, String> {
+    let card_board_id =
+        sqlx::query_scalar::<_, String>("SELECT board_id FROM kanban_cards WHERE id = ?")
+            .bind(&card_id)
+            .fetch_optional(&*pool)
+            .await
+            .map_err(|e| format!("Falha ao carregar cartão: {e}"))?
+            .ok_or_else(|| "Cartão não encontrado.".to_string())?;
+
+    if card_board_id != board_id {
+        return Err("O cartão não pertence ao quadro informado.".to_string());
+    }
+
+    load_entity_activity(&pool, "card", &card_id, limit).await
+}
+
+/// Share of active cards sitting in done columns, rounded to a whole percent.
+fn completion_percent(done_cards: i64, active_cards: i64) -> i64 {
+    if active_cards <= 0 {
+        return 0;
+    }
+    ((done_cards as f64 / active_cards as f64) * 100.0).round() as i64
+}
+
+#[tauri::command]
+async fn get_favorite_boards<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+) -> Result<Vec<Value>, String> {
+    let keywords = load_done_column_keywords(&app).await;
+    let done_predicate = done_column_predicate("col", &keywords);
+
+    let query = format!(
+        r#"
+        SELECT
+            b.id,
+            b.title,
+            b.icon,
+            b.emoji,
+            b.color,
+            b.created_at,
+            b.updated_at,
+            b.is_favorite,
+            COUNT(DISTINCT c.id) as total_cards,
+            COUNT(DISTINCT CASE WHEN c.archived_at IS NULL THEN c.id END) as active_cards,
+            COUNT(DISTINCT CASE WHEN c.archived_at IS NULL AND {done_predicate} THEN c.id END) as done_cards
+        FROM kanban_boards b
+        LEFT JOIN kanban_columns col ON col.board_id = b.id
+        LEFT JOIN kanban_cards c ON c.column_id = col.id
+        WHERE b.is_favorite = 1 AND b.archived_at IS NULL AND {BOARD_WORKSPACE_ACTIVE_SQL}
+        GROUP BY b.id
+        ORDER BY b.updated_at DESC
+    "#
+    );
+
+    let mut statement = sqlx::query(&query);
+    for pattern in done_keyword_patterns(&keywords) {
+        statement = statement.bind(pattern);
+    }
+
+    let boards = statement
+        .fetch_all(&*pool)
+        .await
+        .map_err(|e| format!("Failed to get favorite boards: {e}"))?;
+
+    let mapped_boards: Vec<Value> = boards
+        .into_iter()
+        .map(|board| {
+            let id = board.get::<String, _>("id");
+            let title = board.get::<String, _>("title");
+            let icon = board.get::<String, _>("icon");
+            let emoji = board.get::<Option<String>, _>("emoji");
+            let color = board.get::<Option<String>, _>("color");
+            let created_at = board.get::<String, _>("created_at");
+            let updated_at = board.get::<String, _>("updated_at");
+            let is_favorite: i32 = board.get("is_favorite");
+            let total_cards: i64 = board.get("total_cards");
+            let active_cards: i64 = board.get("active_cards");
+            let done_cards: i64 = board.get("done_cards");
+
+            json!({
+                "id": id,
+                "title": title,
+                "icon": icon,
+                "emoji": emoji,
+                "color": color,
+                "isFavorite": is_favorite != 0,
+                "createdAt": created_at,
+                "updatedAt": updated_at,
+                "totalCards": total_cards,
+                "activeCards": active_cards,
+                "completionPercent": completion_percent(done_cards, active_cards),
+            })
+        })
+        .collect();
+
+    Ok(mapped_boards)
+}
+
+fn map_board_stats_row(row: SqliteRow) -> Result<Value, sqlx::Error> {
+    Ok(json!({
+        "boardId": row.try_get::<String, _>("board_id")?,
+        "totalCards": row.try_get::<i64, _>("total_cards")?,
+        "activeCards": row.try_get::<i64, _>("active_cards")?,
+        "updatedAt": row.try_get::<String, _>("updated_at")?,
+    }))
+}
+
+/// Renumbers columns, cards and subtasks of a board to contiguous
+/// positions. The returned counts only include rows whose position changed.
+#[tauri::command]
+async fn repair_board_positions<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    board_id: String,
+) -> Result<Value, String> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Falha ao abrir transação: {e}"))?;
+
+    let board_exists = sqlx::query_scalar::<_, i64>("SELECT 1 FROM kanban_boards WHERE id = ?")
+        .bind(&board_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| format!("Falha ao carregar quadro: {e}"))?
+        .is_some();
+
+    if !board_exists {
+        return Err("Quadro não encontrado.".to_string());
+    }
+
+    let (columns, cards, subtasks) = sqlx::query_as::<_, (i64, i64, i64)>(
+        "SELECT
+            (SELECT COUNT(*) FROM (SELECT position, ROW_NUMBER() OVER (ORDER BY position ASC, created_at ASC) - 1 AS expected FROM kanban_columns WHERE board_id = ?1) WHERE position != expected),
+            (SELECT COUNT(*) FROM (SELECT position, ROW_NUMBER() OVER (PARTITION BY column_id ORDER BY position ASC, created_at ASC) - 1 AS expected FROM kanban_cards WHERE board_id = ?1) WHERE position != expected),
+            (SELECT COUNT(*) FROM (SELECT position, ROW_NUMBER() OVER (PARTITION BY card_id ORDER BY position ASC, created_at ASC) - 1 AS expected FROM kanban_subtasks WHERE board_id = ?1) WHERE position != expected)",
+    )
+    .bind(&board_id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| format!("Falha ao analisar posições do quadro: {e}"))?;
+
+    normalize_column_positions_tx(&mut tx, &board_id)
+        .await
+        .map_err(|e| format!("Falha ao normalizar posições das colunas: {e}"))?;
+
+    let column_ids =
+        sqlx::query_scalar::<_, String>("SELECT id FROM kanban_columns WHERE board_id = ?")
+            .bind(&board_id)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(|e| format!("Falha ao carregar colunas: {e}"))?;
+
+    for column_id in &column_ids {
+        normalize_card_positions_tx(&mut tx, column_id)
+            .await
+            .map_err(|e| format!("Falha ao normalizar posições dos cartões: {e}"))?;
+    }
+
+    let card_ids = sqlx::query_scalar::<_, String>(
+        "SELECT DISTINCT card_id FROM kanban_subtasks WHERE board_id = ?",
+    )
+    .bind(&board_id)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| format!("Falha ao carregar cartões: {e}"))?;
+
+    for card_id in &card_ids {
+        normalize_subtask_positions_tx(&mut tx, card_id)
+            .await
+            .map_err(|e| format!("Falha ao normalizar posições das subtarefas: {e}"))?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Falha ao confirmar transação: {e}"))?;
+
+    if columns + cards + subtasks > 0 {
+        emit_board_changed(&app, &board_id, "positions_repaired");
+    }
+
+    Ok(json!({
+        "columns": columns,
+        "cards": cards,
+        "subtasks": subtasks,
+    }))
+}
+
+#[tauri::command]
+async fn refresh_board_stats(pool: State<'_, DbPool>, board_id: String) -> Result<Value, String> {
+    let board_exists = sqlx::query_scalar::<_, i64>("SELECT 1 FROM kanban_boards WHERE id = ?")
+        .bind(&board_id)
+        .fetch_optional(&*pool)
+        .await
+        .map_err(|e| format!("Falha ao carregar quadro: {e}"))?
+        .is_some();
+
+    if !board_exists {
+        return Err("Quadro não encontrado.".to_string());
+    }
+
+    sqlx::query(
+        "INSERT INTO board_stats_cache (board_id, total_cards, active_cards, updated_at)
+         SELECT ?,
+                COUNT(*),
+                COUNT(CASE WHEN archived_at IS NULL THEN 1 END),
+                strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+         FROM kanban_cards WHERE board_id = ?
+         ON CONFLICT(board_id) DO UPDATE SET
+            total_cards = excluded.total_cards,
+            active_cards = excluded.active_cards,
+            updated_at = excluded.updated_at",
+    )
+    .bind(&board_id)
+    .bind(&board_id)
+    .execute(&*pool)
+    .await
+    .map_err(|e| format!("Falha ao atualizar estatísticas do quadro: {e}"))?;
+
+    sqlx::query(
+        "SELECT board_id, total_cards, active_cards, updated_at FROM board_stats_cache WHERE board_id = ?",
+    )
+    .bind(&board_id)
+    .try_map(map_board_stats_row)
+    .fetch_one(&*pool)
+    .await
+    .map_err(|e| format!("Falha ao carregar estatísticas do quadro: {e}"))
+}
+
+#[tauri::command]
+async fn get_cached_board_stats(
+    pool: State<'_, DbPool>,
+    board_id: String,
+) -> Result<Value, String> {
+    let cached = sqlx::query(
+        "SELECT board_id, total_cards, active_cards, updated_at FROM board_stats_cache WHERE board_id = ?",
+    )
+    .bind(&board_id)
+    .try_map(map_board_stats_row)
+    .fetch_optional(&*pool)
+    .await
+    .map_err(|e| format!("Falha ao carregar estatísticas do quadro: {e}"))?;
+
+    match cached {
+        Some(stats) => Ok(stats),
+        None => refresh_board_stats(pool, board_id).await,
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskWithDeadline {
+    pub id: String,
+    pub title: String,
+    pub deadline: String,
+    pub board_name: String,
+    pub board_id: String,
+    pub is_overdue: bool,
+    pub days_until: i64,
+}
+
+#[tauri::command]
+async fn get_upcoming_deadlines(
+    pool: State<'_, DbPool>,
+    days_ahead: Option<i32>,
+) -> Result<Vec<TaskWithDeadline>, String> {
+    let days_ahead = days_ahead.unwrap_or(7);
+
+    // julianday() compares instants, so date-only and full timestamps order correctly
+    let query = format!(
+        r#"
+        SELECT
+            c.id,
+            c.title,
+            c.due_date,
+            b.title as board_name,
+            b.id as board_id,
+            CAST(julianday(c.due_date) - julianday('now') AS INTEGER) as days_until,
+            julianday(c.due_date) < julianday('now') as is_overdue
+        FROM kanban_cards c
+        JOIN kanban_columns col ON col.id = c.column_id
+        JOIN kanban_boards b ON b.id = col.board_id
+        WHERE c.due_date IS NOT NULL
+        AND c.archived_at IS NULL
+        AND date(c.due_date) <= date('now', '+' || ? || ' days')
+        AND b.archived_at IS NULL
+        AND {BOARD_WORKSPACE_ACTIVE_SQL}
+        ORDER BY c.due_date ASC
+    "#
+    );
+
+    let tasks = sqlx::query(&query)
+        .bind(days_ahead as i64)
+        .fetch_all(&*pool)
+        .await
+        .map_err(|e| format!("Failed to get upcoming deadlines: {e}"))?;
+
+    let mapped_tasks: Vec<TaskWithDeadline> = tasks
+        .into_iter()
+        .map(|task| TaskWithDeadline {
+            id: task.get("id"),
+            title: task.get("title"),
+            deadline: task.get("due_date"),
+            board_name: task.get("board_name"),
+            board_id: task.get("board_id"),
+            is_overdue: task.get::<Option<bool>, _>("is_overdue").unwrap_or(false),
+            days_until: task.get::<Option<i64>, _>("days_until").unwrap_or(0),
+        })
+        .collect();
+
+    Ok(mapped_tasks)
+}
+
+/// Everything the home screen needs in one IPC round trip; the four sections are
+/// loaded concurrently and the first failure fails the whole payload.
+#[tauri::command]
+async fn get_home_dashboard<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    deadline_days: Option<i32>,
+    activity_limit: Option<i32>,
+) -> Result<Value, String> {
+    let (statistics, recent_activity, favorite_boards, upcoming_deadlines) = join4(
+        get_task_statistics(app.clone(), pool.clone()),
+        get_recent_activity(pool.clone(), activity_limit),
+        get_favorite_boards(app.clone(), pool.clone()),
+        get_upcoming_deadlines(pool.clone(), deadline_days),
+    )
+    .await;
+
+    Ok(json!({
+        "statistics": statistics?,
+        "recentActivity": recent_activity?,
+        "favoriteBoards": favorite_boards?,
+        "upcomingDeadlines": upcoming_deadlines?,
+    }))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResult {
+    pub id: String,
+    pub title: String,
+    pub item_type: String,
+    pub board_id: String,
+    pub board_name: String,
+    pub description: Option<String>,
+    /// BM25 score from the FTS index (lower is more relevant); 0 for `LIKE` matches.
+    pub rank: f64,
+}
+
+/// Quotes each whitespace-separated term as an FTS5 prefix query, so user input
+/// can never be parsed as query syntax.
+fn build_fts_query(query: &str) -> String {
+    query
+        .split_whitespace()
+        .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResults {
+    pub results: Vec<SearchResult>,
+    pub total_boards: i64,
+    pub total_cards: i64,
+    pub total_notes: i64,
+}
+
+const DEFAULT_SEARCH_LIMIT: i64 = 100;
+const MAX_SEARCH_LIMIT: i64 = 500;
+
+/// One result type in `global_search`; `source` is the FROM/WHERE shared by the
+/// count and the page query.
+struct SearchSection {
+    item_type: &'static str,
+    columns: &'static str,
+    source: &'static str,
+    order_by: &'static str,
+}
+
+const FTS_SEARCH_SECTIONS: [SearchSection; 3] = [
+    SearchSection {
+        item_type: "board",
+        columns: "b.id, b.title, b.description, b.title as board_name, b.id as board_id, bm25(kanban_fts) as score",
+        source: concat!(
+            "FROM kanban_fts JOIN kanban_boards b ON b.id = kanban_fts.entity_id WHERE kanban_fts MATCH ? AND kanban_fts.entity_type = 'board' AND b.archived_at IS NULL AND ",
+            board_workspace_active_sql!()
+        ),
+        order_by: "score ASC",
+    },
+    SearchSection {
+        item_type: "card",
+        columns: "c.id, c.title, c.description, b.title as board_name, b.id as board_id, bm25(kanban_fts) as score",
+        source: concat!(
+            "FROM kanban_fts JOIN kanban_cards c ON c.id = kanban_fts.entity_id JOIN kanban_columns col ON col.id = c.column_id JOIN kanban_boards b ON b.id = col.board_id WHERE kanban_fts MATCH ? AND kanban_fts.entity_type = 'card' AND c.archived_at IS NULL AND ",
+            board_workspace_active_sql!()
+        ),
+        order_by: "score ASC",
+    },
+    SearchSection {
+        item_type: "note",
+        columns: "n.id, n.title, n.content as description, b.title as board_name, b.id as board_id, bm25(kanban_fts) as score",
+        source: concat!(
+            "FROM kanban_fts JOIN notes n ON n.id = kanban_fts.entity_id JOIN kanban_boards b ON b.id = n.board_id WHERE kanban_fts MATCH ? AND kanban_fts.entity_type = 'note' AND n.archived_at IS NULL AND ",
+            board_workspace_active_sql!()
+        ),
+        order_by: "score ASC",
+    },
+];
+
+const LIKE_SEARCH_SECTIONS: [SearchSection; 3] = [
+    SearchSection {
+        item_type: "board",
+        columns: "b.id, b.title, b.description, b.title as board_name, b.id as board_id, 0.0 as score",
+        source: concat!(
+            "FROM kanban_boards b WHERE b.archived_at IS NULL AND (b.title LIKE ? OR b.description LIKE ?) AND ",
+            board_workspace_active_sql!()
+        ),
+        order_by: "b.title ASC",
+    },
+    SearchSection {
+        item_type: "card",
+        columns: "c.id, c.title, c.description, b.title as board_name, b.id as board_id, 0.0 as score",
+        source: concat!(
+            "FROM kanban_cards c JOIN kanban_columns col ON col.id = c.column_id JOIN kanban_boards b ON b.id = col.board_id WHERE c.archived_at IS NULL AND (c.title LIKE ? OR c.description LIKE ?) AND ",
+            board_workspace_active_sql!()
+        ),
+        order_by: "c.updated_at DESC",
+    },
+    SearchSection {
+        item_type: "note",
+        columns: "n.id, n.title, n.content as description, b.title as board_name, b.id as board_id, 0.0 as score",
+        source: concat!(
+            "FROM notes n JOIN kanban_boards b ON b.id = n.board_id WHERE n.archived_at IS NULL AND (n.title LIKE ? OR n.content LIKE ?) AND ",
+            board_workspace_active_sql!()
+        ),
+        order_by: "n.updated_at DESC",
+    },
+];
+
+/// Results are ordered boards, then cards, then notes; `offset` and `limit`
+/// page through that combined list.
+#[tauri::command]
+async fn global_search(
+    pool: State<'_, DbPool>,
+    query: String,
+    offset: Option<i64>,
+    limit: Option<i64>,
+) -> Result<SearchResults, String> {
+    let offset = offset.unwrap_or(0).max(0);
+    let limit = limit
+        .unwrap_or(DEFAULT_SEARCH_LIMIT)
+        .clamp(0, MAX_SEARCH_LIMIT);
+    let fts_query = build_fts_query(&query);
+
+    if !fts_query.is_empty() && search_index_available(&pool).await {
+        let fts_results = search_sections(
+            &pool,
+            &FTS_SEARCH_SECTIONS,
+            &[fts_query.as_str()],
+            offset,
+            limit,
+        )
+        .await;
+        match fts_results {
+            Ok(results) => return Ok(results),
+            Err(e) => log::warn!("FTS search failed, falling back to LIKE: {e}"),
+        }
+    }
+
+    let search_term = format!("%{}%", query.trim());
+    search_sections(
+        &pool,
+        &LIKE_SEARCH_SECTIONS,
+        &[search_term.as_str(), search_term.as_str()],
+        offset,
+        limit,
+    )
+    .await
+    .map_err(|e| format!("Failed to search: {e}"))
+}
+
+async fn search_sections(
+    pool: &DbPool,
+    sections: &[SearchSection; 3],
+    binds: &[&str],
+    offset: i64,
+    limit: i64,
+) -> Result<SearchResults, sqlx::Error> {
+    let mut totals = [0_i64; 3];
+    for (section, total) in sections.iter().zip(totals.iter_mut()) {
+        let sql = format!("SELECT COUNT(*) {}", section.source);
+        let mut count_query = sqlx::query_scalar::<_, i64>(&sql);
+        for value in binds {
+            count_query = count_query.bind(*value);
+        }
+        *total = count_query.fetch_one(pool).await?;
+    }
+
+    let mut results = Vec::new();
+    let mut preceding = 0;
+    for (section, total) in sections.iter().zip(totals) {
+        // Clip the combined window to the slice of it that falls in this section.
+        let section_start = (offset - preceding).clamp(0, total);
+        let section_end = (offset + limit - preceding).clamp(0, total);
+        preceding += total;
+
+        if section_end <= section_start {
+            continue;
+        }
+
+        let sql = format!(
+            "SELECT {} {} ORDER BY {} LIMIT ? OFFSET ?",
+            section.columns, section.source, section.order_by
+        );
+        let mut page_query = sqlx::query(&sql);
+        for value in binds {
+            page_query = page_query.bind(*value);
+        }
+        let rows = page_query
+            .bind(section_end - section_start)
+            .bind(section_start)
+            .fetch_all(pool)
+            .await?;
+
+        for row in rows {
+            results.push(SearchResult {
+                id: row.try_get("id")?,
+                title: row.try_get("title")?,
+                item_type: section.item_type.to_string(),
+                board_id: row.try_get("board_id")?,
+                board_name: row.try_get("board_name")?,
+                description: row.try_get("description")?,
+                rank: row.try_get("score")?,
+            });
+        }
+    }
+
+    Ok(SearchResults {
+        results,
+        total_boards: totals[0],
+        total_cards: totals[1],
+        total_notes: totals[2],
+    })
+}
+
+/// Counts rows `global_search` can return. `inSync` compares the FTS index with
+/// its source tables and is null when FTS5 is unavailable.
+#[tauri::command]
+async fn get_search_index_status(pool: State<'_, DbPool>) -> Result<Value, String> {
+    let counts_sql = format!(
+        r#"
+        SELECT
+            (SELECT COUNT(*) FROM kanban_boards b
+                WHERE b.archived_at IS NULL AND {BOARD_WORKSPACE_ACTIVE_SQL}),
+            (SELECT COUNT(*) FROM kanban_cards c
+                JOIN kanban_columns col ON col.id = c.column_id
+                JOIN kanban_boards b ON b.id = col.board_id
+                WHERE c.archived_at IS NULL AND {BOARD_WORKSPACE_ACTIVE_SQL}),
+            (SELECT COUNT(*) FROM notes n
+                JOIN kanban_boards b ON b.id = n.board_id
+                WHERE n.archived_at IS NULL AND {BOARD_WORKSPACE_ACTIVE_SQL})
+        "#
+    );
+    let (boards, cards, notes) = sqlx::query_as::<_, (i64, i64, i64)>(&counts_sql)
+        .fetch_one(&*pool)
+        .await
+        .map_err(|e| format!("Failed to count searchable items: {e}"))?;
+
+    let fts_enabled = search_index_available(&pool).await;
+    let in_sync = if fts_enabled {
+        let mismatched = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT
+                ((SELECT COUNT(*) FROM kanban_fts WHERE entity_type = 'board') <> (SELECT COUNT(*) FROM kanban_boards))
+                + ((SELECT COUNT(*) FROM kanban_fts WHERE entity_type = 'card') <> (SELECT COUNT(*) FROM kanban_cards))
+                + ((SELECT COUNT(*) FROM kanban_fts WHERE entity_type = 'note') <> (SELECT COUNT(*) FROM notes))
+            "#,
+        )
+        .fetch_one(&*pool)
+        .await
+        .map_err(|e| format!("Failed to inspect search index: {e}"))?;
+        json!(mismatched == 0)
+    } else {
+        Value::Null
+    };
+
+    Ok(json!({
+        "boards": boards,
+        "cards": cards,
+        "notes": notes,
+        "ftsEnabled": fts_enabled,
+        "inSync": in_sync,
+    }))
+}
+
+#[tauri::command]
+async fn set_workspace_icon_path<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    workspace_id: String,
+    icon_path: String,
+) -> Result<Value, String> {
+    let workspace_id = workspace_id.trim();
+    if workspace_id.is_empty() {
+        return Err("Identificador do workspace inválido.".to_string());
+    }
+
+    let icon_path = icon_path.trim();
+    if icon_path.is_empty() {
+        return Err("Caminho do ícone inválido.".to_string());
+    }
+
+    // Get the existing icon to clean it up
+    let existing_icon: Option<String> =
+        sqlx::query_scalar("SELECT icon_path FROM workspaces WHERE id = ?")
+            .bind(workspace_id)
+            .fetch_optional(&*pool)
+            .await
+            .map_err(|e| format!("Falha ao carregar workspace: {e}"))?
+            .flatten();
+
+    // Update the icon_path in the database
+    let result = sqlx::query(
+        "UPDATE workspaces SET icon_path = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
+    )
+    .bind(icon_path)
+    .bind(workspace_id)
+    .execute(&*pool)
+    .await
+    .map_err(|e| {
+        log::error!("Failed to update workspace icon path for {workspace_id}: {e}");
+        e.to_string()
+    })?;
+
+    if result.rows_affected() == 0 {
+        return Err("Workspace não encontrado.".to_string());
+    }
+
+    // Clean up the old icon file if it exists and is different
+    if let Some(previous) = existing_icon
+        && previous != icon_path
+    {
+        let _ = remove_workspace_icon_file(&app, &previous);
+    }
+
+    // The new path is stored, so earlier crops for this workspace can go
+    if let Ok(app_data_dir) = app.path().app_data_dir() {
+        remove_stray_workspace_icons(
+            &app_data_dir.join(WORKSPACE_ICON_DIR),
+            workspace_id,
+            Some(icon_path),
+            None,
+        );
+    }
+
+    // Return the updated workspace
+    sqlx::query("SELECT id, name, color, icon_path, position, created_at, updated_at, archived_at FROM workspaces WHERE id = ?")
+        .bind(workspace_id)
+        .try_map(map_workspace_row)
+        .fetch_one(&*pool)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to load workspace after icon path update {workspace_id}: {e}");
+            e.to_string()
+        })
+}
+
+#[cfg_attr(mobile, tauri::mobile_entry_point)]
+pub fn run() {
+    tauri::Builder::default()
+        .plugin(tauri_plugin_updater::Builder::new().build())
+        .plugin(tauri_plugin_process::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(
+            tauri_plugin_log::Builder::new()
+                // Use Debug level in development, Info in production
+                .level(if cfg!(debug_assertions) {
+                    log::LevelFilter::Debug
+                } else {
+                    log::LevelFilter::Info
+                })
+                .targets([
+                    // Always log to stdout for development
+                    tauri_plugin_log::Target::new(tauri_plugin_log::TargetKind::Stdout),
+                    // Log to webview console for development
+                    tauri_plugin_log::Target::new(tauri_plugin_log::TargetKind::Webview),
+                    // Log to system logs on macOS (appears in Console.app)
+                    #[cfg(target_os = "macos")]
+                    tauri_plugin_log::Target::new(tauri_plugin_log::TargetKind::LogDir {
+                        file_name: None,
+                    }),
+                ])
+                .build(),
+        )
+        .plugin(tauri_plugin_fs::init())
+        .plugin(tauri_plugin_persisted_scope::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(tauri_plugin_opener::init())
+        .setup(|app| {
+            log::info!("🚀 Application starting up");
+            log::debug!(
+                "App handle initialized for package: {}",
+                app.package_info().name
+            );
+
+            let handle = app.handle();
+
+            let pool =
+                tauri::async_runtime::block_on(establish_pool(handle)).map_err(|e| anyhow!(e))?;
+
+            tauri::async_runtime::block_on(initialize_schema(&pool)).map_err(|e| anyhow!(e))?;
+
+            app.manage(pool);
+
+            // Set up native menu system
+            if let Err(e) = create_app_menu(app) {
+                log::error!("Failed to create app menu: {e}");
+                return Err(e);
+            }
+
+            // Set up menu event handlers
+            app.on_menu_event(move |app, event| {
+                log::debug!("Menu event received: {:?}", event.id());
+
+                match event.id().as_ref() {
+                    "about" => {
+                        log::info!("About menu item clicked");
+                        // Emit event to React for handling
+                        match app.emit("menu-about", ()) {
+                            Ok(_) => log::debug!("Successfully emitted menu-about event"),
+                            Err(e) => log::error!("Failed to emit menu-about event: {e}"),
+                        }
+                    }
+                    "check-updates" => {
+                        log::info!("Check for Updates menu item clicked");
+                        // Emit event to React for handling
+                        match app.emit("menu-check-updates", ()) {
+                            Ok(_) => log::debug!("Successfully emitted menu-check-updates event"),
+                            Err(e) => log::error!("Failed to emit menu-check-updates event: {e}"),
+                        }
+                    }
+                    "preferences" => {
+                        log::info!("Preferences menu item clicked");
+                        // Emit event to React for handling
+                        match app.emit("menu-preferences", ()) {
+                            Ok(_) => log::debug!("Successfully emitted menu-preferences event"),
+                            Err(e) => log::error!("Failed to emit menu-preferences event: {e}"),
+                        }
+                    }
+                    "toggle-left-sidebar" => {
+                        log::info!("Toggle Left Sidebar menu item clicked");
+                        // Emit event to React for handling
+                        match app.emit("menu-toggle-left-sidebar", ()) {
+                            Ok(_) => {
+                                log::debug!("Successfully emitted menu-toggle-left-sidebar event")
+                            }
+                            Err(e) => {
+                                log::error!("Failed to emit menu-toggle-left-sidebar event: {e}")
+                            }
+                        }
+                    }
+                    "toggle-right-sidebar" => {
+                        log::info!("Toggle Right Sidebar menu item clicked");
+                        // Emit event to React for handling
+                        match app.emit("menu-toggle-right-sidebar", ()) {
+                            Ok(_) => {
+                                log::debug!("Successfully emitted menu-toggle-right-sidebar event")
+                            }
+                            Err(e) => {
+                                log::error!("Failed to emit menu-toggle-right-sidebar event: {e}")
+                            }
+                        }
+                    }
+                    _ => {
+                        log::debug!("Unhandled menu event: {:?}", event.id());
+                    }
+                }
+            });
+
+            // Example of different log levels
+            log::trace!("This is a trace message (most verbose)");
+            log::debug!("This is a debug message (development only)");
+            log::info!("This is an info message (production)");
+            log::warn!("This is a warning message");
+            // log::error!("This is an error message");
+
+            Ok(())
+        })
+        .invoke_handler(tauri::generate_handler![
+            greet,
+            get_version_info,
+            load_preferences,
+            save_preferences,
+            touch_last_board,
+            set_done_column_keywords,
+            set_window_transparency,
+            load_shortcuts,
+            save_shortcuts,
+            send_native_notification,
+            save_emergency_data,
+            load_emergency_data,
+            backup_preferences,
+            restore_preferences,
+            cleanup_old_recovery_files,
+            load_workspaces,
+            create_workspace,
+            update_workspace,
+            delete_workspace,
+            archive_workspace,
+            unarchive_workspace,
+            reorder_workspaces,
+            merge_workspaces,
+            update_workspace_icon,
+            remove_workspace_icon,
+            verify_workspace_icons,
+            consolidate_workspace_icons,
+            get_workspace_storage,
+            save_cropped_workspace_icon,
+            set_workspace_icon_path,
+            get_workspace_icon_url,
+            get_icon_options,
+            load_boards,
+            load_boards_for_workspace,
+            get_board,
+            load_boards_with_stats,
+            get_last_active_board,
+            create_board,
+            duplicate_board,
+            rename_board,
+            update_board_icon,
+            toggle_board_favorite,
+            archive_board,
+            unarchive_board,
+            update_board_workspace,
+            delete_board,
+            load_columns,
+            get_column,
+            count_column_cards,
+            get_next_position,
+            create_column,
+            update_column,
+            set_column_wip_mode,
+            set_column_kind,
+            set_default_intake_column,
+            delete_column,
+            move_column,
+            reorder_columns,
+            load_cards,
+            get_card,
+            find_orphaned_cards,
+            reassign_card_column,
+            find_invalid_priorities,
+            normalize_card_priorities,
+            find_duplicate_card_titles,
+            set_card_column_by_title,
+            load_tags,
+            get_tag_cloud,
+            create_tag,
+            create_tags,
+            update_tag,
+            delete_tag,
+            reorder_tags,
+            merge_tags,
+            add_tag_to_cards,
+            remove_tag_from_cards,
+            set_card_tags,
+            create_subtask,
+            create_subtasks,
+            update_subtask,
+            reorder_subtask,
+            set_all_subtasks_completed,
+            delete_subtask,
+            create_card,
+            quick_add_card,
+            insert_cards_at,
+            copy_card,
+            save_card,
+            delete_card,
+            archive_card,
+            unarchive_card,
+            toggle_card_archived,
+            unarchive_all_cards,
+            archive_cards_before,
+            clear_cards_due_date,
+            update_card,
+            set_reminder_before_due,
+            set_card_due_date,
+            move_card,
+            drag_move_card,
+            move_cards_bulk,
+            find_position_anomalies,
+            upload_image,
+            link_external_attachment,
+            remove_image,
+            get_attachment_url,
+            get_thumbnail_url,
+            open_attachment,
+            list_card_attachments,
+            list_board_attachments,
+            restore_attachment_version,
+            delete_attachment_version,
+            prune_board_attachments,
+            get_storage_stats,
+            clear_attachments,
+            cleanup_orphaned_attachments,
+            optimize_database,
+            reset_application_data,
+            import_application_data,
+            export_application_data,
+            load_notes,
+            create_note,
+            update_note,
+            set_note_tags,
+            delete_note,
+            archive_note,
+            get_task_statistics,
+            get_overdue_by_priority,
+            get_sidebar_badges,
+            get_recent_activity,
+            get_card_activity,
+            get_activity_for_entity,
+            get_favorite_boards,
+            refresh_board_stats,
+            repair_board_positions,
+            get_cached_board_stats,
+            get_upcoming_deadlines,
+            get_home_dashboard,
+            global_search,
+            export_board_html,
+            export_board_markdown,
+            export_cards_csv,
+            render_markdown,
+            export_board_to_file,
+            export_board,
+            validate_import,
+            import_board,
+            get_search_index_status,
+            get_board_changes_since,
+            get_deletions_since
+        ])
+        .run(tauri::generate_context!())
+        .expect("error while running tauri application");
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UploadImageResponse {
+    success: bool,
+    file_path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    attachment: Option<Value>,
+    error: Option<String>,
+    /// Set when the card already holds a file with the same checksum; `attachment`
+    /// then describes the existing copy and nothing new is stored.
+    duplicate: bool,
+}
+
+fn copy_workspace_icon<R: Runtime>(
+    app: &AppHandle<R>,
+    workspace_id: &str,
+    file_path: &str,
+) -> Result<String, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data directory: {e}"))?;
+
+    let icons_dir = app_data_dir.join(WORKSPACE_ICON_DIR);
+    fs::create_dir_all(&icons_dir)
+        .map_err(|e| format!("Failed to create workspace icon directory: {e}"))?;
+
+    let source_path = PathBuf::from(file_path);
+    if !source_path.exists() {
+        return Err("Arquivo selecionado não existe.".to_string());
+    }
+
+    let extension = source_path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    let mime_type = mime_guess::from_path(&source_path).first_or_octet_stream();
+    let is_image_by_mime = mime_type.essence_str().starts_with("image/");
+    let is_image_by_extension = matches!(
+        extension.as_str(),
+        "jpg" | "jpeg" | "png" | "gif" | "webp" | "svg" | "bmp" | "ico" | "tiff" | "tif"
+    );
+
+    if !is_image_by_mime && !is_image_by_extension {
+        return Err(format!(
+            "Arquivo selecionado não é uma imagem válida. MIME detectado: {}",
+            mime_type
+        ));
+    }
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| format!("System time error: {e}"))?
+        .as_secs();
+
+    let filename = if extension.is_empty() {
+        format!("{workspace_id}_{timestamp}")
+    } else {
+        format!("{workspace_id}_{timestamp}.{extension}")
+    };
+
+    let destination_path = icons_dir.join(&filename);
+    fs::copy(&source_path, &destination_path)
+        .map_err(|e| format!("Falha ao copiar arquivo de ícone: {e}"))?;
+
+    Ok(format!("{WORKSPACE_ICON_DIR}/{filename}"))
+}
+
+fn remove_workspace_icon_file<R: Runtime>(
+    app: &AppHandle<R>,
+    relative_path: &str,
+) -> Result<(), String> {
+    if relative_path.trim().is_empty() {
+        return Ok(());
+    }
+
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data directory: {e}"))?;
+
+    let full_path = app_data_dir.join(relative_path);
+    if full_path.exists() {
+        fs::remove_file(&full_path)
+            .map_err(|e| format!("Falha ao remover arquivo de ícone antigo: {e}"))?;
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+async fn save_cropped_workspace_icon<R: Runtime>(
+    app: AppHandle<R>,
+    workspace_id: String,
+    image_data: Vec<u8>,
+) -> Result<String, String> {
+    let workspace_id = workspace_id.trim();
+    if workspace_id.is_empty() {
+        return Err("Invalid workspace ID".to_string());
+    }
+
+    if image_data.is_empty() {
+        return Err("No image data provided".to_string());
+    }
+
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data directory: {e}"))?;
+
+    let workspace_icon_dir = app_data_dir.join(WORKSPACE_ICON_DIR);
+    fs::create_dir_all(&workspace_icon_dir)
+        .map_err(|e| format!("Failed to create workspace icon directory: {e}"))?;
+
+    // Generate a unique filename for the cropped image
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::from_secs(0))
+        .as_millis();
+    let filename = format!("{}_cropped_{}.png", workspace_id, timestamp);
+    let full_path = workspace_icon_dir.join(&filename);
+
+    // Write the image data to file
+    fs::write(&full_path, &image_data)
+        .map_err(|e| format!("Failed to write cropped image file: {e}"))?;
+
+    // Return the relative path
+    let relative_path = format!("{}/{}", WORKSPACE_ICON_DIR, filename);
+    Ok(relative_path)
+}
+
+#[tauri::command]
+async fn get_workspace_icon_url<R: Runtime>(
+    app: AppHandle<R>,
+    relative_path: String,
+) -> Result<String, String> {
+    if relative_path.trim().is_empty() {
+        return Err("Invalid relative path".to_string());
+    }
+
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data directory: {e}"))?;
+
+    let full_path = app_data_dir.join(&relative_path);
+
+    if !full_path.exists() {
+        return Err(format!("Workspace icon file not found: {}", relative_path));
+    }
+
+    // Read the file and convert to base64 data URL
+    let file_bytes =
+        fs::read(&full_path).map_err(|e| format!("Failed to read workspace icon file: {e}"))?;
+
+    // Determine MIME type based on file extension
+    let mime_type = match full_path.extension().and_then(|ext| ext.to_str()) {
+        Some("png") => "image/png",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        Some("svg") => "image/svg+xml",
+        Some("bmp") => "image/bmp",
+        _ => "image/png", // Default fallback
+    };
+
+    // Encode as base64 data URL
+    let base64_data = general_purpose::STANDARD.encode(&file_bytes);
+    Ok(format!("data:{};base64,{}", mime_type, base64_data))
+}
+
+const THUMBNAIL_MAX_SIZE: u32 = 256;
+
+/// Writes `<name>.thumb.png` next to `source` and returns its path relative to
+/// `app_data_dir`. Images already within the bounds are stored unscaled.
+fn generate_thumbnail(app_data_dir: &Path, source: &Path) -> Result<String, String> {
+    let image = image::open(source).map_err(|e| format!("Failed to decode image: {e}"))?;
+
+    let thumbnail = if image.width() > THUMBNAIL_MAX_SIZE || image.height() > THUMBNAIL_MAX_SIZE {
+        image.thumbnail(THUMBNAIL_MAX_SIZE, THUMBNAIL_MAX_SIZE)
+    } else {
+        image
+    };
+
+    let file_name = source
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| "Invalid attachment filename".to_string())?;
+    let thumbnail_path = source.with_file_name(format!("{file_name}.thumb.png"));
+
+    thumbnail
+        .save_with_format(&thumbnail_path, image::ImageFormat::Png)
+        .map_err(|e| format!("Failed to write thumbnail: {e}"))?;
+
+    Ok(thumbnail_path
+        .strip_prefix(app_data_dir)
+        .map_err(|e| format!("Failed to compute relative thumbnail path: {e}"))?
+        .iter()
+        .map(|component| component.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/"))
+}
+
+#[tauri::command]
+async fn upload_image<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    card_id: String,
+    board_id: String,
+    file_path: String,
+) -> Result<UploadImageResponse, String> {
+    log::debug!(
+        "Starting upload image for card: {}, board: {}, file: {}",
+        card_id,
+        board_id,
+        file_path
+    );
+
+    let app_data_dir = app.path().app_data_dir().map_err(|e| {
+        log::error!("Failed to resolve app data directory: {e}");
+        format!("Failed to resolve app data directory: {e}")
+    })?;
+
+    let attachments_dir = app_data_dir.join("attachments");
+    log::debug!("Creating attachments directory: {attachments_dir:?}");
+
+    fs::create_dir_all(&attachments_dir).map_err(|e| {
+        log::error!("Failed to create attachments directory: {e}");
+        format!("Failed to create attachments directory: {e}")
+    })?;
+
+    let card_attachments_dir = attachments_dir.join(&card_id);
+    log::debug!(
+        "Ensuring card attachment directory exists: {:?}",
+        card_attachments_dir
+    );
+
+    fs::create_dir_all(&card_attachments_dir).map_err(|e| {
+        log::error!("Failed to create card attachment directory: {e}");
+        format!("Failed to create card attachment directory: {e}")
+    })?;
+
+    let source_path = PathBuf::from(&file_path);
+    log::debug!("Checking source path: {source_path:?}");
+
+    if !source_path.exists() {
+        log::warn!("Source file does not exist");
+        return Ok(UploadImageResponse {
+            success: false,
+            file_path: String::new(),
+            attachment: None,
+            error: Some("Source file does not exist".to_string()),
+            duplicate: false,
+        });
+    }
+
+    let file_extension = source_path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("");
+
+    log::debug!("File extension: {file_extension}");
+
+    let ext_lower = file_extension.to_lowercase();
+    let image_extensions = [
+        "jpg", "jpeg", "png", "gif", "webp", "svg", "bmp", "ico", "tiff", "tif",
+    ];
+    let document_extensions = [
+        "pdf", "doc", "docx", "xls", "xlsx", "ppt", "pptx", "txt", "csv", "md", "rtf", "zip",
+        "rar", "7z", "tar", "json",
+    ];
+
+    let is_image_by_extension = image_extensions.contains(&ext_lower.as_str());
+    let is_document_by_extension = document_extensions.contains(&ext_lower.as_str());
+
+    if !is_image_by_extension && !is_document_by_extension {
+        log::warn!(
+            "Unsupported attachment extension received: {}",
+            file_extension
+        );
+        return Ok(UploadImageResponse {
+            success: false,
+            file_path: String::new(),
+            attachment: None,
+            error: Some(format!("Unsupported attachment type: .{}", file_extension)),
+            duplicate: false,
+        });
+    }
+
+    let preferences = load_preferences(app.clone()).await.unwrap_or_else(|e| {
+        log::warn!("Using default attachment limits: {e}");
+        AppPreferences::default()
+    });
+
+    let is_blocked_extension = preferences
+        .blocked_extensions
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .any(|blocked| blocked.trim().trim_start_matches('.').to_lowercase() == ext_lower);
+
+    if is_blocked_extension {
+        log::warn!("Blocked attachment extension received: {file_extension}");
+        return Ok(UploadImageResponse {
+            success: false,
+            file_path: String::new(),
+            attachment: None,
+            error: Some(format!("Attachment type .{} is blocked", file_extension)),
+            duplicate: false,
+        });
+    }
+
+    let max_attachment_bytes = preferences
+        .max_attachment_bytes
+        .unwrap_or(DEFAULT_MAX_ATTACHMENT_BYTES);
+    let source_size = fs::metadata(&source_path)
+        .map_err(|e| {
+            log::error!("Failed to read source file metadata: {e}");
+            format!("Failed to read source file metadata: {e}")
+        })?
+        .len();
+
+    if source_size > max_attachment_bytes {
+        log::warn!(
+            "Attachment of {} bytes exceeds the {} byte limit",
+            source_size,
+            max_attachment_bytes
+        );
+        return Ok(UploadImageResponse {
+            success: false,
+            file_path: String::new(),
+            attachment: None,
+            error: Some(format!(
+                "File is too large ({} bytes, limit is {} bytes)",
+                source_size, max_attachment_bytes
+            )),
+            duplicate: false,
+        });
+    }
+
+    let mime_type = mime_guess::from_path(&source_path).first_or_octet_stream();
+    log::debug!("Detected MIME type: {mime_type}");
+    log::debug!("MIME type string: {}", mime_type.as_ref());
+
+    if !is_image_by_extension && mime_type.type_().as_str().starts_with("image/") {
+        log::debug!(
+            "Attachment extension {} detected as image MIME {}; treating as document",
+            file_extension,
+            mime_type
+        );
+    }
+
+    let original_name = source_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .map(|name| name.replace(['/', '\\'], "_"))
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| {
+            let fallback = format!("attachment.{}", file_extension);
+            log::debug!(
+                "Unable to determine original filename, falling back to {}",
+                fallback
+            );
+            fallback
+        });
+
+    let mut file_reader = fs::File::open(&source_path).map_err(|e| {
+        log::error!("Failed to open file for checksum: {e}");
+        format!("Failed to open file for checksum: {e}")
+    })?;
+
+    let mut hasher = Sha256::new();
+    use std::io::Read;
+    let mut buffer = [0u8; 8192];
+    loop {
+        let bytes_read = file_reader
+            .read(&mut buffer)
+            .map_err(|e| format!("Failed to read file for checksum: {e}"))?;
+        if bytes_read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..bytes_read]);
+    }
+
+    let checksum = format!("{:x}", hasher.finalize());
+
+    let duplicate_attachment = sqlx::query(
+        "SELECT id, card_id, board_id, version, filename, original_name, mime_type, size_bytes, checksum, storage_path, thumbnail_path, is_external, created_at, updated_at FROM kanban_attachments att WHERE card_id = ? AND board_id = ? AND is_external = 0 AND checksum = ? AND version = (SELECT MAX(latest.version) FROM kanban_attachments latest WHERE latest.id = att.id) ORDER BY created_at DESC LIMIT 1",
+    )
+    .bind(&card_id)
+    .bind(&board_id)
+    .bind(&checksum)
+    .map(|row: SqliteRow| AttachmentRecord::from_row(row))
+    .fetch_optional(&*pool)
+    .await
+    .map_err(|e| format!("Failed to look up duplicate attachment: {e}"))?
+    .transpose()
+    .map_err(|e| format!("Failed to map attachment row: {e}"))?;
+
+    if let Some(existing) = duplicate_attachment {
+        log::debug!(
+            "Identical file already attached to card {card_id}: {}",
+            existing.storage_path
+        );
+        return Ok(UploadImageResponse {
+            success: true,
+            file_path: existing.storage_path.clone(),
+            attachment: Some(existing.into_json()),
+            error: None,
+            duplicate: true,
+        });
+    }
+
+    let mut destination_path = card_attachments_dir.join(&original_name);
+
+    if destination_path.exists() {
+        log::debug!(
+            "Attachment with same name exists, generating unique filename for {:?}",
+            destination_path
+        );
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|e| {
+                log::error!("System time error: {e}");
+                format!("System time error: {e}")
+            })?
+            .as_secs();
+
+        let (base, ext) = match destination_path.file_stem().and_then(|s| s.to_str()) {
+            Some(stem) => (
+                stem.to_string(),
+                source_path
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .map(|e| e.to_string()),
+            ),
+            None => (
+                "attachment".to_string(),
+                source_path
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .map(|e| e.to_string()),
+            ),
+        };
+
+        let mut counter = 1u32;
+        loop {
+            let candidate_name = if let Some(ref ext) = ext {
+                format!("{}_{timestamp}_{counter}.{}", base, ext)
+            } else {
+                format!("{}_{timestamp}_{counter}", base)
+            };
+
+            let candidate_path = card_attachments_dir.join(&candidate_name);
+            if !candidate_path.exists() {
+                destination_path = candidate_path;
+                break;
+            }
+
+            counter += 1;
+        }
+    }
+
+    log::debug!("Copying from {:?} to {:?}", source_path, destination_path);
+
+    fs::copy(&source_path, &destination_path).map_err(|e| {
+        log::error!("Failed to copy file: {e}");
+        format!("Failed to copy file: {e}")
+    })?;
+
+    let relative_path = destination_path
+        .strip_prefix(&app_data_dir)
+        .map_err(|e| {
+            log::error!(
+                "Failed to compute relative path for {:?}: {}",
+                destination_path,
+                e
+            );
+            format!("Failed to compute relative attachment path: {e}")
+        })?
+        .iter()
+        .map(|component| component.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+
+    log::debug!("Generated relative path: {relative_path}");
+
+    let file_metadata = fs::metadata(&destination_path).map_err(|e| {
+        log::error!("Failed to read file metadata: {e}");
+        format!("Failed to read file metadata: {e}")
+    })?;
+
+    let file_size: i64 = file_metadata.len().try_into().unwrap_or(i64::MAX);
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let mime_string = mime_type.essence_str().to_string();
+
+    let thumbnail_path = if mime_string.starts_with("image/") {
+        match generate_thumbnail(&app_data_dir, &destination_path) {
+            Ok(path) => Some(path),
+            Err(e) => {
+                log::debug!("Skipping thumbnail generation: {e}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let mut tx = pool.begin().await.map_err(|e| {
+        log::error!("Failed to begin transaction: {e}");
+        format!("Failed to begin transaction: {e}")
+    })?;
+
+    // Identical content was handled above; only a matching name adds a new version
+    let existing_attachment_id: Option<String> = sqlx::query_scalar(
+        "SELECT id FROM kanban_attachments WHERE card_id = ? AND board_id = ? AND is_external = 0 AND original_name = ? ORDER BY created_at DESC, version DESC LIMIT 1",
+    )
+    .bind(&card_id)
+    .bind(&board_id)
+    .bind(&original_name)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| {
+        log::error!("Failed to look up existing attachment: {e}");
+        format!("Failed to look up existing attachment: {e}")
+    })?;
+
+    let (attachment_id, version, replaced_path) = match existing_attachment_id {
+        Some(existing_id) => {
+            let (latest_version, latest_path): (i64, String) = sqlx::query_as(
+                "SELECT version, storage_path FROM kanban_attachments WHERE id = ? ORDER BY version DESC LIMIT 1",
+            )
+            .bind(&existing_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to load attachment versions: {e}"))?;
+            (existing_id, latest_version + 1, Some(latest_path))
+        }
+        None => (Uuid::new_v4().to_string(), 1i64, None),
+    };
+
+    // Maintain legacy attachment JSON for existing clients
+    let existing_attachments: Option<String> =
+        sqlx::query_scalar("SELECT attachments FROM kanban_cards WHERE id = ? AND board_id = ?")
+            .bind(&card_id)
+            .bind(&board_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to fetch legacy attachments: {e}");
+                format!("Failed to fetch legacy attachments: {e}")
+            })?;
+
+    let mut attachments_vec: Vec<String> = existing_attachments
+        .as_deref()
+        .and_then(|json_str| serde_json::from_str(json_str).ok())
+        .unwrap_or_default();
+    if let Some(replaced_path) = replaced_path.as_ref() {
+        attachments_vec.retain(|path| path != replaced_path);
+    }
+    attachments_vec.push(relative_path.clone());
+
+    let attachments_json = serde_json::to_string(&attachments_vec).map_err(|e| {
+        log::error!("Failed to serialize attachments JSON: {e}");
+        format!("Failed to serialize attachments JSON: {e}")
+    })?;
+
+    sqlx::query(
+        "UPDATE kanban_cards SET attachments = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ? AND board_id = ?",
+    )
+    .bind(&attachments_json)
+    .bind(&card_id)
+    .bind(&board_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| {
+        log::error!("Failed to update legacy attachments column: {e}");
+        format!("Failed to update legacy attachments column: {e}")
+    })?;
+
+    sqlx::query(
+        "INSERT INTO kanban_attachments (
+            id, card_id, board_id, version, filename, original_name, mime_type, size_bytes,
+            checksum, storage_path, thumbnail_path, created_at, updated_at
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&attachment_id)
+    .bind(&card_id)
+    .bind(&board_id)
+    .bind(version)
+    .bind(&original_name)
+    .bind(&original_name)
+    .bind(&mime_string)
+    .bind(file_size)
+    .bind(&checksum)
+    .bind(&relative_path)
+    .bind(&thumbnail_path)
+    .bind(&now)
+    .bind(&now)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| {
+        log::error!("Failed to insert attachment metadata: {e}");
+        format!("Failed to insert attachment metadata: {e}")
+    })?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {e}"))?;
+
+    log::info!("Attachment uploaded successfully: {relative_path}");
+
+    Ok(UploadImageResponse {
+        success: true,
+        file_path: relative_path.clone(),
+        attachment: Some(json!({
+            "id": attachment_id,
+            "boardId": board_id,
+            "cardId": card_id,
+            "version": version,
+            "filename": original_name.clone(),
+            "originalName": original_name,
+            "mimeType": mime_string,
+            "sizeBytes": file_size,
+            "checksum": checksum,
+            "storagePath": relative_path,
+            "thumbnailPath": thumbnail_path,
+            "isExternal": false,
+            "createdAt": now,
+            "updatedAt": now,
+        })),
+        error: None,
+        duplicate: false,
+    })
+}
+
+/// Links a file in place instead of copying it into app data. The row keeps the
+/// absolute path, so moving or deleting the original file breaks the attachment.
+#[tauri::command]
+async fn link_external_attachment(
+    pool: State<'_, DbPool>,
+    card_id: String,
+    board_id: String,
+    absolute_path: String,
+    label: String,
+) -> Result<Value, String> {
+    let source_path = PathBuf::from(absolute_path.trim());
+    if !source_path.is_absolute() {
+        return Err("External attachments require an absolute path".to_string());
+    }
+
+    let file_metadata =
+        fs::metadata(&source_path).map_err(|e| format!("External file is not accessible: {e}"))?;
+    if !file_metadata.is_file() {
+        return Err("External attachment path must point to a file".to_string());
+    }
+
+    let storage_path = source_path
+        .to_str()
+        .ok_or_else(|| "External attachment path is not valid UTF-8".to_string())?
+        .to_string();
+
+    let original_name = source_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| "attachment".to_string());
+
+    let label = label.trim();
+    let filename = if label.is_empty() {
+        original_name.clone()
+    } else {
+        validate_string_input(label, 200, "Attachment label")?;
+        label.to_string()
+    };
+
+    let card_board_id: Option<String> =
+        sqlx::query_scalar("SELECT board_id FROM kanban_cards WHERE id = ?")
+            .bind(&card_id)
+            .fetch_optional(&*pool)
+            .await
+            .map_err(|e| format!("Failed to load card: {e}"))?;
+
+    match card_board_id {
+        Some(id) if id == board_id => {}
+        Some(_) => return Err("Card does not belong to the given board".to_string()),
+        None => return Err("Card not found".to_string()),
+    }
+
+    log::warn!(
+        "Linking external attachment {storage_path}; moving or deleting the file will break the link"
+    );
+
+    let attachment_id = Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+    let mime_string = mime_guess::from_path(&source_path)
+        .first_or_octet_stream()
+        .essence_str()
+        .to_string();
+    let file_size: i64 = file_metadata.len().try_into().unwrap_or(i64::MAX);
+
+    sqlx::query(
+        "INSERT INTO kanban_attachments (
+            id, card_id, board_id, version, filename, original_name, mime_type, size_bytes,
+            checksum, storage_path, thumbnail_path, is_external, created_at, updated_at
+        ) VALUES (?, ?, ?, 1, ?, ?, ?, ?, NULL, ?, NULL, 1, ?, ?)",
+    )
+    .bind(&attachment_id)
+    .bind(&card_id)
+    .bind(&board_id)
+    .bind(&filename)
+    .bind(&original_name)
+    .bind(&mime_string)
+    .bind(file_size)
+    .bind(&storage_path)
+    .bind(&now)
+    .bind(&now)
+    .execute(&*pool)
+    .await
+    .map_err(|e| format!("Failed to insert attachment metadata: {e}"))?;
+
+    Ok(json!({
+        "id": attachment_id,
+        "boardId": board_id,
+        "cardId": card_id,
+        "version": 1,
+        "filename": filename,
+        "originalName": original_name,
+        "mimeType": mime_string,
+        "sizeBytes": file_size,
+        "checksum": Value::Null,
+        "storagePath": storage_path,
+        "thumbnailPath": Value::Null,
+        "isExternal": true,
+        "createdAt": now,
+        "updatedAt": now,
+    }))
+}
+
+#[tauri::command]
+async fn list_card_attachments(
+    pool: State<'_, DbPool>,
+    args: ListAttachmentsArgs,
+) -> Result<Value, String> {
+    let attachments = sqlx::query(
+        "SELECT id, card_id, board_id, version, filename, original_name, mime_type, size_bytes, checksum, storage_path, thumbnail_path, is_external, created_at, updated_at FROM kanban_attachments att WHERE board_id = ? AND card_id = ? ORDER BY (SELECT MAX(latest.created_at) FROM kanban_attachments latest WHERE latest.id = att.id) DESC, id ASC, version DESC",
+    )
+    .bind(&args.board_id)
+    .bind(&args.card_id)
+    .map(|row: SqliteRow| AttachmentRecord::from_row(row))
+    .fetch_all(&*pool)
+    .await
+    .map_err(|e| format!("Failed to load attachments: {e}"))?
+    .into_iter()
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(|e| format!("Failed to map attachment row: {e}"))?;
+
+    // Rows arrive newest version first, so the first row of each id is its current version
+    let mut grouped: Vec<(String, Vec<Value>)> = Vec::new();
+    for attachment in attachments {
+        let attachment_id = attachment.id.clone();
+        let json = attachment.into_json();
+        match grouped.iter_mut().find(|(id, _)| id == &attachment_id) {
+            Some((_, versions)) => versions.push(json),
+            None => grouped.push((attachment_id, vec![json])),
+        }
+    }
+
+    Ok(Value::Array(
+        grouped
+            .into_iter()
+            .map(|(_, versions)| {
+                let mut current = versions[0].clone();
+                current["versions"] = Value::Array(versions);
+                current
+            })
+            .collect(),
+    ))
+}
+
+/// Current version of every attachment on the board, newest first, for the media gallery.
+#[tauri::command]
+async fn list_board_attachments(
+    pool: State<'_, DbPool>,
+    board_id: String,
+) -> Result<Vec<Value>, String> {
+    let rows = sqlx::query(
+        "SELECT att.id, att.card_id, att.board_id, att.version, att.filename, att.original_name, att.mime_type, att.size_bytes, att.checksum, att.storage_path, att.thumbnail_path, att.is_external, att.created_at, att.updated_at, c.title AS card_title
+        FROM kanban_attachments att
+        JOIN kanban_cards c ON c.id = att.card_id
+        WHERE att.board_id = ?
+        AND att.version = (SELECT MAX(latest.version) FROM kanban_attachments latest WHERE latest.id = att.id)
+        ORDER BY att.created_at DESC, att.id ASC",
+    )
+    .bind(&board_id)
+    .fetch_all(&*pool)
+    .await
+    .map_err(|e| format!("Failed to load board attachments: {e}"))?;
+
+    rows.into_iter()
+        .map(|row| {
+            let card_title: String = row
+                .try_get("card_title")
+                .map_err(|e| format!("Failed to map attachment row: {e}"))?;
+            let attachment = AttachmentRecord::from_row(row)
+                .map_err(|e| format!("Failed to map attachment row: {e}"))?;
+            let is_image = attachment
+                .mime_type
+                .as_deref()
+                .is_some_and(|mime| mime.starts_with("image/"));
+
+            let mut json = attachment.into_json();
+            json["cardTitle"] = json!(card_title);
+            json["kind"] = json!(if is_image { "image" } else { "document" });
+            Ok(json)
+        })
+        .collect()
+}
+
+#[tauri::command]
+async fn remove_image<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    card_id: String,
+    board_id: String,
+    file_path: String,
+) -> Result<(), String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data directory: {e}"))?;
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to begin transaction: {e}"))?;
+
+    // Removing an attachment drops every stored version of it
+    let attachment_id: Option<String> = sqlx::query_scalar(
+        "SELECT id FROM kanban_attachments WHERE card_id = ? AND board_id = ? AND storage_path = ? ORDER BY version DESC LIMIT 1",
+    )
+    .bind(&card_id)
+    .bind(&board_id)
+    .bind(&file_path)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to look up attachment metadata: {e}"))?;
+
+    let removed_files: Vec<(String, bool, Option<String>)> = match attachment_id {
+        Some(attachment_id) => {
+            let rows: Vec<(String, bool, Option<String>)> = sqlx::query_as(
+                "SELECT storage_path, is_external, thumbnail_path FROM kanban_attachments WHERE id = ? AND card_id = ? AND board_id = ?",
+            )
+            .bind(&attachment_id)
+            .bind(&card_id)
+            .bind(&board_id)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to look up attachment versions: {e}"))?;
+
+            sqlx::query(
+                "DELETE FROM kanban_attachments WHERE id = ? AND card_id = ? AND board_id = ?",
+            )
+            .bind(&attachment_id)
+            .bind(&card_id)
+            .bind(&board_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to delete attachment metadata: {e}"))?;
+
+            rows
+        }
+        None => vec![(file_path.clone(), false, None)],
+    };
+
+    let existing_attachments: Option<String> =
+        sqlx::query_scalar("SELECT attachments FROM kanban_cards WHERE id = ? AND board_id = ?")
+            .bind(&card_id)
+            .bind(&board_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to fetch existing attachments: {e}"))?;
+
+    let mut attachments_vec: Vec<String> = existing_attachments
+        .as_deref()
+        .and_then(|json_str| serde_json::from_str(json_str).ok())
+        .unwrap_or_default();
+    attachments_vec.retain(|path| {
+        !removed_files
+            .iter()
+            .any(|(storage_path, _, _)| storage_path == path)
+    });
+
+    let attachments_json = serde_json::to_string(&attachments_vec)
+        .map_err(|e| format!("Failed to serialize attachments: {e}"))?;
+
+    sqlx::query(
+        "UPDATE kanban_cards SET attachments = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ? AND board_id = ?",
+    )
+    .bind(&attachments_json)
+    .bind(&card_id)
+    .bind(&board_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to update card attachments: {e}"))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {e}"))?;
+
+    for (storage_path, is_external, thumbnail_path) in removed_files {
+        // Linked files belong to the user and are never removed from disk
+        if is_external {
+            continue;
+        }
+
+        let remaining_references: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM kanban_attachments WHERE storage_path = ?")
+                .bind(&storage_path)
+                .fetch_one(&*pool)
+                .await
+                .map_err(|e| format!("Failed to check attachment references: {e}"))?;
+
+        if remaining_references > 0 {
+            continue;
+        }
+
+        let full_file_path = app_data_dir.join(&storage_path);
+        if full_file_path.exists()
+            && let Err(e) = fs::remove_file(&full_file_path)
+        {
+            eprintln!(
+                "Warning: Failed to delete file {}: {}",
+                full_file_path.display(),
+                e
+            );
+        }
+
+        if let Some(thumbnail_path) = thumbnail_path {
+            let full_thumbnail_path = app_data_dir.join(&thumbnail_path);
+            if full_thumbnail_path.exists()
+                && let Err(e) = fs::remove_file(&full_thumbnail_path)
+            {
+                log::error!(
+                    "Failed to delete thumbnail {}: {e}",
+                    full_thumbnail_path.display()
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_attachment_url<R: Runtime>(
+    app: AppHandle<R>,
+    file_path: String,
+) -> Result<String, String> {
+    // Read the image file and convert to base64
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data directory: {e}"))?;
+
+    let full_path = app_data_dir.join(&file_path);
+    if !full_path.exists() {
+        return Err(format!("File does not exist: {:?}", full_path));
+    }
+
+    let image_data = std::fs::read(&full_path).map_err(|e| format!("Failed to read file: {e}"))?;
+
+    // Determine MIME type from file extension
+    let extension = full_path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("");
+
+    let mime_type = match extension.to_lowercase().as_str() {
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "bmp" => "image/bmp",
+        "ico" => "image/x-icon",
+        _ => "image/jpeg",
+    };
+
+    let base64_data = general_purpose::STANDARD.encode(&image_data);
+    let data_url = format!("data:{};base64,{}", mime_type, base64_data);
+
+    Ok(data_url)
+}
+
+#[tauri::command]
+async fn get_thumbnail_url<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    file_path: String,
+) -> Result<String, String> {
+    let thumbnail_path = sqlx::query_scalar::<_, Option<String>>(
+        "SELECT thumbnail_path FROM kanban_attachments WHERE storage_path = ? AND thumbnail_path IS NOT NULL ORDER BY version DESC LIMIT 1",
+    )
+    .bind(&file_path)
+    .fetch_optional(&*pool)
+    .await
+    .map_err(|e| format!("Failed to look up thumbnail: {e}"))?
+    .flatten();
+
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data directory: {e}"))?;
+
+    if let Some(thumbnail_path) = thumbnail_path {
+        let full_path = app_data_dir.join(&thumbnail_path);
+        if full_path.exists() {
+            let thumbnail_data =
+                std::fs::read(&full_path).map_err(|e| format!("Failed to read thumbnail: {e}"))?;
+            let base64_data = general_purpose::STANDARD.encode(&thumbnail_data);
+            return Ok(format!("data:image/png;base64,{}", base64_data));
+        }
+    }
+
+    get_attachment_url(app, file_path).await
+}
+
+#[tauri::command]
+async fn restore_attachment_version(
+    pool: State<'_, DbPool>,
+    args: ManageAttachmentVersionArgs,
+) -> Result<Value, String> {
+    let ManageAttachmentVersionArgs {
+        board_id,
+        card_id,
+        attachment_id,
+        target_version,
+    } = args;
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to begin transaction: {e}"))?;
+
+    let version = match target_version {
+        Some(version) => version,
+        None => sqlx::query_scalar::<_, Option<i64>>(
+            "SELECT MAX(version) FROM kanban_attachments WHERE id = ? AND board_id = ? AND card_id = ?",
+        )
+        .bind(&attachment_id)
+        .bind(&board_id)
+        .bind(&card_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to load attachment versions: {e}"))?
+        .ok_or_else(|| "Attachment not found".to_string())?,
+    };
+
+    let mut attachment = sqlx::query(
+        "SELECT id, card_id, board_id, version, filename, original_name, mime_type, size_bytes, checksum, storage_path, thumbnail_path, is_external, created_at, updated_at FROM kanban_attachments WHERE id = ? AND board_id = ? AND card_id = ? AND version = ?",
+    )
+    .bind(&attachment_id)
+    .bind(&board_id)
+    .bind(&card_id)
+    .bind(version)
+    .map(|row: SqliteRow| AttachmentRecord::from_row(row))
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to load attachment version: {e}"))?
+    .ok_or_else(|| "Attachment version not found".to_string())?
+    .map_err(|e| format!("Failed to parse attachment row: {e}"))?;
+
+    let (latest_version, latest_path): (i64, String) = sqlx::query_as(
+        "SELECT version, storage_path FROM kanban_attachments WHERE id = ? AND board_id = ? AND card_id = ? ORDER BY version DESC LIMIT 1",
+    )
+    .bind(&attachment_id)
+    .bind(&board_id)
+    .bind(&card_id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to load attachment versions: {e}"))?;
+
+    // Restoring an older version re-publishes it as the newest one
+    if attachment.version < latest_version {
+        let now = chrono::Utc::now().to_rfc3339();
+        attachment.version = latest_version + 1;
+        attachment.created_at = now.clone();
+        attachment.updated_at = now;
+
+        sqlx::query(
+            "INSERT INTO kanban_attachments (
+                id, card_id, board_id, version, filename, original_name, mime_type, size_bytes,
+                checksum, storage_path, thumbnail_path, is_external, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&attachment.id)
+        .bind(&attachment.card_id)
+        .bind(&attachment.board_id)
+        .bind(attachment.version)
+        .bind(&attachment.filename)
+        .bind(&attachment.original_name)
+        .bind(&attachment.mime_type)
+        .bind(attachment.size_bytes)
+        .bind(&attachment.checksum)
+        .bind(&attachment.storage_path)
+        .bind(&attachment.thumbnail_path)
+        .bind(attachment.is_external)
+        .bind(&attachment.created_at)
+        .bind(&attachment.updated_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to restore attachment version: {e}"))?;
+    }
+
+    let attachment_clone = attachment.clone();
+
+    let existing_attachments: Option<String> =
+        sqlx::query_scalar("SELECT attachments FROM kanban_cards WHERE id = ? AND board_id = ?")
+            .bind(&card_id)
+            .bind(&board_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to fetch existing attachments: {e}"))?;
+
+    let mut attachments_vec: Vec<String> = existing_attachments
+        .as_deref()
+        .and_then(|json_str| serde_json::from_str(json_str).ok())
+        .unwrap_or_default();
+    if latest_path != attachment.storage_path {
+        attachments_vec.retain(|path| path != &latest_path);
+    }
+    if !attachments_vec.contains(&attachment.storage_path) {
+        attachments_vec.push(attachment.storage_path.clone());
+    }
+
+    let attachments_json = serde_json::to_string(&attachments_vec)
+        .map_err(|e| format!("Failed to serialize attachments: {e}"))?;
+
+    sqlx::query(
+        "UPDATE kanban_cards SET attachments = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ? AND board_id = ?",
+    )
+    .bind(&attachments_json)
+    .bind(&card_id)
+    .bind(&board_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to update card attachments: {e}"))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {e}"))?;
+
+    Ok(attachment_clone.into_json())
+}
+
+#[tauri::command]
+async fn delete_attachment_version<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    args: ManageAttachmentVersionArgs,
+) -> Result<(), String> {
+    let ManageAttachmentVersionArgs {
+        board_id,
+        card_id,
+        attachment_id,
+        target_version,
+    } = args;
+
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data directory: {e}"))?;
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to begin transaction: {e}"))?;
+
+    let storage_paths_to_check: Vec<(String, bool, Option<String>)> = if let Some(version) =
+        target_version
+    {
+        sqlx::query_as(
+            "SELECT storage_path, is_external, thumbnail_path FROM kanban_attachments WHERE id = ? AND board_id = ? AND card_id = ? AND version = ?",
+        )
+        .bind(&attachment_id)
+        .bind(&board_id)
+        .bind(&card_id)
+        .bind(version)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to fetch attachment storage path: {e}"))?
+    } else {
+        sqlx::query_as(
+            "SELECT storage_path, is_external, thumbnail_path FROM kanban_attachments WHERE id = ? AND board_id = ? AND card_id = ?",
+        )
+        .bind(&attachment_id)
+        .bind(&board_id)
+        .bind(&card_id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to fetch attachment storage paths: {e}"))?
+    };
+
+    if storage_paths_to_check.is_empty() {
+        return Err("Attachment version not found".to_string());
+    }
+
+    let delete_query = if let Some(version) = target_version {
+        sqlx::query(
+            "DELETE FROM kanban_attachments WHERE id = ? AND board_id = ? AND card_id = ? AND version = ?",
+        )
+        .bind(&attachment_id)
+        .bind(&board_id)
+        .bind(&card_id)
+        .bind(version)
+    } else {
+        sqlx::query("DELETE FROM kanban_attachments WHERE id = ? AND board_id = ? AND card_id = ?")
+            .bind(&attachment_id)
+            .bind(&board_id)
+            .bind(&card_id)
+    };
+
+    delete_query
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to delete attachment version: {e}"))?;
+
+    let remaining_storage_paths: Vec<String> = sqlx::query_scalar(
+        "SELECT DISTINCT storage_path FROM kanban_attachments WHERE card_id = ? AND board_id = ?",
+    )
+    .bind(&card_id)
+    .bind(&board_id)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to fetch remaining attachments: {e}"))?;
+
+    let attachments_json = serde_json::to_string(&remaining_storage_paths)
+        .map_err(|e| format!("Failed to serialize attachments: {e}"))?;
+
+    sqlx::query(
+        "UPDATE kanban_cards SET attachments = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ? AND board_id = ?",
+    )
+    .bind(&attachments_json)
+    .bind(&card_id)
+    .bind(&board_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to update card attachments: {e}"))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {e}"))?;
+
+    for (storage_path, is_external, thumbnail_path) in storage_paths_to_check {
+        if is_external {
+            continue;
+        }
+
+        let remaining: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM kanban_attachments WHERE storage_path = ?")
+                .bind(&storage_path)
+                .fetch_one(&*pool)
+                .await
+                .map_err(|e| format!("Failed to check attachment references: {e}"))?;
+
+        if remaining > 0 {
+            continue;
+        }
+
+        for path in std::iter::once(storage_path).chain(thumbnail_path) {
+            let full_path = app_data_dir.join(&path);
+            if full_path.exists()
+                && let Err(e) = fs::remove_file(&full_path)
+            {
+                log::warn!(
+                    "Failed to delete attachment file {}: {e}",
+                    full_path.display()
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+async fn prune_board_attachments<R: Runtime>(
+    app: AppHandle<R>,
+    pool: State<'_, DbPool>,
+    board_id: String,
+) -> Result<Value, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data directory: {e}"))?;
+
+    let rows: Vec<(String, i64, String, bool, Option<String>, bool)> = sqlx::query_as(
+        "SELECT att.id, att.version, att.storage_path, att.is_external, att.thumbnail_path, EXISTS(SELECT 1 FROM kanban_cards c WHERE c.id = att.card_id) AS card_exists FROM kanban_attachments att WHERE att.board_id = ?",
+    )
+    .bind(&board_id)
+    .fetch_all(&*pool)
+    .await
+    .map_err(|e| format!("Failed to load board attachments: {e}"))?;
+
+    let stale: Vec<(String, i64, String, bool, Option<String>)> = rows
+        .into_iter()
+        .filter(|(_, _, storage_path, _, _, card_exists)| {
+            !card_exists || !app_data_dir.join(storage_path).exists()
+        })
+        .map(
+            |(id, version, storage_path, is_external, thumbnail_path, _)| {
+                (id, version, storage_path, is_external, thumbnail_path)
+            },
+        )
+        .collect();
+
+    if stale.is_empty() {
+        return Ok(json!({ "rowsRemoved": 0, "bytesFreed": 0 }));
+    }
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to begin transaction: {e}"))?;
+
+    for (id, version, _, _, _) in &stale {
+        sqlx::query("DELETE FROM kanban_attachments WHERE id = ? AND version = ?")
+            .bind(id)
+            .bind(version)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to delete attachment metadata: {e}"))?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {e}"))?;
+
+    // Versions sharing a storage path share its thumbnail as well
+    let storage_paths: BTreeMap<&String, Option<&String>> = stale
+        .iter()
+        .filter(|(_, _, _, is_external, _)| !is_external)
+        .map(|(_, _, path, _, thumbnail_path)| (path, thumbnail_path.as_ref()))
+        .collect();
+    let mut bytes_freed: u64 = 0;
+
+    for (storage_path, thumbnail_path) in storage_paths {
+        let remaining: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM kanban_attachments WHERE storage_path = ?")
+                .bind(storage_path)
+                .fetch_one(&*pool)
+                .await
+                .map_err(|e| format!("Failed to check attachment references: {e}"))?;
+
+        if remaining > 0 {
+            continue;
+        }
+
+        for path in std::iter::once(storage_path).chain(thumbnail_path) {
+            let full_file_path = app_data_dir.join(path);
+            if let Ok(metadata) = fs::metadata(&full_file_path) {
+                match fs::remove_file(&full_file_path) {
+                    Ok(()) => bytes_freed += metadata.len(),
+                    Err(e) => log::warn!(
+                        "Failed to delete attachment file {}: {e}",
+                        full_file_path.display()
+                    ),
+                }
+            }
+        }
+    }
+
+    Ok(json!({
+        "rowsRemoved": stale.len(),
+        "bytesFreed": bytes_freed,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use tauri::test::{MockRuntime, mock_builder, mock_context, noop_assets};
+
+    /// In-memory database with the real schema. A single connection keeps every
+    /// query on the same in-memory database.
+    async fn test_pool() -> DbPool {
+        let options = SqliteConnectOptions::from_str("sqlite::memory:")
+            .expect("valid in-memory database url")
+            .foreign_keys(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(options)
+            .await
+            .expect("in-memory pool");
+        initialize_schema(&pool).await.expect("schema initializes");
+        pool
+    }
+
+    /// Mock app managing a fresh database, with its app data directory under the
+    /// system temp dir so file-backed commands never touch real user data.
+    async fn test_app() -> tauri::App<MockRuntime> {
+        let mut context = mock_context(noop_assets());
+        let data_dir = std::env::temp_dir().join(format!("modulo-test-{}", Uuid::new_v4()));
+        context.config_mut().identifier = data_dir.to_string_lossy().into_owned();
+        let app = mock_builder().build(context).expect("mock app builds");
+        app.manage(test_pool().await);
+        app
+    }
+
+    async fn seed_board(pool: &DbPool, board_id: &str) {
+        sqlx::query("INSERT INTO kanban_boards (id, workspace_id, title) VALUES (?, ?, ?)")
+            .bind(board_id)
+            .bind(DEFAULT_WORKSPACE_ID)
+            .bind(format!("Quadro {board_id}"))
+            .execute(pool)
+            .await
+            .expect("board inserts");
+    }
+
+    async fn seed_column(
+        pool: &DbPool,
+        board_id: &str,
+        column_id: &str,
+        title: &str,
+        position: i64,
+    ) {
+        sqlx::query(
+            "INSERT INTO kanban_columns (id, board_id, title, position) VALUES (?, ?, ?, ?)",
+        )
+        .bind(column_id)
+        .bind(board_id)
+        .bind(title)
+        .bind(position)
+        .execute(pool)
+        .await
+        .expect("column inserts");
+    }
+
+    async fn seed_card(
+        pool: &DbPool,
+        board_id: &str,
+        column_id: &str,
+        card_id: &str,
+        title: &str,
+        position: i64,
+    ) {
+        sqlx::query(
+            "INSERT INTO kanban_cards (id, board_id, column_id, title, position) VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(card_id)
+        .bind(board_id)
+        .bind(column_id)
+        .bind(title)
+        .bind(position)
+        .execute(pool)
+        .await
+        .expect("card inserts");
+    }
+
+    /// Board `b1` with a "To Do" column `c1` holding card `k1`.
+    async fn seed_basic_board(pool: &DbPool) {
+        seed_board(pool, "b1").await;
+        seed_column(pool, "b1", "c1", "To Do", 0).await;
+        seed_card(pool, "b1", "c1", "k1", "Card", 0).await;
+    }
+
+    async fn seed_tag(pool: &DbPool, board_id: &str, tag_id: &str, label: &str, card_ids: &[&str]) {
+        sqlx::query("INSERT INTO kanban_tags (id, board_id, label) VALUES (?, ?, ?)")
+            .bind(tag_id)
+            .bind(board_id)
+            .bind(label)
+            .execute(pool)
+            .await
+            .expect("tag inserts");
+        for card_id in card_ids {
+            sqlx::query("INSERT INTO kanban_card_tags (card_id, tag_id) VALUES (?, ?)")
+                .bind(card_id)
+                .bind(tag_id)
+                .execute(pool)
+                .await
+                .expect("card tag inserts");
+        }
+    }
+
+    async fn seed_attachment(pool: &DbPool, id: &str, card_id: &str, storage_path: &str) {
+        sqlx::query(
+            "INSERT INTO kanban_attachments (id, card_id, board_id, filename, original_name, storage_path) VALUES (?, ?, 'b1', ?, ?, ?)",
+        )
+        .bind(id)
+        .bind(card_id)
+        .bind(storage_path)
+        .bind(storage_path)
+        .bind(storage_path)
+        .execute(pool)
+        .await
+        .expect("attachment inserts");
+    }
+
+    async fn load_board_cards(app: &tauri::App<MockRuntime>, board_id: &str) -> Vec<Value> {
+        load_cards(
+            app.state(),
+            board_id.into(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap()
+    }
+
+    fn update_args(fields: Value) -> UpdateCardArgs {
+        serde_json::from_value(fields).expect("valid update args")
+    }
+
+    /// Board b1 with two columns, two tagged cards, a subtask and a pinned note.
+    async fn seed_export_board(pool: &DbPool) {
+        seed_basic_board(pool).await;
+        seed_column(pool, "b1", "c2", "Done", 1).await;
+        seed_card(pool, "b1", "c2", "k2", "Entregue", 0).await;
+        seed_tag(pool, "b1", "t1", "Bug", &["k1"]).await;
+        seed_tag(pool, "b1", "t2", "UX", &["k1", "k2"]).await;
+        sqlx::query(
+            "INSERT INTO kanban_subtasks (id, board_id, card_id, title, position) VALUES ('s1', 'b1', 'k1', 'Revisar', 0)",
+        )
+        .execute(pool)
+        .await
+        .expect("subtask inserts");
+        sqlx::query("UPDATE kanban_cards SET priority = 'high' WHERE id = 'k1'")
+            .execute(pool)
+            .await
+            .expect("priority updates");
+        sqlx::query(
+            "INSERT INTO notes (id, board_id, title, content, pinned, tags) VALUES ('n1', 'b1', 'Ata', 'Decisões', 1, '[\"reunião\"]')",
+        )
+        .execute(pool)
+        .await
+        .expect("note inserts");
+    }
+
+    /// Id-independent view of an export document, so a re-imported board compares equal.
+    fn export_shape(document: &Value) -> Value {
+        let label = |section: &str, id: &Value, field: &str| {
+            document[section]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|entity| entity["id"] == *id)
+                .map(|entity| entity[field].clone())
+                .unwrap_or(Value::Null)
+        };
+        let cards: Vec<Value> = document["cards"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|card| {
+                let mut tags: Vec<Value> = card["tagIds"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|tag_id| label("tags", tag_id, "label"))
+                    .collect();
+                tags.sort_by_key(|tag| tag.to_string());
+                let subtasks: Vec<Value> = card["subtasks"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(|subtask| subtask["title"].clone())
+                    .collect();
+                json!({
+                    "column": label("columns", &card["columnId"], "title"),
+                    "title": card["title"],
+                    "priority": card["priority"],
+                    "tags": tags,
+                    "subtasks": subtasks,
+                })
+            })
+            .collect();
+        let notes: Vec<Value> = document["notes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|note| json!([note["title"], note["content"], note["pinned"]]))
+            .collect();
+        json!({
+            "schemaVersion": document["schemaVersion"],
+            "board": document["board"]["title"],
+            "columns": document["columns"].as_array().unwrap().iter().map(|column| column["title"].clone()).collect::<Vec<_>>(),
+            "cards": cards,
+            "notes": notes,
+        })
+    }
+
+    /// Writes `content` to `dir/name` and uploads it to card k1 on board b1.
+    async fn upload_test_file(
+        app: &tauri::App<MockRuntime>,
+        dir: &std::path::Path,
+        name: &str,
+        content: &[u8],
+    ) -> UploadImageResponse {
+        let source = dir.join(name);
+        fs::write(&source, content).unwrap();
+        upload_image(
+            app.handle().clone(),
+            app.state(),
+            "k1".into(),
+            "b1".into(),
+            source.to_string_lossy().into_owned(),
+        )
+        .await
+        .unwrap()
+    }
+
+    fn titles(values: &[Value]) -> Vec<&str> {
+        values
+            .iter()
+            .map(|value| value["title"].as_str().unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn create_subtasks_skips_blank_lines_and_keeps_order() {
+        let app = test_app().await;
+        seed_basic_board(&app.state::<DbPool>()).await;
+
+        let lines = ["Um", "Dois", "   ", "Três", "Quatro"]
+            .map(String::from)
+            .to_vec();
+        let created = create_subtasks(
+            app.handle().clone(),
+            app.state(),
+            "b1".into(),
+            "k1".into(),
+            lines,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(titles(&created), ["Um", "Dois", "Três", "Quatro"]);
+        let positions: Vec<i64> = created
+            .iter()
+            .map(|s| s["position"].as_i64().unwrap())
+            .collect();
+        assert_eq!(positions, [0, 1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn create_subtasks_appends_after_position_gaps() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+
+        create_subtasks(
+            app.handle().clone(),
+            app.state(),
+            "b1".into(),
+            "k1".into(),
+            vec!["A".into(), "B".into()],
+        )
+        .await
+        .unwrap();
+        sqlx::query("DELETE FROM kanban_subtasks WHERE title = 'A'")
+            .execute(&*pool)
+            .await
+            .unwrap();
+
+        let created = create_subtasks(
+            app.handle().clone(),
+            app.state(),
+            "b1".into(),
+            "k1".into(),
+            vec!["C".into(), "D".into()],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(titles(&created), ["C", "D"]);
+    }
+
+    #[tokio::test]
+    async fn icon_options_mirror_the_allowlists() {
+        let options = get_icon_options().await.unwrap();
+
+        assert_eq!(options["boardIcons"], json!(ALLOWED_BOARD_ICONS));
+        assert_eq!(options["columnIcons"], json!(ALLOWED_COLUMN_ICONS));
+        assert_eq!(options["defaultBoardIcon"], DEFAULT_BOARD_ICON);
+        assert_eq!(options["defaultColumnIcon"], DEFAULT_COLUMN_ICON);
+    }
+
+    async fn search_ids(app: &tauri::App<MockRuntime>, query: &str) -> Vec<String> {
+        global_search(app.state(), query.into(), None, None)
+            .await
+            .unwrap()
+            .results
+            .into_iter()
+            .map(|r| r.id)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn search_index_follows_card_writes() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        sqlx::query("UPDATE kanban_cards SET title = 'Revisar orçamento' WHERE id = 'k1'")
+            .execute(&*pool)
+            .await
+            .unwrap();
+
+        assert!(search_index_available(&pool).await);
+        assert_eq!(search_ids(&app, "orçamento").await, ["k1"]);
+
+        sqlx::query("UPDATE kanban_cards SET title = 'Planejar viagem' WHERE id = 'k1'")
+            .execute(&*pool)
+            .await
+            .unwrap();
+        assert!(search_ids(&app, "orçamento").await.is_empty());
+        assert_eq!(search_ids(&app, "viagem").await, ["k1"]);
+
+        sqlx::query("DELETE FROM kanban_cards WHERE id = 'k1'")
+            .execute(&*pool)
+            .await
+            .unwrap();
+        assert!(search_ids(&app, "viagem").await.is_empty());
+        let leftover = sqlx::query_scalar::<_, i64>(
+            "SELECT (SELECT COUNT(*) FROM kanban_fts_rows WHERE entity_type = 'card')
+                + (SELECT COUNT(*) FROM kanban_fts WHERE entity_type = 'card')",
+        )
+        .fetch_one(&*pool)
+        .await
+        .unwrap();
+        assert_eq!(leftover, 0);
+    }
+
+    #[tokio::test]
+    async fn search_index_rebuilds_legacy_table() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        sqlx::query("DROP TABLE kanban_fts_rows")
+            .execute(&*pool)
+            .await
+            .unwrap();
+
+        ensure_search_index(&pool).await.unwrap();
+
+        assert_eq!(search_ids(&app, "Quadro").await, ["b1"]);
+        let mapped = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM kanban_fts_rows")
+            .fetch_one(&*pool)
+            .await
+            .unwrap();
+        assert_eq!(mapped, 2);
+    }
+
+    #[tokio::test]
+    async fn tag_cloud_counts_only_active_cards() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_card(&pool, "b1", "c1", "k2", "Segundo", 1).await;
+        seed_card(&pool, "b1", "c1", "k3", "Arquivado", 2).await;
+        sqlx::query("UPDATE kanban_cards SET archived_at = '2024-01-01T00:00:00Z' WHERE id = 'k3'")
+            .execute(&*pool)
+            .await
+            .unwrap();
+        seed_tag(&pool, "b1", "t-bug", "bug", &["k1", "k3"]).await;
+        seed_tag(&pool, "b1", "t-ux", "ux", &["k1", "k2"]).await;
+        seed_tag(&pool, "b1", "t-old", "antigo", &["k3"]).await;
+
+        let cloud = get_tag_cloud(app.state(), "b1".into()).await.unwrap();
+
+        let counts: Vec<(&str, i64)> = cloud
+            .iter()
+            .map(|tag| {
+                (
+                    tag["label"].as_str().unwrap(),
+                    tag["cardCount"].as_i64().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(counts, [("ux", 2), ("bug", 1), ("antigo", 0)]);
+    }
+
+    #[tokio::test]
+    async fn move_card_sets_priority_with_the_move() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_column(&pool, "b1", "c2", "Done", 1).await;
+        sqlx::query("UPDATE kanban_cards SET priority = 'high' WHERE id = 'k1'")
+            .execute(&*pool)
+            .await
+            .unwrap();
+
+        move_card(
+            app.handle().clone(),
+            app.state(),
+            "b1".into(),
+            "k1".into(),
+            "c1".into(),
+            "c2".into(),
+            0,
+            Some("none".into()),
+            None,
+        )
+        .await
+        .unwrap();
+
+        let (column_id, priority) = sqlx::query_as::<_, (String, String)>(
+            "SELECT column_id, priority FROM kanban_cards WHERE id = 'k1'",
+        )
+        .fetch_one(&*pool)
+        .await
+        .unwrap();
+        assert_eq!((column_id.as_str(), priority.as_str()), ("c2", "none"));
+    }
+
+    #[tokio::test]
+    async fn move_card_rejects_invalid_priority_without_moving() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_column(&pool, "b1", "c2", "Done", 1).await;
+
+        let result = move_card(
+            app.handle().clone(),
+            app.state(),
+            "b1".into(),
+            "k1".into(),
+            "c1".into(),
+            "c2".into(),
+            0,
+            Some("urgent".into()),
+            None,
+        )
+        .await;
+
+        assert!(result.is_err());
+        let column_id =
+            sqlx::query_scalar::<_, String>("SELECT column_id FROM kanban_cards WHERE id = 'k1'")
+                .fetch_one(&*pool)
+                .await
+                .unwrap();
+        assert_eq!(column_id, "c1");
+    }
+
+    #[tokio::test]
+    async fn position_anomalies_report_duplicate_card_positions() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_card(&pool, "b1", "c1", "k2", "Duplicado", 0).await;
+        seed_card(&pool, "b1", "c1", "k3", "Terceiro", 1).await;
+
+        let report = find_position_anomalies(app.state(), "b1".into())
+            .await
+            .unwrap();
+
+        assert_eq!(report["columns"], json!([]));
+        let cards = report["cards"].as_array().unwrap();
+        assert_eq!(cards.len(), 1);
+        assert_eq!(cards[0]["columnId"], "c1");
+        let mut flagged: Vec<(&str, &str)> = cards[0]["issues"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|issue| {
+                (
+                    issue["cardId"].as_str().unwrap(),
+                    issue["issue"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        flagged.sort();
+        assert_eq!(flagged, [("k1", "duplicate"), ("k2", "duplicate")]);
+    }
+
+    fn save_card_args(title: &str, due_date: Option<&str>) -> SaveCardArgs {
+        SaveCardArgs {
+            id: "k-sync".into(),
+            board_id: "b1".into(),
+            column_id: "c1".into(),
+            title: title.into(),
+            description: None,
+            position: None,
+            priority: None,
+            due_date: due_date.map(String::from),
+            estimate_minutes: None,
+            tag_ids: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn save_card_inserts_then_updates_the_same_id() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+
+        let created = save_card(
+            app.handle().clone(),
+            app.state(),
+            save_card_args("Rascunho", None),
+        )
+        .await
+        .unwrap();
+        let updated = save_card(
+            app.handle().clone(),
+            app.state(),
+            save_card_args("  Final  ", None),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(created["title"], "Rascunho");
+        assert_eq!(updated["title"], "Final");
+        let count =
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM kanban_cards WHERE id = 'k-sync'")
+                .fetch_one(&*pool)
+                .await
+                .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[tokio::test]
+    async fn save_card_updates_log_activity_and_drop_stale_reminders() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        save_card(
+            app.handle().clone(),
+            app.state(),
+            save_card_args("Cartão", None),
+        )
+        .await
+        .unwrap();
+        sqlx::query(
+            "UPDATE kanban_cards SET remind_at = '2030-06-01T09:00:00Z' WHERE id = 'k-sync'",
+        )
+        .execute(&*pool)
+        .await
+        .unwrap();
+
+        let updated = save_card(
+            app.handle().clone(),
+            app.state(),
+            save_card_args("Cartão", Some("2030-01-02")),
+        )
+        .await
+        .unwrap();
+        let activity = get_card_activity(app.state(), "k-sync".into(), "b1".into(), None)
+            .await
+            .unwrap();
+        let types: Vec<&str> = activity
+            .iter()
+            .map(|entry| entry.activity_type.as_str())
+            .collect();
+
+        assert_eq!(updated["remindAt"], Value::Null);
+        assert!(types.contains(&"card_updated"));
+        assert!(types.contains(&"card_due_date_changed"));
+    }
+
+    #[tokio::test]
+    async fn save_card_treats_blank_due_dates_alike_on_both_paths() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+
+        let created = save_card(
+            app.handle().clone(),
+            app.state(),
+            save_card_args("Cartão", Some("   ")),
+        )
+        .await
+        .unwrap();
+        let updated = save_card(
+            app.handle().clone(),
+            app.state(),
+            save_card_args("Cartão", Some("   ")),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(created["dueDate"], Value::Null);
+        assert_eq!(updated["dueDate"], Value::Null);
+    }
+
+    #[tokio::test]
+    async fn prune_board_attachments_removes_dangling_rows() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        let data_dir = app.path().app_data_dir().unwrap();
+        fs::create_dir_all(data_dir.join("attachments")).unwrap();
+        fs::write(data_dir.join("attachments/kept.txt"), b"kept").unwrap();
+        fs::write(data_dir.join("attachments/orphan.txt"), b"orphan").unwrap();
+        fs::write(data_dir.join("attachments/orphan.thumb.png"), b"th").unwrap();
+
+        seed_attachment(&pool, "a-kept", "k1", "attachments/kept.txt").await;
+        seed_attachment(&pool, "a-missing", "k1", "attachments/missing.txt").await;
+        sqlx::query("PRAGMA foreign_keys = OFF")
+            .execute(&*pool)
+            .await
+            .unwrap();
+        seed_attachment(&pool, "a-orphan", "gone", "attachments/orphan.txt").await;
+        sqlx::query(
+            "UPDATE kanban_attachments SET thumbnail_path = 'attachments/orphan.thumb.png' WHERE id = 'a-orphan'",
+        )
+        .execute(&*pool)
+        .await
+        .unwrap();
+        sqlx::query("PRAGMA foreign_keys = ON")
+            .execute(&*pool)
+            .await
+            .unwrap();
+
+        let summary = prune_board_attachments(app.handle().clone(), app.state(), "b1".into())
+            .await
+            .unwrap();
+
+        assert_eq!(summary, json!({ "rowsRemoved": 2, "bytesFreed": 8 }));
+        let remaining = sqlx::query_scalar::<_, String>("SELECT id FROM kanban_attachments")
+            .fetch_all(&*pool)
+            .await
+            .unwrap();
+        assert_eq!(remaining, ["a-kept"]);
+        assert!(!data_dir.join("attachments/orphan.txt").exists());
+        assert!(!data_dir.join("attachments/orphan.thumb.png").exists());
+        assert!(data_dir.join("attachments/kept.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn delete_attachment_version_removes_its_thumbnail() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        let data_dir = app.path().app_data_dir().unwrap();
+        fs::create_dir_all(data_dir.join("attachments")).unwrap();
+        fs::write(data_dir.join("attachments/photo.png"), b"photo").unwrap();
+        fs::write(data_dir.join("attachments/photo.thumb.png"), b"th").unwrap();
+        seed_attachment(&pool, "a1", "k1", "attachments/photo.png").await;
+        sqlx::query(
+            "UPDATE kanban_attachments SET thumbnail_path = 'attachments/photo.thumb.png' WHERE id = 'a1'",
+        )
+        .execute(&*pool)
+        .await
+        .unwrap();
+
+        delete_attachment_version(
+            app.handle().clone(),
+            app.state(),
+            ManageAttachmentVersionArgs {
+                board_id: "b1".into(),
+                card_id: "k1".into(),
+                attachment_id: "a1".into(),
+                target_version: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        assert!(!data_dir.join("attachments/photo.png").exists());
+        assert!(!data_dir.join("attachments/photo.thumb.png").exists());
+    }
+
+    #[tokio::test]
+    async fn done_keywords_count_feito_cards_as_completed() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_board(&pool, "b1").await;
+        seed_column(&pool, "b1", "c1", "Feito", 0).await;
+        seed_card(&pool, "b1", "c1", "k1", "Entregue", 0).await;
+
+        let saved = set_done_column_keywords(app.handle().clone(), vec!["  Feito ".into()])
+            .await
+            .unwrap();
+        let stats = get_task_statistics(app.handle().clone(), app.state())
+            .await
+            .unwrap();
+
+        assert_eq!(saved, ["feito"]);
+        assert_eq!(stats.completed_this_week, 1);
+    }
+
+    #[tokio::test]
+    async fn done_keywords_reject_empty_and_long_lists() {
+        let app = test_app().await;
+
+        let empty = set_done_column_keywords(app.handle().clone(), vec!["   ".into()]).await;
+        let long = set_done_column_keywords(app.handle().clone(), vec!["x".repeat(51)]).await;
+
+        assert_eq!(
+            empty.unwrap_err(),
+            "Informe ao menos uma palavra-chave de conclusão."
+        );
+        assert_eq!(
+            long.unwrap_err(),
+            "A palavra-chave de conclusão deve ter no máximo 50 caracteres."
+        );
+    }
+
+    #[tokio::test]
+    async fn set_window_transparency_persists_the_preference() {
+        let app = test_app().await;
+
+        set_window_transparency(app.handle().clone(), true)
+            .await
+            .unwrap();
+        let enabled = load_preferences(app.handle().clone())
+            .await
+            .unwrap()
+            .transparency_enabled;
+        set_window_transparency(app.handle().clone(), false)
+            .await
+            .unwrap();
+        let disabled = load_preferences(app.handle().clone())
+            .await
+            .unwrap()
+            .transparency_enabled;
+
+        assert!(enabled);
+        assert!(!disabled);
+    }
+
+    #[tokio::test]
+    async fn board_changes_since_reports_only_newer_rows() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_card(&pool, "b1", "c1", "k2", "Alterado", 1).await;
+        for table in ["kanban_cards", "kanban_columns"] {
+            sqlx::query(&format!(
+                "UPDATE {table} SET updated_at = '2024-01-01T00:00:00.000Z'"
+            ))
+            .execute(&*pool)
+            .await
+            .unwrap();
+        }
+        sqlx::query("UPDATE kanban_cards SET title = 'Novo', updated_at = '2024-06-01T00:00:00.000Z' WHERE id = 'k2'")
+            .execute(&*pool)
+            .await
+            .unwrap();
+
+        let changes =
+            get_board_changes_since(app.state(), "b1".into(), "2024-03-01T00:00:00Z".into())
+                .await
+                .unwrap();
+
+        let card_ids: Vec<&str> = changes["cards"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|card| card["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(card_ids, ["k2"]);
+        assert_eq!(changes["columns"], json!([]));
+        assert_eq!(changes["deleted"], json!([]));
+    }
+
+    #[tokio::test]
+    async fn deleting_a_card_leaves_a_tombstone() {
+        let app = test_app().await;
+        seed_basic_board(&app.state::<DbPool>()).await;
 
-    Ok(format!("{WORKSPACE_ICON_DIR}/{filename}"))
-}
+        delete_card(app.handle().clone(), app.state(), "k1".into(), "b1".into())
+            .await
+            .unwrap();
+        let deletions = get_deletions_since(app.state(), "2000-01-01T00:00:00Z".into())
+            .await
+            .unwrap();
+
+        assert_eq!(deletions.len(), 1);
+        assert_eq!(deletions[0]["entityType"], "card");
+        assert_eq!(deletions[0]["entityId"], "k1");
+        assert_eq!(deletions[0]["boardId"], "b1");
+    }
+
+    #[tokio::test]
+    async fn verify_workspace_icons_clears_missing_files() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        let data_dir = app.path().app_data_dir().unwrap();
+        let icon_dir = data_dir.join(WORKSPACE_ICON_DIR);
+        fs::create_dir_all(&icon_dir).unwrap();
+        fs::write(icon_dir.join("present.png"), b"png").unwrap();
+        fs::write(icon_dir.join("deleted.png"), b"png").unwrap();
+        for (id, file) in [("w-ok", "present.png"), ("w-gone", "deleted.png")] {
+            sqlx::query("INSERT INTO workspaces (id, name, icon_path) VALUES (?, ?, ?)")
+                .bind(id)
+                .bind(id)
+                .bind(format!("{WORKSPACE_ICON_DIR}/{file}"))
+                .execute(&*pool)
+                .await
+                .unwrap();
+        }
+        fs::remove_file(icon_dir.join("deleted.png")).unwrap();
 
-fn remove_workspace_icon_file(app: &AppHandle, relative_path: &str) -> Result<(), String> {
-    if relative_path.trim().is_empty() {
-        return Ok(());
+        let affected = verify_workspace_icons(app.handle().clone(), app.state(), Some(true))
+            .await
+            .unwrap();
+
+        assert_eq!(affected.len(), 1);
+        assert_eq!(affected[0]["workspaceId"], "w-gone");
+        assert_eq!(affected[0]["cleared"], true);
+        let icons = sqlx::query_as::<_, (String, Option<String>)>(
+            "SELECT id, icon_path FROM workspaces WHERE id IN ('w-ok', 'w-gone') ORDER BY id",
+        )
+        .fetch_all(&*pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            icons,
+            [
+                ("w-gone".to_string(), None),
+                (
+                    "w-ok".to_string(),
+                    Some(format!("{WORKSPACE_ICON_DIR}/present.png"))
+                ),
+            ]
+        );
     }
 
-    let app_data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to resolve app data directory: {e}"))?;
+    #[tokio::test]
+    async fn board_stats_report_half_completed_board() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_column(&pool, "b1", "c2", "Entregues", 1).await;
+        seed_card(&pool, "b1", "c2", "k2", "Pronto", 0).await;
+        seed_card(&pool, "b1", "c2", "k3", "Arquivado", 1).await;
+        sqlx::query("UPDATE kanban_columns SET column_kind = 'done' WHERE id = 'c2'")
+            .execute(&*pool)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE kanban_cards SET archived_at = '2024-01-01T00:00:00Z' WHERE id = 'k3'")
+            .execute(&*pool)
+            .await
+            .unwrap();
+        toggle_board_favorite(app.state(), "b1".into())
+            .await
+            .unwrap();
+        seed_board(&pool, "b2").await;
+        sqlx::query(
+            "UPDATE kanban_boards SET archived_at = '2024-01-01T00:00:00Z' WHERE id = 'b2'",
+        )
+        .execute(&*pool)
+        .await
+        .unwrap();
 
-    let full_path = app_data_dir.join(relative_path);
-    if full_path.exists() {
-        fs::remove_file(&full_path)
-            .map_err(|e| format!("Falha ao remover arquivo de ícone antigo: {e}"))?;
-    }
+        let boards = load_boards_with_stats(app.handle().clone(), app.state())
+            .await
+            .unwrap();
+        let favorites = get_favorite_boards(app.handle().clone(), app.state())
+            .await
+            .unwrap();
+
+        assert_eq!(boards.len(), 1);
+        assert_eq!(boards[0]["id"], "b1");
+        assert_eq!(boards[0]["activeCards"], 2);
+        assert_eq!(boards[0]["completionPercent"], 50);
+        assert_eq!(favorites[0]["completionPercent"], 50);
+    }
+
+    #[tokio::test]
+    async fn archive_cards_before_skips_recent_and_non_done_cards() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_column(&pool, "b1", "c2", "Done", 1).await;
+        seed_card(&pool, "b1", "c1", "k-old-todo", "Antigo", 1).await;
+        seed_card(&pool, "b1", "c2", "k-old-done", "Antigo concluído", 0).await;
+        seed_card(&pool, "b1", "c2", "k-new-done", "Recente concluído", 1).await;
+        sqlx::query("UPDATE kanban_cards SET updated_at = '2024-01-01T00:00:00.000Z' WHERE id LIKE 'k-old-%'")
+            .execute(&*pool)
+            .await
+            .unwrap();
+        let archived_ids = || async {
+            sqlx::query_scalar::<_, String>(
+                "SELECT id FROM kanban_cards WHERE archived_at IS NOT NULL ORDER BY id",
+            )
+            .fetch_all(&*app.state::<DbPool>())
+            .await
+            .unwrap()
+        };
 
-    Ok(())
-}
+        let done_only = archive_cards_before(
+            app.handle().clone(),
+            app.state(),
+            "b1".into(),
+            "2024-06-01T00:00:00Z".into(),
+            true,
+        )
+        .await
+        .unwrap();
+        assert_eq!(done_only, 1);
+        assert_eq!(archived_ids().await, ["k-old-done"]);
+
+        let all = archive_cards_before(
+            app.handle().clone(),
+            app.state(),
+            "b1".into(),
+            "2024-06-01T00:00:00Z".into(),
+            false,
+        )
+        .await
+        .unwrap();
+        assert_eq!(all, 1);
+        assert_eq!(archived_ids().await, ["k-old-done", "k-old-todo"]);
+    }
+
+    #[tokio::test]
+    async fn card_positions_count_archived_cards() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_card(&pool, "b1", "c1", "k2", "Arquivado", 1).await;
+        sqlx::query("UPDATE kanban_cards SET archived_at = '2024-01-01T00:00:00Z' WHERE id = 'k2'")
+            .execute(&*pool)
+            .await
+            .unwrap();
 
-#[tauri::command]
-async fn save_cropped_workspace_icon(
-    app: AppHandle,
-    workspace_id: String,
-    image_data: Vec<u8>,
-) -> Result<String, String> {
-    let workspace_id = workspace_id.trim();
-    if workspace_id.is_empty() {
-        return Err("Invalid workspace ID".to_string());
+        let next = get_next_position(app.state(), "card".into(), "c1".into())
+            .await
+            .unwrap();
+        let created = save_card(
+            app.handle().clone(),
+            app.state(),
+            save_card_args("Novo", None),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(next, 2);
+        assert_eq!(created["position"], 2);
+    }
+
+    #[tokio::test]
+    async fn get_card_embeds_tags_and_subtasks() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_board(&pool, "b2").await;
+        seed_tag(&pool, "b1", "t1", "bug", &["k1"]).await;
+        create_subtasks(
+            app.handle().clone(),
+            app.state(),
+            "b1".into(),
+            "k1".into(),
+            vec!["Passo".into()],
+        )
+        .await
+        .unwrap();
+
+        let card = get_card(app.state(), "b1".into(), "k1".into())
+            .await
+            .unwrap();
+        let foreign = get_card(app.state(), "b2".into(), "k1".into()).await;
+        let missing = get_card(app.state(), "b1".into(), "nope".into()).await;
+
+        assert_eq!(card["tags"][0]["label"], "bug");
+        assert_eq!(card["subtasks"][0]["title"], "Passo");
+        assert!(matches!(foreign, Err(CommandError::Validation(_))));
+        assert!(matches!(missing, Err(CommandError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn reorder_columns_applies_full_order_and_rejects_partial_lists() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_board(&pool, "b1").await;
+        seed_column(&pool, "b1", "c1", "A", 0).await;
+        seed_column(&pool, "b1", "c2", "B", 1).await;
+        seed_column(&pool, "b1", "c3", "C", 2).await;
+
+        reorder_columns(
+            app.state(),
+            "b1".into(),
+            vec!["c3".into(), "c1".into(), "c2".into()],
+        )
+        .await
+        .unwrap();
+        let partial =
+            reorder_columns(app.state(), "b1".into(), vec!["c1".into(), "c2".into()]).await;
+
+        let columns = load_columns(app.state(), "b1".into(), None).await.unwrap();
+        assert_eq!(titles(&columns), ["C", "A", "B"]);
+        assert_eq!(
+            partial.unwrap_err(),
+            "A nova ordem precisa conter exatamente as colunas do quadro informado."
+        );
     }
 
-    if image_data.is_empty() {
-        return Err("No image data provided".to_string());
+    #[tokio::test]
+    async fn external_attachments_resolve_to_the_original_path() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        let external = std::env::temp_dir().join(format!("modulo-external-{}.pdf", Uuid::new_v4()));
+        fs::write(&external, b"%PDF").unwrap();
+
+        let attachment = link_external_attachment(
+            app.state(),
+            "k1".into(),
+            "b1".into(),
+            external.to_string_lossy().into_owned(),
+            "Contrato".into(),
+        )
+        .await
+        .unwrap();
+        let storage_path = attachment["storagePath"].as_str().unwrap();
+        let resolved = resolve_attachment_path(app.handle(), &pool, storage_path)
+            .await
+            .unwrap();
+        let internal = resolve_attachment_path(app.handle(), &pool, "attachments/x.pdf")
+            .await
+            .unwrap();
+
+        assert_eq!(attachment["isExternal"], true);
+        assert_eq!(attachment["filename"], "Contrato");
+        assert_eq!(resolved, external);
+        assert_eq!(
+            internal,
+            app.path().app_data_dir().unwrap().join("attachments/x.pdf")
+        );
+        fs::remove_file(external).unwrap();
     }
 
-    let app_data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to resolve app data directory: {e}"))?;
+    #[tokio::test]
+    async fn cached_board_stats_refresh_after_card_changes() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
 
-    let workspace_icon_dir = app_data_dir.join(WORKSPACE_ICON_DIR);
-    fs::create_dir_all(&workspace_icon_dir)
-        .map_err(|e| format!("Failed to create workspace icon directory: {e}"))?;
+        let before = get_cached_board_stats(app.state(), "b1".into())
+            .await
+            .unwrap();
+        seed_card(&pool, "b1", "c1", "k2", "Novo", 1).await;
+        let cached_rows = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM board_stats_cache WHERE board_id = 'b1'",
+        )
+        .fetch_one(&*pool)
+        .await
+        .unwrap();
+        let after = get_cached_board_stats(app.state(), "b1".into())
+            .await
+            .unwrap();
 
-    // Generate a unique filename for the cropped image
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or(Duration::from_secs(0))
-        .as_millis();
-    let filename = format!("{}_cropped_{}.png", workspace_id, timestamp);
-    let full_path = workspace_icon_dir.join(&filename);
+        assert_eq!(before["totalCards"], 1);
+        assert_eq!(cached_rows, 0);
+        assert_eq!(after["totalCards"], 2);
+        assert_eq!(after["activeCards"], 2);
+    }
 
-    // Write the image data to file
-    fs::write(&full_path, &image_data)
-        .map_err(|e| format!("Failed to write cropped image file: {e}"))?;
+    #[tokio::test]
+    async fn orphaned_cards_are_found_and_reassigned() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        sqlx::query("PRAGMA foreign_keys = OFF")
+            .execute(&*pool)
+            .await
+            .unwrap();
+        seed_card(&pool, "b1", "gone", "o1", "Órfão 1", 0).await;
+        seed_card(&pool, "b1", "gone", "o2", "Órfão 2", 1).await;
+        sqlx::query("PRAGMA foreign_keys = ON")
+            .execute(&*pool)
+            .await
+            .unwrap();
 
-    // Return the relative path
-    let relative_path = format!("{}/{}", WORKSPACE_ICON_DIR, filename);
-    Ok(relative_path)
-}
+        let orphans = find_orphaned_cards(app.state(), "b1".into()).await.unwrap();
+        let moved = reassign_card_column(app.state(), "b1".into(), "o1".into(), "c1".into())
+            .await
+            .unwrap();
 
-#[tauri::command]
-async fn get_workspace_icon_url(app: AppHandle, relative_path: String) -> Result<String, String> {
-    if relative_path.trim().is_empty() {
-        return Err("Invalid relative path".to_string());
+        assert_eq!(titles(&orphans), ["Órfão 1", "Órfão 2"]);
+        assert_eq!(moved["columnId"], "c1");
+        assert_eq!(moved["position"], 1);
+        let left_behind =
+            sqlx::query_scalar::<_, i64>("SELECT position FROM kanban_cards WHERE id = 'o2'")
+                .fetch_one(&*pool)
+                .await
+                .unwrap();
+        assert_eq!(left_behind, 0);
+    }
+
+    #[tokio::test]
+    async fn create_tags_skips_existing_labels() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_tag(&pool, "b1", "t-bug", "Bug", &[]).await;
+
+        let created = create_tags(
+            app.handle().clone(),
+            app.state(),
+            "b1".into(),
+            vec!["bug".into(), "UX".into(), "Docs".into(), "Infra".into()],
+            None,
+        )
+        .await
+        .unwrap();
+
+        let labels: Vec<&str> = created
+            .iter()
+            .map(|tag| tag["label"].as_str().unwrap())
+            .collect();
+        assert_eq!(labels, ["UX", "Docs", "Infra"]);
+        let total = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM kanban_tags")
+            .fetch_one(&*pool)
+            .await
+            .unwrap();
+        assert_eq!(total, 4);
     }
 
-    let app_data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to resolve app data directory: {e}"))?;
+    #[tokio::test]
+    async fn search_index_status_matches_base_tables() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_card(&pool, "b1", "c1", "k2", "Outro", 1).await;
 
-    let full_path = app_data_dir.join(&relative_path);
+        let status = get_search_index_status(app.state()).await.unwrap();
 
-    if !full_path.exists() {
-        return Err(format!("Workspace icon file not found: {}", relative_path));
+        assert_eq!(status["boards"], 1);
+        assert_eq!(status["cards"], 2);
+        assert_eq!(status["notes"], 0);
+        assert_eq!(status["ftsEnabled"], true);
+        assert_eq!(status["inSync"], true);
     }
 
-    // Read the file and convert to base64 data URL
-    let file_bytes =
-        fs::read(&full_path).map_err(|e| format!("Failed to read workspace icon file: {e}"))?;
+    #[tokio::test]
+    async fn set_card_column_by_title_moves_and_rejects_ambiguity() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_column(&pool, "b1", "c2", "Done", 1).await;
 
-    // Determine MIME type based on file extension
-    let mime_type = match full_path.extension().and_then(|ext| ext.to_str()) {
-        Some("png") => "image/png",
-        Some("jpg") | Some("jpeg") => "image/jpeg",
-        Some("gif") => "image/gif",
-        Some("webp") => "image/webp",
-        Some("svg") => "image/svg+xml",
-        Some("bmp") => "image/bmp",
-        _ => "image/png", // Default fallback
-    };
+        let moved =
+            set_card_column_by_title(app.state(), "b1".into(), "k1".into(), " done ".into())
+                .await
+                .unwrap();
+        seed_column(&pool, "b1", "c3", "DONE", 2).await;
+        let ambiguous =
+            set_card_column_by_title(app.state(), "b1".into(), "k1".into(), "Done".into()).await;
+
+        assert_eq!(moved["columnId"], "c2");
+        assert_eq!(
+            ambiguous.unwrap_err(),
+            "Mais de uma coluna chamada \"Done\" neste quadro."
+        );
+    }
 
-    // Encode as base64 data URL
-    let base64_data = general_purpose::STANDARD.encode(&file_bytes);
-    Ok(format!("data:{};base64,{}", mime_type, base64_data))
-}
+    #[tokio::test]
+    async fn export_board_html_escapes_user_content() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        sqlx::query(
+            "UPDATE kanban_cards SET title = '<script>alert(\"x\")</script>' WHERE id = 'k1'",
+        )
+        .execute(&*pool)
+        .await
+        .unwrap();
 
-#[tauri::command]
-async fn upload_image(
-    app: AppHandle,
-    pool: State<'_, DbPool>,
-    card_id: String,
-    board_id: String,
-    file_path: String,
-) -> Result<UploadImageResponse, String> {
-    println!(
-        "Starting upload image for card: {}, board: {}, file: {}",
-        card_id, board_id, file_path
-    );
+        let html = export_board_html(app.state(), "b1".into()).await.unwrap();
 
-    let app_data_dir = app.path().app_data_dir().map_err(|e| {
-        println!("Failed to resolve app data directory: {}", e);
-        format!("Failed to resolve app data directory: {e}")
-    })?;
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("&lt;script&gt;"));
+    }
 
-    let attachments_dir = app_data_dir.join("attachments");
-    println!("Creating attachments directory: {:?}", attachments_dir);
+    #[tokio::test]
+    async fn wip_mode_defaults_to_hard_and_warns_before_hard() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_card(&pool, "b1", "c1", "k2", "Segundo", 1).await;
+        let default_mode =
+            sqlx::query_scalar::<_, String>("SELECT wip_mode FROM kanban_columns WHERE id = 'c1'")
+                .fetch_one(&*pool)
+                .await
+                .unwrap();
+        sqlx::query("UPDATE kanban_columns SET wip_limit = 1, wip_mode = 'soft' WHERE id = 'c1'")
+            .execute(&*pool)
+            .await
+            .unwrap();
 
-    fs::create_dir_all(&attachments_dir).map_err(|e| {
-        println!("Failed to create attachments directory: {}", e);
-        format!("Failed to create attachments directory: {e}")
-    })?;
+        let warning =
+            set_column_wip_mode(app.state(), "b1".into(), "c1".into(), "hard".into(), None)
+                .await
+                .unwrap();
+        let confirmed = set_column_wip_mode(
+            app.state(),
+            "b1".into(),
+            "c1".into(),
+            "hard".into(),
+            Some(true),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(default_mode, "hard");
+        assert_eq!(warning["applied"], false);
+        assert_eq!(warning["mode"], "soft");
+        assert_eq!(warning["cardCount"], 2);
+        assert_eq!(confirmed["applied"], true);
+        assert_eq!(confirmed["mode"], "hard");
+    }
+
+    #[tokio::test]
+    async fn invalid_priorities_are_flagged_and_normalized() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_card(&pool, "b1", "c1", "k2", "Legado", 1).await;
+        // Legacy rows predate the CHECK constraint on priority.
+        sqlx::query("PRAGMA ignore_check_constraints = ON")
+            .execute(&*pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "UPDATE kanban_cards SET priority = CASE id WHEN 'k2' THEN 'urgent' ELSE 'high' END",
+        )
+        .execute(&*pool)
+        .await
+        .unwrap();
 
-    let card_attachments_dir = attachments_dir.join(&card_id);
-    println!(
-        "Ensuring card attachment directory exists: {:?}",
-        card_attachments_dir
-    );
+        let flagged = find_invalid_priorities(app.state(), "b1".into())
+            .await
+            .unwrap();
+        let normalized = normalize_card_priorities(app.state(), "b1".into())
+            .await
+            .unwrap();
+        let remaining = find_invalid_priorities(app.state(), "b1".into())
+            .await
+            .unwrap();
+
+        assert_eq!(titles(&flagged), ["Legado"]);
+        assert_eq!(normalized, 1);
+        assert!(remaining.is_empty());
+        let priorities =
+            sqlx::query_scalar::<_, String>("SELECT priority FROM kanban_cards ORDER BY id")
+                .fetch_all(&*pool)
+                .await
+                .unwrap();
+        assert_eq!(priorities, ["high", "none"]);
+    }
+
+    #[tokio::test]
+    async fn preferences_backup_survives_a_corrupted_live_file() {
+        let app = test_app().await;
+        let handle = app.handle().clone();
+        let mut preferences = load_preferences(handle.clone()).await.unwrap();
+        preferences.theme = "dark".into();
+        preferences.last_workspace_id = Some("w-1".into());
+        save_preferences(handle.clone(), preferences).await.unwrap();
+
+        let backup = backup_preferences(handle.clone()).await.unwrap();
+        fs::write(get_preferences_path(&handle).unwrap(), "{ not json").unwrap();
+        restore_preferences(handle.clone(), backup).await.unwrap();
+
+        let restored = load_preferences(handle).await.unwrap();
+        assert_eq!(restored.theme, "dark");
+        assert_eq!(restored.last_workspace_id.as_deref(), Some("w-1"));
+    }
+
+    #[tokio::test]
+    async fn load_columns_can_skip_disabled_columns() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_column(&pool, "b1", "c2", "Oculta", 1).await;
+        sqlx::query("UPDATE kanban_columns SET is_enabled = 0 WHERE id = 'c2'")
+            .execute(&*pool)
+            .await
+            .unwrap();
 
-    fs::create_dir_all(&card_attachments_dir).map_err(|e| {
-        println!("Failed to create card attachment directory: {}", e);
-        format!("Failed to create card attachment directory: {e}")
-    })?;
+        let all = load_columns(app.state(), "b1".into(), None).await.unwrap();
+        let enabled = load_columns(app.state(), "b1".into(), Some(true))
+            .await
+            .unwrap();
+
+        assert_eq!(titles(&all), ["To Do", "Oculta"]);
+        assert_eq!(titles(&enabled), ["To Do"]);
+    }
+
+    #[tokio::test]
+    async fn card_estimates_are_validated_and_round_trip() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        let create = |id: &str, estimate: i64| {
+            create_card(
+                app.handle().clone(),
+                app.state(),
+                id.into(),
+                "b1".into(),
+                "c1".into(),
+                "Estimado".into(),
+                None,
+                1,
+                "none".into(),
+                None,
+                None,
+                Some(estimate),
+            )
+        };
 
-    let source_path = PathBuf::from(&file_path);
-    println!("Checking source path: {:?}", source_path);
+        let negative = create("k-neg", -5).await;
+        create("k-est", 90).await.unwrap();
+        let negative_update = update_card(
+            app.handle().clone(),
+            app.state(),
+            update_args(json!({ "id": "k-est", "boardId": "b1", "estimateMinutes": -1 })),
+        )
+        .await;
+
+        assert!(matches!(negative, Err(CommandError::Validation(_))));
+        assert!(matches!(negative_update, Err(CommandError::Validation(_))));
+        let cards = load_board_cards(&app, "b1").await;
+        let card = cards.iter().find(|card| card["id"] == "k-est").unwrap();
+        assert_eq!(card["estimateMinutes"], 90);
+        assert!(cards.iter().all(|card| card["id"] != "k-neg"));
+    }
+
+    #[tokio::test]
+    async fn update_card_binds_values_instead_of_splicing_them() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_card(&pool, "b1", "c1", "k2", "Intacto", 1).await;
+        let payload = "O'Brien' OR 1=1 --";
+
+        update_card(
+            app.handle().clone(),
+            app.state(),
+            update_args(json!({
+                "id": "k1",
+                "boardId": "b1",
+                "title": payload,
+                "description": payload,
+            })),
+        )
+        .await
+        .unwrap();
 
-    if !source_path.exists() {
-        println!("Source file does not exist");
-        return Ok(UploadImageResponse {
-            success: false,
-            file_path: String::new(),
-            attachment: None,
-            error: Some("Source file does not exist".to_string()),
-        });
+        let rows = sqlx::query_as::<_, (String, String, Option<String>)>(
+            "SELECT id, title, description FROM kanban_cards ORDER BY id",
+        )
+        .fetch_all(&*pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            rows,
+            [
+                (
+                    "k1".to_string(),
+                    payload.to_string(),
+                    Some(payload.to_string())
+                ),
+                ("k2".to_string(), "Intacto".to_string(), None),
+            ]
+        );
     }
 
-    let file_extension = source_path
-        .extension()
-        .and_then(|ext| ext.to_str())
-        .unwrap_or("");
+    #[tokio::test]
+    async fn last_active_board_follows_the_newest_card_update() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_board(&pool, "b2").await;
+        seed_column(&pool, "b2", "c2", "To Do", 0).await;
+        seed_card(&pool, "b2", "c2", "k2", "Outro", 0).await;
+        for table in ["kanban_boards", "kanban_cards"] {
+            sqlx::query(&format!(
+                "UPDATE {table} SET updated_at = '2024-01-01T00:00:00.000Z'"
+            ))
+            .execute(&*pool)
+            .await
+            .unwrap();
+        }
+        sqlx::query(
+            "UPDATE kanban_cards SET updated_at = '2024-05-01T00:00:00.000Z' WHERE id = 'k1'",
+        )
+        .execute(&*pool)
+        .await
+        .unwrap();
 
-    println!("File extension: {}", file_extension);
+        let first = get_last_active_board(app.state()).await.unwrap().unwrap();
+        sqlx::query(
+            "UPDATE kanban_cards SET updated_at = '2024-06-01T00:00:00.000Z' WHERE id = 'k2'",
+        )
+        .execute(&*pool)
+        .await
+        .unwrap();
+        let second = get_last_active_board(app.state()).await.unwrap().unwrap();
 
-    let ext_lower = file_extension.to_lowercase();
-    let image_extensions = [
-        "jpg", "jpeg", "png", "gif", "webp", "svg", "bmp", "ico", "tiff", "tif",
-    ];
-    let document_extensions = [
-        "pdf", "doc", "docx", "xls", "xlsx", "ppt", "pptx", "txt", "csv", "md", "rtf", "zip",
-        "rar", "7z", "tar", "json",
-    ];
+        assert_eq!(first["id"], "b1");
+        assert_eq!(second["id"], "b2");
+    }
 
-    let is_image_by_extension = image_extensions.contains(&ext_lower.as_str());
-    let is_document_by_extension = document_extensions.contains(&ext_lower.as_str());
+    #[tokio::test]
+    async fn duplicate_card_titles_are_grouped_per_column() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_card(&pool, "b1", "c1", "k2", "  card ", 1).await;
+        seed_card(&pool, "b1", "c1", "k3", "Único", 2).await;
 
-    if !is_image_by_extension && !is_document_by_extension {
-        println!(
-            "Unsupported attachment extension received: {}",
-            file_extension
+        let groups = find_duplicate_card_titles(app.state(), "b1".into())
+            .await
+            .unwrap();
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0]["columnId"], "c1");
+        assert_eq!(groups[0]["cardIds"], json!(["k1", "k2"]));
+    }
+
+    #[tokio::test]
+    async fn clear_cards_due_date_nulls_only_the_listed_cards() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_card(&pool, "b1", "c1", "k2", "Dois", 1).await;
+        seed_card(&pool, "b1", "c1", "k3", "Três", 2).await;
+        sqlx::query("UPDATE kanban_cards SET due_date = '2025-01-01T00:00:00Z'")
+            .execute(&*pool)
+            .await
+            .unwrap();
+
+        clear_cards_due_date(
+            app.state(),
+            "b1".into(),
+            vec!["k1".into(), "k2".into()],
+            None,
+        )
+        .await
+        .unwrap();
+
+        let due_dates = sqlx::query_scalar::<_, Option<String>>(
+            "SELECT due_date FROM kanban_cards ORDER BY id",
+        )
+        .fetch_all(&*pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            due_dates,
+            [None, None, Some("2025-01-01T00:00:00Z".to_string())]
         );
-        return Ok(UploadImageResponse {
-            success: false,
-            file_path: String::new(),
-            attachment: None,
-            error: Some(format!("Unsupported attachment type: .{}", file_extension)),
-        });
     }
 
-    let mime_type = mime_guess::from_path(&source_path).first_or_octet_stream();
-    println!("Detected MIME type: {}", mime_type);
-    println!("MIME type string: {}", mime_type.as_ref());
+    async fn move_to_limited_column(
+        app: &tauri::App<MockRuntime>,
+        card_id: &str,
+        force: Option<bool>,
+    ) -> Result<Value, CommandError> {
+        move_card(
+            app.handle().clone(),
+            app.state(),
+            "b1".into(),
+            card_id.into(),
+            "c1".into(),
+            "c2".into(),
+            0,
+            None,
+            force,
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn hard_wip_limit_blocks_moves_unless_forced() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_card(&pool, "b1", "c1", "k2", "Dois", 1).await;
+        seed_column(&pool, "b1", "c2", "Doing", 1).await;
+        seed_card(&pool, "b1", "c2", "k3", "Em andamento", 0).await;
+        sqlx::query("UPDATE kanban_columns SET wip_limit = 1, wip_mode = 'hard' WHERE id = 'c2'")
+            .execute(&*pool)
+            .await
+            .unwrap();
+
+        let blocked = move_to_limited_column(&app, "k1", None).await;
+        let column_after_block =
+            sqlx::query_scalar::<_, String>("SELECT column_id FROM kanban_cards WHERE id = 'k1'")
+                .fetch_one(&*pool)
+                .await
+                .unwrap();
+        move_to_limited_column(&app, "k2", Some(true))
+            .await
+            .unwrap();
+
+        match blocked {
+            Err(CommandError::Conflict(message)) => {
+                assert_eq!(
+                    message,
+                    "A coluna de destino atingiu o limite WIP de 1 cartões."
+                )
+            }
+            other => panic!("expected a WIP conflict, got {other:?}"),
+        }
+        assert_eq!(column_after_block, "c1");
+        let target_count = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM kanban_cards WHERE column_id = 'c2'",
+        )
+        .fetch_one(&*pool)
+        .await
+        .unwrap();
+        assert_eq!(target_count, 2);
+    }
+
+    #[tokio::test]
+    async fn drag_move_clamps_a_stale_target_index_to_the_end() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_column(&pool, "b1", "c2", "Doing", 1).await;
+        seed_card(&pool, "b1", "c2", "k2", "Dois", 0).await;
+        seed_card(&pool, "b1", "c2", "k3", "Três", 1).await;
+
+        let moved = drag_move_card(
+            app.handle().clone(),
+            app.state(),
+            DragMoveArgs {
+                board_id: "b1".into(),
+                card_id: "k1".into(),
+                from_column_id: "c1".into(),
+                to_column_id: "c2".into(),
+                target_index: 42,
+                priority: None,
+                force: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(moved["columnId"], "c2");
+        assert_eq!(moved["position"], 2);
+        let order = sqlx::query_scalar::<_, String>(
+            "SELECT id FROM kanban_cards WHERE column_id = 'c2' ORDER BY position ASC",
+        )
+        .fetch_all(&*pool)
+        .await
+        .unwrap();
+        assert_eq!(order, ["k2", "k3", "k1"]);
+    }
+
+    #[tokio::test]
+    async fn wip_limit_is_hard_by_default_and_soft_mode_warns() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_card(&pool, "b1", "c1", "k2", "Dois", 1).await;
+        seed_column(&pool, "b1", "c2", "Doing", 1).await;
+        seed_card(&pool, "b1", "c2", "k3", "Em andamento", 0).await;
+        sqlx::query("UPDATE kanban_columns SET wip_limit = 1 WHERE id = 'c2'")
+            .execute(&*pool)
+            .await
+            .unwrap();
 
-    if !is_image_by_extension && mime_type.type_().as_str().starts_with("image/") {
-        println!(
-            "Attachment extension {} detected as image MIME {}; treating as document",
-            file_extension, mime_type
+        let blocked = move_to_limited_column(&app, "k1", None).await;
+        sqlx::query("UPDATE kanban_columns SET wip_mode = 'soft' WHERE id = 'c2'")
+            .execute(&*pool)
+            .await
+            .unwrap();
+        let warned = move_to_limited_column(&app, "k2", None).await.unwrap();
+
+        assert!(matches!(blocked, Err(CommandError::Conflict(_))));
+        assert_eq!(
+            warned["wipWarning"],
+            "A coluna de destino ultrapassou o limite WIP de 1 cartões."
         );
+        let column_id =
+            sqlx::query_scalar::<_, String>("SELECT column_id FROM kanban_cards WHERE id = 'k2'")
+                .fetch_one(&*pool)
+                .await
+                .unwrap();
+        assert_eq!(column_id, "c2");
     }
 
-    let original_name = source_path
-        .file_name()
-        .and_then(|name| name.to_str())
-        .map(|name| name.replace(['/', '\\'], "_"))
-        .filter(|name| !name.trim().is_empty())
-        .unwrap_or_else(|| {
-            let fallback = format!("attachment.{}", file_extension);
-            println!(
-                "Unable to determine original filename, falling back to {}",
-                fallback
-            );
-            fallback
-        });
+    #[tokio::test]
+    async fn version_info_reports_schema_and_sqlite_versions() {
+        let app = test_app().await;
 
-    let mut destination_path = card_attachments_dir.join(&original_name);
+        let info = get_version_info(app.state(), app.handle().clone())
+            .await
+            .unwrap();
 
-    if destination_path.exists() {
-        println!(
-            "Attachment with same name exists, generating unique filename for {:?}",
-            destination_path
-        );
+        assert_eq!(info["schemaVersion"], SCHEMA_VERSION);
+        assert!(!info["sqliteVersion"].as_str().unwrap().is_empty());
+    }
 
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map_err(|e| {
-                println!("System time error: {}", e);
-                format!("System time error: {e}")
-            })?
-            .as_secs();
+    #[tokio::test]
+    async fn quick_add_uses_the_single_intake_column() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_column(&pool, "b1", "c2", "Entrada", 1).await;
 
-        let (base, ext) = match destination_path.file_stem().and_then(|s| s.to_str()) {
-            Some(stem) => (
-                stem.to_string(),
-                source_path
-                    .extension()
-                    .and_then(|e| e.to_str())
-                    .map(|e| e.to_string()),
-            ),
-            None => (
-                "attachment".to_string(),
-                source_path
-                    .extension()
-                    .and_then(|e| e.to_str())
-                    .map(|e| e.to_string()),
-            ),
-        };
+        set_default_intake_column(app.state(), "b1".into(), "c1".into())
+            .await
+            .unwrap();
+        set_default_intake_column(app.state(), "b1".into(), "c2".into())
+            .await
+            .unwrap();
+        let card = quick_add_card(
+            app.handle().clone(),
+            app.state(),
+            "b1".into(),
+            "Ideia".into(),
+            None,
+        )
+        .await
+        .unwrap();
 
-        let mut counter = 1u32;
-        loop {
-            let candidate_name = if let Some(ref ext) = ext {
-                format!("{}_{timestamp}_{counter}.{}", base, ext)
-            } else {
-                format!("{}_{timestamp}_{counter}", base)
-            };
+        let intake = sqlx::query_scalar::<_, String>(
+            "SELECT id FROM kanban_columns WHERE board_id = 'b1' AND is_default_intake = 1",
+        )
+        .fetch_all(&*pool)
+        .await
+        .unwrap();
+        assert_eq!(intake, ["c2"]);
+        assert_eq!(card["columnId"], "c2");
+    }
+
+    #[tokio::test]
+    async fn overdue_counts_group_by_priority_and_skip_done_columns() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_column(&pool, "b1", "c2", "Done", 1).await;
+        seed_card(&pool, "b1", "c1", "k2", "Alta", 1).await;
+        seed_card(&pool, "b1", "c1", "k3", "Baixa", 2).await;
+        seed_card(&pool, "b1", "c1", "k4", "Futura", 3).await;
+        seed_card(&pool, "b1", "c2", "k5", "Concluída", 0).await;
+        sqlx::query(
+            "UPDATE kanban_cards SET
+                priority = CASE id WHEN 'k3' THEN 'low' ELSE 'high' END,
+                due_date = CASE id WHEN 'k4' THEN '2999-01-01T00:00:00Z' ELSE '2020-01-01T00:00:00Z' END",
+        )
+        .execute(&*pool)
+        .await
+        .unwrap();
 
-            let candidate_path = card_attachments_dir.join(&candidate_name);
-            if !candidate_path.exists() {
-                destination_path = candidate_path;
-                break;
-            }
+        let counts = get_overdue_by_priority(app.handle().clone(), app.state())
+            .await
+            .unwrap();
 
-            counter += 1;
-        }
+        assert_eq!(
+            counts,
+            json!({ "none": 0, "low": 1, "medium": 0, "high": 2, "total": 3 })
+        );
     }
 
-    println!("Copying from {:?} to {:?}", source_path, destination_path);
-
-    fs::copy(&source_path, &destination_path).map_err(|e| {
-        println!("Failed to copy file: {}", e);
-        format!("Failed to copy file: {e}")
-    })?;
+    #[tokio::test]
+    async fn merge_workspaces_moves_boards_and_removes_source() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        for workspace_id in ["w-src", "w-dst"] {
+            sqlx::query("INSERT INTO workspaces (id, name) VALUES (?, ?)")
+                .bind(workspace_id)
+                .bind(workspace_id)
+                .execute(&*pool)
+                .await
+                .unwrap();
+        }
+        seed_board(&pool, "b1").await;
+        seed_board(&pool, "b2").await;
+        seed_board(&pool, "b3").await;
+        sqlx::query("UPDATE kanban_boards SET workspace_id = 'w-src' WHERE id IN ('b1', 'b2')")
+            .execute(&*pool)
+            .await
+            .unwrap();
 
-    let relative_path = destination_path
-        .strip_prefix(&app_data_dir)
-        .map_err(|e| {
-            println!(
-                "Failed to compute relative path for {:?}: {}",
-                destination_path, e
-            );
-            format!("Failed to compute relative attachment path: {e}")
-        })?
-        .iter()
-        .map(|component| component.to_string_lossy())
-        .collect::<Vec<_>>()
-        .join("/");
+        let moved = merge_workspaces(
+            app.handle().clone(),
+            app.state(),
+            "w-src".into(),
+            "w-dst".into(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(moved, 2);
 
-    println!("Generated relative path: {}", relative_path);
+        let source =
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM workspaces WHERE id = 'w-src'")
+                .fetch_one(&*pool)
+                .await
+                .unwrap();
+        assert_eq!(source, 0);
 
-    let file_metadata = fs::metadata(&destination_path).map_err(|e| {
-        println!("Failed to read file metadata: {}", e);
-        format!("Failed to read file metadata: {e}")
-    })?;
+        let homes = sqlx::query_as::<_, (String, String)>(
+            "SELECT id, workspace_id FROM kanban_boards ORDER BY id",
+        )
+        .fetch_all(&*pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            homes,
+            [
+                ("b1".to_string(), "w-dst".to_string()),
+                ("b2".to_string(), "w-dst".to_string()),
+                ("b3".to_string(), DEFAULT_WORKSPACE_ID.to_string()),
+            ]
+        );
+    }
 
-    let file_size: i64 = file_metadata.len().try_into().unwrap_or(i64::MAX);
+    #[tokio::test]
+    async fn attachment_versions_upload_list_and_delete() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        let source_dir = std::env::temp_dir().join(format!("modulo-upload-{}", Uuid::new_v4()));
+        fs::create_dir_all(&source_dir).unwrap();
+        let source = source_dir.join("notes.txt");
+
+        for content in ["v1", "v2"] {
+            fs::write(&source, content).unwrap();
+            let response = upload_image(
+                app.handle().clone(),
+                app.state(),
+                "k1".into(),
+                "b1".into(),
+                source.to_string_lossy().into_owned(),
+            )
+            .await
+            .unwrap();
+            assert!(response.success && !response.duplicate);
+        }
 
-    let mut file_reader = fs::File::open(&destination_path).map_err(|e| {
-        println!("Failed to open file for checksum: {}", e);
-        format!("Failed to open file for checksum: {e}")
-    })?;
+        let list = |pool| {
+            list_card_attachments(
+                pool,
+                serde_json::from_value(json!({ "boardId": "b1", "cardId": "k1" })).unwrap(),
+            )
+        };
+        let attachments = list(app.state()).await.unwrap();
+        assert_eq!(attachments.as_array().unwrap().len(), 1);
+        let versions = |attachment: &Value| -> Vec<i64> {
+            attachment["versions"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|version| version["version"].as_i64().unwrap())
+                .collect()
+        };
+        assert_eq!(versions(&attachments[0]), [2, 1]);
+        let attachment_id = attachments[0]["id"].as_str().unwrap().to_string();
+
+        delete_attachment_version(
+            app.handle().clone(),
+            app.state(),
+            serde_json::from_value(json!({
+                "boardId": "b1",
+                "cardId": "k1",
+                "attachmentId": attachment_id,
+                "targetVersion": 1,
+            }))
+            .unwrap(),
+        )
+        .await
+        .unwrap();
 
-    let mut hasher = Sha256::new();
-    use std::io::Read;
-    let mut buffer = [0u8; 8192];
-    loop {
-        let bytes_read = file_reader
-            .read(&mut buffer)
-            .map_err(|e| format!("Failed to read file for checksum: {e}"))?;
-        if bytes_read == 0 {
-            break;
-        }
-        hasher.update(&buffer[..bytes_read]);
+        let attachments = list(app.state()).await.unwrap();
+        assert_eq!(attachments[0]["id"], attachment_id.as_str());
+        assert_eq!(versions(&attachments[0]), [2]);
+        let _ = fs::remove_dir_all(&source_dir);
     }
 
-    let checksum = format!("{:x}", hasher.finalize());
-
-    let now = chrono::Utc::now().to_rfc3339();
-    let mime_string = mime_type.essence_str().to_string();
+    #[tokio::test]
+    async fn export_board_round_trips_through_import() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_export_board(&pool).await;
 
-    let mut tx = pool.begin().await.map_err(|e| {
-        println!("Failed to begin transaction: {}", e);
-        format!("Failed to begin transaction: {e}")
-    })?;
+        let exported = export_board(app.state(), "b1".into()).await.unwrap();
+        assert_eq!(exported["schemaVersion"], BOARD_EXPORT_SCHEMA_VERSION);
 
-    // Maintain legacy attachment JSON for existing clients
-    let existing_attachments: Option<String> =
-        sqlx::query_scalar("SELECT attachments FROM kanban_cards WHERE id = ? AND board_id = ?")
-            .bind(&card_id)
-            .bind(&board_id)
-            .fetch_one(&mut *tx)
+        let imported_id = import_board(app.state(), exported.clone(), DEFAULT_WORKSPACE_ID.into())
             .await
-            .map_err(|e| {
-                println!("Failed to fetch legacy attachments: {}", e);
-                format!("Failed to fetch legacy attachments: {e}")
-            })?;
-
-    let mut attachments_vec: Vec<String> = existing_attachments
-        .as_deref()
-        .and_then(|json_str| serde_json::from_str(json_str).ok())
-        .unwrap_or_default();
-    attachments_vec.push(relative_path.clone());
-
-    let attachments_json = serde_json::to_string(&attachments_vec).map_err(|e| {
-        println!("Failed to serialize attachments JSON: {}", e);
-        format!("Failed to serialize attachments JSON: {e}")
-    })?;
+            .unwrap();
+        assert_ne!(imported_id, "b1");
+
+        let reexported = export_board(app.state(), imported_id).await.unwrap();
+        assert_eq!(export_shape(&reexported), export_shape(&exported));
+        assert_eq!(
+            export_shape(&exported)["cards"][0]["tags"],
+            json!(["Bug", "UX"])
+        );
 
-    sqlx::query(
-        "UPDATE kanban_cards SET attachments = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ? AND board_id = ?",
-    )
-    .bind(&attachments_json)
-    .bind(&card_id)
-    .bind(&board_id)
-    .execute(&mut *tx)
-    .await
-    .map_err(|e| {
-        println!("Failed to update legacy attachments column: {}", e);
-        format!("Failed to update legacy attachments column: {e}")
-    })?;
+        let mut future = exported;
+        future["schemaVersion"] = json!(BOARD_EXPORT_SCHEMA_VERSION + 1);
+        assert!(
+            import_board(app.state(), future, DEFAULT_WORKSPACE_ID.into())
+                .await
+                .is_err()
+        );
+    }
 
-    let attachment_id = Uuid::new_v4().to_string();
-    let version = 1i64;
+    #[tokio::test]
+    async fn validate_import_names_a_missing_column() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_export_board(&pool).await;
+        let mut document = export_board(app.state(), "b1".into()).await.unwrap();
+
+        let valid = validate_import(document.clone()).unwrap();
+        document["cards"][0]["columnId"] = json!("c-missing");
+        let report = validate_import(document).unwrap();
+
+        assert_eq!(valid["ok"], true);
+        assert_eq!(report["ok"], false);
+        let problems = report["problems"].as_array().unwrap();
+        assert_eq!(problems.len(), 1);
+        assert!(
+            problems[0]
+                .as_str()
+                .unwrap()
+                .ends_with("referencia a coluna c-missing, que não existe no documento.")
+        );
+        assert_eq!(report["summary"]["cards"], 2);
+    }
+
+    #[tokio::test]
+    async fn insert_cards_at_places_batch_mid_column() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_card(&pool, "b1", "c1", "k2", "Último", 1).await;
+        let new_cards = |titles: &[&str], priority: &str| -> Vec<NewCard> {
+            titles
+                .iter()
+                .map(|title| {
+                    serde_json::from_value(json!({ "title": title, "priority": priority })).unwrap()
+                })
+                .collect()
+        };
 
-    sqlx::query(
-        "INSERT INTO kanban_attachments (
-            id, card_id, board_id, version, filename, original_name, mime_type, size_bytes,
-            checksum, storage_path, thumbnail_path, created_at, updated_at
-        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
-    )
-    .bind(&attachment_id)
-    .bind(&card_id)
-    .bind(&board_id)
-    .bind(version)
-    .bind(&original_name)
-    .bind(&original_name)
-    .bind(&mime_string)
-    .bind(file_size)
-    .bind(&checksum)
-    .bind(&relative_path)
-    .bind(Option::<String>::None)
-    .bind(&now)
-    .bind(&now)
-    .execute(&mut *tx)
-    .await
-    .map_err(|e| {
-        println!("Failed to insert attachment metadata: {}", e);
-        format!("Failed to insert attachment metadata: {e}")
-    })?;
+        let created = insert_cards_at(
+            app.handle().clone(),
+            app.state(),
+            "b1".into(),
+            "c1".into(),
+            new_cards(&["A", "B", "C"], "low"),
+            1,
+        )
+        .await
+        .unwrap();
+        assert_eq!(created.len(), 3);
 
-    tx.commit()
+        let order = sqlx::query_as::<_, (String, i64)>(
+            "SELECT title, position FROM kanban_cards WHERE column_id = 'c1' ORDER BY position",
+        )
+        .fetch_all(&*pool)
         .await
-        .map_err(|e| format!("Failed to commit transaction: {e}"))?;
+        .unwrap();
+        assert_eq!(
+            order,
+            [
+                ("Card".to_string(), 0),
+                ("A".to_string(), 1),
+                ("B".to_string(), 2),
+                ("C".to_string(), 3),
+                ("Último".to_string(), 4),
+            ]
+        );
 
-    println!("Attachment uploaded successfully: {}", relative_path);
+        assert!(
+            insert_cards_at(
+                app.handle().clone(),
+                app.state(),
+                "b1".into(),
+                "c1".into(),
+                new_cards(&["D"], "urgent"),
+                0,
+            )
+            .await
+            .is_err()
+        );
+        let count = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM kanban_cards")
+            .fetch_one(&*pool)
+            .await
+            .unwrap();
+        assert_eq!(count, 5);
+    }
+
+    #[tokio::test]
+    async fn sidebar_badges_skip_done_columns() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_column(&pool, "b1", "c2", "Done", 1).await;
+        seed_card(&pool, "b1", "c1", "k2", "Amanhã", 1).await;
+        seed_card(&pool, "b1", "c2", "k3", "Concluída atrasada", 0).await;
+        seed_card(&pool, "b1", "c2", "k4", "Concluída amanhã", 1).await;
+        let tomorrow = (Utc::now() + chrono::Duration::days(1)).to_rfc3339();
+        sqlx::query(
+            "UPDATE kanban_cards SET due_date = CASE WHEN id IN ('k1', 'k3') THEN '2020-01-01T00:00:00Z' ELSE ? END",
+        )
+        .bind(&tomorrow)
+        .execute(&*pool)
+        .await
+        .unwrap();
 
-    Ok(UploadImageResponse {
-        success: true,
-        file_path: relative_path.clone(),
-        attachment: Some(json!({
-            "id": attachment_id,
-            "boardId": board_id,
-            "cardId": card_id,
-            "version": version,
-            "filename": original_name.clone(),
-            "originalName": original_name,
-            "mimeType": mime_string,
-            "sizeBytes": file_size,
-            "checksum": checksum,
-            "storagePath": relative_path,
-            "thumbnailPath": Value::Null,
-            "createdAt": now,
-            "updatedAt": now,
-        })),
-        error: None,
-    })
-}
+        let badges = get_sidebar_badges(app.handle().clone(), app.state())
+            .await
+            .unwrap();
 
-#[tauri::command]
-#[allow(dead_code)]
-async fn list_card_attachments(
-    pool: State<'_, DbPool>,
-    args: ListAttachmentsArgs,
-) -> Result<Value, String> {
-    let attachments = sqlx::query(
-        "SELECT id, card_id, board_id, version, filename, original_name, mime_type, size_bytes, checksum, storage_path, thumbnail_path, created_at, updated_at FROM kanban_attachments WHERE board_id = ? AND card_id = ? ORDER BY created_at DESC, version DESC",
-    )
-    .bind(&args.board_id)
-    .bind(&args.card_id)
-    .map(|row: SqliteRow| AttachmentRecord::from_row(row))
-    .fetch_all(&*pool)
-    .await
-    .map_err(|e| format!("Failed to load attachments: {e}"))?
-    .into_iter()
-    .collect::<Result<Vec<_>, _>>()
-    .map_err(|e| format!("Failed to map attachment row: {e}"))?;
+        assert_eq!(
+            badges,
+            [json!({ "boardId": "b1", "overdueCount": 1, "dueSoonCount": 1, "totalActive": 4 })]
+        );
+    }
 
-    Ok(Value::Array(
-        attachments
-            .into_iter()
-            .map(AttachmentRecord::into_json)
-            .collect(),
-    ))
-}
+    #[tokio::test]
+    async fn exported_file_imports_with_tags() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_export_board(&pool).await;
+        let destination = std::env::temp_dir()
+            .join(format!("modulo-export-{}", Uuid::new_v4()))
+            .join("board.json");
 
-#[tauri::command]
-async fn remove_image(
-    app: AppHandle,
-    pool: State<'_, DbPool>,
-    card_id: String,
-    board_id: String,
-    file_path: String,
-) -> Result<(), String> {
-    let app_data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to resolve app data directory: {e}"))?;
+        let summary = export_board_to_file(
+            app.state(),
+            "b1".into(),
+            destination.to_string_lossy().into_owned(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(summary["cardCount"], 2);
+
+        // Same document as export_board, with no timestamp that would break diffs
+        let document: Value = serde_json::from_slice(&fs::read(&destination).unwrap()).unwrap();
+        assert_eq!(
+            document,
+            export_board(app.state(), "b1".into()).await.unwrap()
+        );
 
-    let mut tx = pool
-        .begin()
+        let imported_id = import_board(app.state(), document, DEFAULT_WORKSPACE_ID.into())
+            .await
+            .unwrap();
+        let tag_links = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM kanban_card_tags ct JOIN kanban_cards c ON c.id = ct.card_id WHERE c.board_id = ?",
+        )
+        .bind(&imported_id)
+        .fetch_one(&*pool)
         .await
-        .map_err(|e| format!("Failed to begin transaction: {e}"))?;
+        .unwrap();
+        assert_eq!(tag_links, 3);
+        let _ = fs::remove_dir_all(destination.parent().unwrap());
+    }
 
-    let attachment_row: Option<(String, i64)> = sqlx::query_as(
-        "SELECT id, version FROM kanban_attachments WHERE card_id = ? AND board_id = ? AND storage_path = ? ORDER BY version DESC LIMIT 1",
-    )
-    .bind(&card_id)
-    .bind(&board_id)
-    .bind(&file_path)
-    .fetch_optional(&mut *tx)
-    .await
-    .map_err(|e| format!("Failed to look up attachment metadata: {e}"))?;
+    #[tokio::test]
+    async fn import_board_restores_notes() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_export_board(&pool).await;
 
-    if let Some((_id, _version)) = attachment_row {
+        let exported = export_board(app.state(), "b1".into()).await.unwrap();
+        let imported_id = import_board(app.state(), exported, DEFAULT_WORKSPACE_ID.into())
+            .await
+            .unwrap();
+
+        let notes = load_notes(app.state(), imported_id).await.unwrap();
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0]["title"], "Ata");
+        assert_eq!(notes[0]["content"], "Decisões");
+        assert_eq!(notes[0]["pinned"], true);
+        assert_eq!(notes[0]["tags"], json!(["reunião"]));
+        assert_ne!(notes[0]["id"], "n1");
+    }
+
+    #[tokio::test]
+    async fn import_board_keeps_recurring_reminders() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_export_board(&pool).await;
         sqlx::query(
-            "DELETE FROM kanban_attachments WHERE card_id = ? AND board_id = ? AND storage_path = ?",
+            "UPDATE kanban_cards SET remind_at = '2030-01-01T09:00:00.000Z', remind_recurrence = 'weekly' WHERE id = 'k1'",
         )
-        .bind(&card_id)
-        .bind(&board_id)
-        .bind(&file_path)
-        .execute(&mut *tx)
+        .execute(&*pool)
+        .await
+        .unwrap();
+
+        let exported = export_board(app.state(), "b1".into()).await.unwrap();
+        let imported_id = import_board(app.state(), exported, DEFAULT_WORKSPACE_ID.into())
+            .await
+            .unwrap();
+
+        let cards = load_board_cards(&app, &imported_id).await;
+        let card = cards.iter().find(|card| card["title"] == "Card").unwrap();
+        assert_eq!(card["remindAt"], "2030-01-01T09:00:00.000Z");
+        assert_eq!(card["remindRecurrence"], "weekly");
+    }
+
+    #[tokio::test]
+    async fn validate_import_applies_the_create_command_rules() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_export_board(&pool).await;
+        let mut document = export_board(app.state(), "b1".into()).await.unwrap();
+        document["columns"][0]["wipMode"] = json!("strict");
+        document["tags"][0]["label"] = json!("x".repeat(101));
+        document["cards"][0]["title"] = json!("x".repeat(201));
+        document["cards"][0]["remindAt"] = json!("amanhã");
+        document["cards"][0]["remindRecurrence"] = json!("hourly");
+
+        let report = validate_import(document.clone()).unwrap();
+        let imported = import_board(app.state(), document, DEFAULT_WORKSPACE_ID.into()).await;
+
+        assert_eq!(report["ok"], false);
+        assert_eq!(report["problems"].as_array().unwrap().len(), 5);
+        assert!(imported.is_err());
+    }
+
+    #[tokio::test]
+    async fn toggle_card_archived_returns_refreshed_column() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_card(&pool, "b1", "c1", "k2", "Meio", 1).await;
+        seed_card(&pool, "b1", "c1", "k3", "Fim", 2).await;
+
+        let view =
+            toggle_card_archived(app.handle().clone(), app.state(), "b1".into(), "k2".into())
+                .await
+                .unwrap();
+        assert_eq!(view["archived"], true);
+        assert_eq!(titles(view["cards"].as_array().unwrap()), ["Card", "Fim"]);
+        // Archived cards keep their slot, so positions stay contiguous across the column
+        let positions = sqlx::query_scalar::<_, i64>(
+            "SELECT position FROM kanban_cards WHERE column_id = 'c1' ORDER BY position",
+        )
+        .fetch_all(&*pool)
         .await
-        .map_err(|e| format!("Failed to delete attachment metadata: {e}"))?;
+        .unwrap();
+        assert_eq!(positions, [0, 1, 2]);
+
+        let view =
+            toggle_card_archived(app.handle().clone(), app.state(), "b1".into(), "k2".into())
+                .await
+                .unwrap();
+        assert_eq!(view["archived"], false);
+        assert_eq!(
+            titles(view["cards"].as_array().unwrap()),
+            ["Card", "Fim", "Meio"]
+        );
     }
 
-    let existing_attachments: Option<String> =
-        sqlx::query_scalar("SELECT attachments FROM kanban_cards WHERE id = ? AND board_id = ?")
-            .bind(&card_id)
-            .bind(&board_id)
-            .fetch_one(&mut *tx)
+    #[tokio::test]
+    async fn reminder_before_due_is_derived_from_the_due_date() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_card(&pool, "b1", "c1", "k2", "Sem prazo", 1).await;
+        sqlx::query("UPDATE kanban_cards SET due_date = '2999-06-01T12:00:00Z' WHERE id = 'k1'")
+            .execute(&*pool)
             .await
-            .map_err(|e| format!("Failed to fetch existing attachments: {e}"))?;
+            .unwrap();
+
+        let reminder = set_reminder_before_due(
+            app.handle().clone(),
+            app.state(),
+            "b1".into(),
+            "k1".into(),
+            60,
+        )
+        .await
+        .unwrap();
+        assert_eq!(reminder["remindAt"], "2999-06-01T11:00:00.000Z");
+        let stored =
+            sqlx::query_scalar::<_, String>("SELECT remind_at FROM kanban_cards WHERE id = 'k1'")
+                .fetch_one(&*pool)
+                .await
+                .unwrap();
+        assert_eq!(stored, "2999-06-01T11:00:00.000Z");
+
+        let missing_due = set_reminder_before_due(
+            app.handle().clone(),
+            app.state(),
+            "b1".into(),
+            "k2".into(),
+            60,
+        )
+        .await;
+        assert_eq!(
+            missing_due.unwrap_err(),
+            "O cartão não possui data de vencimento."
+        );
 
-    let mut attachments_vec: Vec<String> = existing_attachments
-        .as_deref()
-        .and_then(|json_str| serde_json::from_str(json_str).ok())
-        .unwrap_or_default();
-    attachments_vec.retain(|path| path != &file_path);
+        sqlx::query("UPDATE kanban_cards SET due_date = '2020-01-01T00:00:00Z' WHERE id = 'k2'")
+            .execute(&*pool)
+            .await
+            .unwrap();
+        let past = set_reminder_before_due(
+            app.handle().clone(),
+            app.state(),
+            "b1".into(),
+            "k2".into(),
+            60,
+        )
+        .await;
+        assert_eq!(past.unwrap_err(), "O lembrete calculado já passou.");
+    }
+
+    #[tokio::test]
+    async fn board_attachments_list_current_versions_newest_first() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_card(&pool, "b1", "c1", "k2", "Contrato", 1).await;
+        seed_board(&pool, "b2").await;
+        seed_column(&pool, "b2", "c9", "Outro", 0).await;
+        seed_card(&pool, "b2", "c9", "k9", "Externo", 0).await;
+        for (id, card_id, board_id, version, mime, created_at) in [
+            ("a1", "k1", "b1", 1, "image/png", "2024-01-01T00:00:00Z"),
+            ("a1", "k1", "b1", 2, "image/png", "2024-01-03T00:00:00Z"),
+            (
+                "a2",
+                "k2",
+                "b1",
+                1,
+                "application/pdf",
+                "2024-01-02T00:00:00Z",
+            ),
+            ("a9", "k9", "b2", 1, "image/png", "2024-01-04T00:00:00Z"),
+        ] {
+            sqlx::query(
+                "INSERT INTO kanban_attachments (id, card_id, board_id, version, filename, original_name, mime_type, storage_path, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(id)
+            .bind(card_id)
+            .bind(board_id)
+            .bind(version)
+            .bind(id)
+            .bind(id)
+            .bind(mime)
+            .bind(format!("attachments/{card_id}/{id}-v{version}"))
+            .bind(created_at)
+            .bind(created_at)
+            .execute(&*pool)
+            .await
+            .unwrap();
+        }
 
-    let attachments_json = serde_json::to_string(&attachments_vec)
-        .map_err(|e| format!("Failed to serialize attachments: {e}"))?;
+        let gallery = list_board_attachments(app.state(), "b1".into())
+            .await
+            .unwrap();
 
-    sqlx::query(
-        "UPDATE kanban_cards SET attachments = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ? AND board_id = ?",
-    )
-    .bind(&attachments_json)
-    .bind(&card_id)
-    .bind(&board_id)
-    .execute(&mut *tx)
-    .await
-    .map_err(|e| format!("Failed to update card attachments: {e}"))?;
+        let summary: Vec<Value> = gallery
+            .iter()
+            .map(|item| json!([item["id"], item["version"], item["cardTitle"], item["kind"]]))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                json!(["a1", 2, "Card", "image"]),
+                json!(["a2", 1, "Contrato", "document"]),
+            ]
+        );
+    }
 
-    tx.commit()
-        .await
-        .map_err(|e| format!("Failed to commit transaction: {e}"))?;
+    #[tokio::test]
+    async fn consolidate_workspace_icons_keeps_only_the_current_icon() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        let icons_dir = app.path().app_data_dir().unwrap().join(WORKSPACE_ICON_DIR);
+        fs::create_dir_all(&icons_dir).unwrap();
+        let current = format!("{WORKSPACE_ICON_DIR}/w1_cropped_3.png");
+        sqlx::query("INSERT INTO workspaces (id, name, icon_path) VALUES ('w1', 'Pessoal', ?)")
+            .bind(&current)
+            .execute(&*pool)
+            .await
+            .unwrap();
+        let old = SystemTime::now() - WORKSPACE_ICON_SWEEP_GRACE * 2;
+        for (filename, modified) in [
+            ("w1_cropped_1.png", old),
+            ("w1_cropped_2.png", old),
+            ("w1_cropped_3.png", old),
+            ("w1_cropped_4.png", SystemTime::now()),
+            ("w2_cropped_1.png", old),
+        ] {
+            let file = File::create(icons_dir.join(filename)).unwrap();
+            file.set_modified(modified).unwrap();
+        }
 
-    let remaining_references: i64 =
-        sqlx::query_scalar("SELECT COUNT(*) FROM kanban_attachments WHERE storage_path = ?")
-            .bind(&file_path)
-            .fetch_one(&*pool)
+        let removed = consolidate_workspace_icons(app.handle().clone(), app.state())
             .await
-            .map_err(|e| format!("Failed to check attachment references: {e}"))?;
+            .unwrap();
+
+        let mut remaining: Vec<String> = fs::read_dir(&icons_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        remaining.sort();
+        assert_eq!(removed, 2);
+        assert_eq!(
+            remaining,
+            ["w1_cropped_3.png", "w1_cropped_4.png", "w2_cropped_1.png"]
+        );
+    }
 
-    if remaining_references == 0 {
-        let full_file_path = app_data_dir.join(&file_path);
-        if full_file_path.exists()
-            && let Err(e) = fs::remove_file(&full_file_path)
-        {
-            eprintln!(
-                "Warning: Failed to delete file {}: {}",
-                full_file_path.display(),
-                e
-            );
+    #[tokio::test]
+    async fn orphaned_attachment_sweep_spares_recent_uploads() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        let app_data_dir = app.path().app_data_dir().unwrap();
+        let attachments_dir = app_data_dir.join("attachments");
+        fs::create_dir_all(&attachments_dir).unwrap();
+        seed_attachment(&pool, "a1", "k1", "attachments/kept.png").await;
+        let old = SystemTime::now() - ATTACHMENT_SWEEP_GRACE * 2;
+        for (filename, modified) in [
+            ("kept.png", old),
+            ("orphan.png", old),
+            ("uploading.png", SystemTime::now()),
+        ] {
+            let file = File::create(attachments_dir.join(filename)).unwrap();
+            file.set_modified(modified).unwrap();
+        }
+
+        let report = cleanup_orphaned_attachments(app.handle().clone(), app.state())
+            .await
+            .unwrap();
+
+        let mut remaining: Vec<String> = fs::read_dir(&attachments_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        remaining.sort();
+        assert_eq!(report["removedFiles"], 1);
+        assert_eq!(remaining, ["kept.png", "uploading.png"]);
+    }
+
+    #[tokio::test]
+    async fn workspace_storage_sums_attachments_across_its_boards() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        let app_data_dir = app.path().app_data_dir().unwrap();
+        fs::create_dir_all(app_data_dir.join(WORKSPACE_ICON_DIR)).unwrap();
+        let icon = format!("{WORKSPACE_ICON_DIR}/w1.png");
+        fs::write(app_data_dir.join(&icon), b"icon").unwrap();
+        sqlx::query("INSERT INTO workspaces (id, name, icon_path) VALUES ('w1', 'Pessoal', ?)")
+            .bind(&icon)
+            .execute(&*pool)
+            .await
+            .unwrap();
+        for (board_id, workspace_id) in [("b1", "w1"), ("b2", "w1"), ("b3", DEFAULT_WORKSPACE_ID)] {
+            seed_board(&pool, board_id).await;
+            seed_column(&pool, board_id, &format!("{board_id}-c"), "To Do", 0).await;
+            seed_card(
+                &pool,
+                board_id,
+                &format!("{board_id}-c"),
+                &format!("{board_id}-k"),
+                "Card",
+                0,
+            )
+            .await;
+            sqlx::query("UPDATE kanban_boards SET workspace_id = ? WHERE id = ?")
+                .bind(workspace_id)
+                .bind(board_id)
+                .execute(&*pool)
+                .await
+                .unwrap();
         }
+        for (id, board_id, version, size, is_external) in [
+            ("a1", "b1", 1, 10, 0),
+            ("a1", "b1", 2, 20, 0),
+            ("a2", "b2", 1, 5, 0),
+            ("a3", "b1", 1, 100, 1),
+            ("a4", "b3", 1, 1000, 0),
+        ] {
+            sqlx::query(
+                "INSERT INTO kanban_attachments (id, card_id, board_id, version, filename, original_name, storage_path, size_bytes, is_external) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(id)
+            .bind(format!("{board_id}-k"))
+            .bind(board_id)
+            .bind(version)
+            .bind(id)
+            .bind(id)
+            .bind(format!("{id}-v{version}"))
+            .bind(size)
+            .bind(is_external)
+            .execute(&*pool)
+            .await
+            .unwrap();
+        }
+
+        let storage = get_workspace_storage(app.handle().clone(), app.state(), "w1".into())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            storage,
+            json!({
+                "workspaceId": "w1",
+                "attachmentCount": 3,
+                "attachmentBytes": 35,
+                "iconBytes": 4,
+                "totalBytes": 39,
+            })
+        );
     }
 
-    Ok(())
-}
+    #[tokio::test]
+    async fn unique_column_titles_follow_the_preference() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        let handle = app.handle().clone();
+        seed_basic_board(&pool).await;
+        let create = |id: &'static str, title: &'static str, position: i64| {
+            create_column(
+                handle.clone(),
+                app.state(),
+                id.into(),
+                "b1".into(),
+                title.into(),
+                position,
+                None,
+                None,
+                None,
+                None,
+            )
+        };
 
-#[tauri::command]
-async fn get_attachment_url(app: AppHandle, file_path: String) -> Result<String, String> {
-    // Read the image file and convert to base64
-    let app_data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to resolve app data directory: {e}"))?;
+        create("c2", "to do", 1).await.unwrap();
 
-    let full_path = app_data_dir.join(&file_path);
-    if !full_path.exists() {
-        return Err(format!("File does not exist: {:?}", full_path));
+        let mut preferences = load_preferences(handle.clone()).await.unwrap();
+        preferences.enforce_unique_column_titles = true;
+        save_preferences(handle.clone(), preferences).await.unwrap();
+
+        let duplicate = create("c3", " TO DO ", 2).await;
+        assert!(matches!(
+            duplicate,
+            Err(CommandError::Validation(message)) if message == "Já existe uma coluna chamada \"TO DO\" neste quadro."
+        ));
+        create("c3", "Doing", 2).await.unwrap();
+
+        let rename = |title: &str| {
+            update_column(
+                handle.clone(),
+                app.state(),
+                serde_json::from_value(json!({ "id": "c3", "boardId": "b1", "title": title }))
+                    .unwrap(),
+            )
+        };
+        assert!(matches!(
+            rename("To Do").await,
+            Err(CommandError::Validation(_))
+        ));
+        rename("doing").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn activity_for_entity_is_chronological() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        for (id, entity_id, activity_type, created_at) in [
+            ("a2", "k1", "card_moved", "2024-01-02T00:00:00Z"),
+            ("a1", "k1", "card_created", "2024-01-01T00:00:00Z"),
+            ("a3", "k2", "card_created", "2024-01-01T12:00:00Z"),
+            ("a4", "k1", "card_archived", "2024-01-03T00:00:00Z"),
+        ] {
+            sqlx::query(
+                "INSERT INTO kanban_activity (id, board_id, entity_type, entity_id, activity_type, title, created_at) VALUES (?, 'b1', 'card', ?, ?, 'Card', ?)",
+            )
+            .bind(id)
+            .bind(entity_id)
+            .bind(activity_type)
+            .bind(created_at)
+            .execute(&*pool)
+            .await
+            .unwrap();
+        }
+
+        let timeline = get_activity_for_entity(app.state(), "card".into(), "k1".into(), None)
+            .await
+            .unwrap();
+        let types: Vec<&str> = timeline
+            .iter()
+            .map(|entry| entry.activity_type.as_str())
+            .collect();
+        assert_eq!(types, ["card_created", "card_moved", "card_archived"]);
+
+        assert!(
+            get_activity_for_entity(app.state(), "column".into(), "c1".into(), None)
+                .await
+                .is_err()
+        );
     }
 
-    let image_data = std::fs::read(&full_path).map_err(|e| format!("Failed to read file: {e}"))?;
+    #[tokio::test]
+    async fn next_position_follows_each_scope() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_card(&pool, "b1", "c1", "k2", "Dois", 1).await;
+        seed_card(&pool, "b1", "c1", "k3", "Três", 2).await;
 
-    // Determine MIME type from file extension
-    let extension = full_path
-        .extension()
-        .and_then(|ext| ext.to_str())
-        .unwrap_or("");
+        let next = |scope: &str, parent_id: &str| {
+            get_next_position(app.state(), scope.into(), parent_id.into())
+        };
+        assert_eq!(next("card", "c1").await.unwrap(), 3);
+        assert_eq!(next("column", "b1").await.unwrap(), 1);
+        assert_eq!(next("subtask", "k1").await.unwrap(), 0);
+        assert_eq!(
+            next("tag", "b1").await.unwrap_err(),
+            "Escopo inválido. Utilize 'card', 'column' ou 'subtask'."
+        );
+    }
 
-    let mime_type = match extension.to_lowercase().as_str() {
-        "jpg" | "jpeg" => "image/jpeg",
-        "png" => "image/png",
-        "gif" => "image/gif",
-        "webp" => "image/webp",
-        "svg" => "image/svg+xml",
-        "bmp" => "image/bmp",
-        "ico" => "image/x-icon",
-        _ => "image/jpeg",
-    };
+    #[tokio::test]
+    async fn unarchive_all_cards_restores_them_to_column_ends() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        for (id, title, position) in [("k2", "A", 1), ("k3", "B", 2), ("k4", "C", 3)] {
+            seed_card(&pool, "b1", "c1", id, title, position).await;
+        }
+        for card_id in ["k1", "k3", "k4"] {
+            toggle_card_archived(
+                app.handle().clone(),
+                app.state(),
+                "b1".into(),
+                card_id.into(),
+            )
+            .await
+            .unwrap();
+        }
+        assert_eq!(titles(&load_board_cards(&app, "b1").await), ["A"]);
 
-    let base64_data = general_purpose::STANDARD.encode(&image_data);
-    let data_url = format!("data:{};base64,{}", mime_type, base64_data);
+        let restored = unarchive_all_cards(app.handle().clone(), app.state(), "b1".into())
+            .await
+            .unwrap();
+        assert_eq!(restored, 3);
 
-    Ok(data_url)
-}
+        let cards = load_board_cards(&app, "b1").await;
+        assert_eq!(titles(&cards), ["A", "Card", "B", "C"]);
+        let positions: Vec<i64> = cards
+            .iter()
+            .map(|card| card["position"].as_i64().unwrap())
+            .collect();
+        assert_eq!(positions, [0, 1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn due_date_writes_are_validated_on_every_path() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        let stored_due = |card_id: &'static str| {
+            sqlx::query_scalar::<_, Option<String>>(
+                "SELECT due_date FROM kanban_cards WHERE id = ?",
+            )
+            .bind(card_id)
+            .fetch_one(&*pool)
+        };
+        let invalid = "Data inválida: \"amanhã\". Utilize o formato ISO 8601 (RFC 3339).";
+
+        // save_card, insert path
+        let error = save_card(
+            app.handle().clone(),
+            app.state(),
+            save_card_args("Novo", Some("amanhã")),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(error.to_string(), invalid);
+        save_card(
+            app.handle().clone(),
+            app.state(),
+            save_card_args("Novo", Some(" 2030-01-02 ")),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            stored_due("k-sync").await.unwrap().as_deref(),
+            Some("2030-01-02")
+        );
+
+        // save_card, update path
+        let error = save_card(
+            app.handle().clone(),
+            app.state(),
+            save_card_args("Novo", Some("amanhã")),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(error.to_string(), invalid);
+        assert_eq!(
+            stored_due("k-sync").await.unwrap().as_deref(),
+            Some("2030-01-02")
+        );
+        save_card(
+            app.handle().clone(),
+            app.state(),
+            save_card_args("Novo", Some("")),
+        )
+        .await
+        .unwrap();
+        assert_eq!(stored_due("k-sync").await.unwrap(), None);
+
+        // insert_cards_at
+        let batch = |due_date: &str| -> Vec<NewCard> {
+            vec![serde_json::from_value(json!({ "title": "Lote", "dueDate": due_date })).unwrap()]
+        };
+        assert_eq!(
+            insert_cards_at(
+                app.handle().clone(),
+                app.state(),
+                "b1".into(),
+                "c1".into(),
+                batch("amanhã"),
+                0
+            )
+            .await
+            .unwrap_err(),
+            invalid
+        );
+        let created = insert_cards_at(
+            app.handle().clone(),
+            app.state(),
+            "b1".into(),
+            "c1".into(),
+            batch("2030-01-02T09:00:00Z"),
+            0,
+        )
+        .await
+        .unwrap();
+        let due = sqlx::query_scalar::<_, Option<String>>(
+            "SELECT due_date FROM kanban_cards WHERE id = ?",
+        )
+        .bind(&created[0])
+        .fetch_one(&*pool)
+        .await
+        .unwrap();
+        assert_eq!(due.as_deref(), Some("2030-01-02T09:00:00Z"));
+
+        // import_board
+        let mut document = export_board(app.state(), "b1".into()).await.unwrap();
+        let card_index = document["cards"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .position(|card| card["title"] == "Card")
+            .unwrap();
+        document["cards"][card_index]["dueDate"] = json!("amanhã");
+        let error = import_board(app.state(), document.clone(), DEFAULT_WORKSPACE_ID.into())
+            .await
+            .unwrap_err();
+        assert!(error.contains("possui data de vencimento inválida: amanhã."));
+        document["cards"][card_index]["dueDate"] = json!("  ");
+        let imported_id = import_board(app.state(), document, DEFAULT_WORKSPACE_ID.into())
+            .await
+            .unwrap();
+        let imported_dues = sqlx::query_scalar::<_, Option<String>>(
+            "SELECT due_date FROM kanban_cards WHERE board_id = ? AND due_date IS NOT NULL",
+        )
+        .bind(&imported_id)
+        .fetch_all(&*pool)
+        .await
+        .unwrap();
+        assert_eq!(imported_dues, [Some("2030-01-02T09:00:00Z".to_string())]);
+    }
+
+    #[tokio::test]
+    async fn home_dashboard_combines_all_sections() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_card(&pool, "b1", "c1", "k2", "Sem prazo", 1).await;
+        let tomorrow = (Utc::now() + chrono::Duration::days(1)).to_rfc3339();
+        sqlx::query("UPDATE kanban_cards SET due_date = ? WHERE id = 'k1'")
+            .bind(&tomorrow)
+            .execute(&*pool)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE kanban_boards SET is_favorite = 1 WHERE id = 'b1'")
+            .execute(&*pool)
+            .await
+            .unwrap();
+        for (id, created_at) in [
+            ("a1", "2024-01-01T00:00:00Z"),
+            ("a2", "2024-01-02T00:00:00Z"),
+        ] {
+            sqlx::query(
+                "INSERT INTO kanban_activity (id, board_id, entity_type, entity_id, activity_type, title, created_at) VALUES (?, 'b1', 'card', 'k1', 'card_created', 'Card', ?)",
+            )
+            .bind(id)
+            .bind(created_at)
+            .execute(&*pool)
+            .await
+            .unwrap();
+        }
+
+        let dashboard = get_home_dashboard(app.handle().clone(), app.state(), Some(3), Some(1))
+            .await
+            .unwrap();
+
+        let mut sections: Vec<&String> = dashboard.as_object().unwrap().keys().collect();
+        sections.sort();
+        assert_eq!(
+            sections,
+            [
+                "favoriteBoards",
+                "recentActivity",
+                "statistics",
+                "upcomingDeadlines"
+            ]
+        );
+        assert_eq!(dashboard["statistics"]["totalProjects"], 1);
+        assert!(dashboard["statistics"]["overdueTasks"].is_i64());
+
+        let activity = dashboard["recentActivity"].as_array().unwrap();
+        assert_eq!(activity.len(), 1);
+        assert_eq!(activity[0]["id"], "a2");
+        assert_eq!(activity[0]["boardName"], "Quadro b1");
+
+        let favorites = dashboard["favoriteBoards"].as_array().unwrap();
+        assert_eq!(favorites.len(), 1);
+        assert_eq!(favorites[0]["id"], "b1");
+        assert_eq!(favorites[0]["activeCards"], 2);
+
+        let deadlines = dashboard["upcomingDeadlines"].as_array().unwrap();
+        assert_eq!(deadlines.len(), 1);
+        assert_eq!(deadlines[0]["id"], "k1");
+        assert_eq!(deadlines[0]["isOverdue"], false);
+    }
+
+    #[tokio::test]
+    async fn upload_skips_identical_content_and_versions_by_name() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        let dir = std::env::temp_dir().join(format!("modulo-upload-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let first = upload_test_file(&app, &dir, "plano.txt", b"v1").await;
+        let first_id = first.attachment.unwrap()["id"].clone();
+
+        let renamed_copy = upload_test_file(&app, &dir, "copia.txt", b"v1").await;
+        assert!(renamed_copy.duplicate);
+        assert_eq!(renamed_copy.attachment.unwrap()["id"], first_id);
+
+        let new_version = upload_test_file(&app, &dir, "plano.txt", b"v2").await;
+        assert!(!new_version.duplicate);
+        let new_version = new_version.attachment.unwrap();
+        assert_eq!(new_version["id"], first_id);
+        assert_eq!(new_version["version"], 2);
+
+        // Matches only a superseded version of plano.txt, so it is a new attachment
+        let other = upload_test_file(&app, &dir, "outro.txt", b"v1").await;
+        assert!(!other.duplicate);
+        let other = other.attachment.unwrap();
+        assert_ne!(other["id"], first_id);
+        assert_eq!(other["version"], 1);
+
+        let rows = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM kanban_attachments")
+            .fetch_one(&*pool)
+            .await
+            .unwrap();
+        assert_eq!(rows, 3);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn update_note_rejects_empty_and_overlong_titles() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_board(&pool, "b1").await;
+        sqlx::query(
+            "INSERT INTO notes (id, board_id, title, content) VALUES ('n1', 'b1', 'Ata', '')",
+        )
+        .execute(&*pool)
+        .await
+        .unwrap();
+        let update = |title: String| {
+            update_note(
+                app.state(),
+                serde_json::from_value(json!({ "id": "n1", "board_id": "b1", "title": title }))
+                    .unwrap(),
+            )
+        };
+
+        assert_eq!(
+            update("   ".into()).await.unwrap_err(),
+            "O título da nota não pode ser vazio."
+        );
+        assert_eq!(
+            update("x".repeat(201)).await.unwrap_err(),
+            "Título da nota too long (max 200 characters)"
+        );
+        let title = sqlx::query_scalar::<_, String>("SELECT title FROM notes WHERE id = 'n1'")
+            .fetch_one(&*pool)
+            .await
+            .unwrap();
+        assert_eq!(title, "Ata");
+
+        update(format!(" {} ", "x".repeat(200))).await.unwrap();
+        let title = sqlx::query_scalar::<_, String>("SELECT title FROM notes WHERE id = 'n1'")
+            .fetch_one(&*pool)
+            .await
+            .unwrap();
+        assert_eq!(title, "x".repeat(200));
+    }
+
+    #[tokio::test]
+    async fn upload_rejects_files_over_the_size_limit() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        let dir = std::env::temp_dir().join(format!("modulo-upload-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("video.zip");
+        File::create(&source)
+            .unwrap()
+            .set_len(30 * 1024 * 1024)
+            .unwrap();
+
+        let response = upload_image(
+            app.handle().clone(),
+            app.state(),
+            "k1".into(),
+            "b1".into(),
+            source.to_string_lossy().into_owned(),
+        )
+        .await
+        .unwrap();
+
+        assert!(!response.success);
+        assert_eq!(
+            response.error.as_deref(),
+            Some("File is too large (31457280 bytes, limit is 26214400 bytes)")
+        );
+        let rows = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM kanban_attachments")
+            .fetch_one(&*pool)
+            .await
+            .unwrap();
+        assert_eq!(rows, 0);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn render_markdown_keeps_task_lists_and_strips_scripts() {
+        let html = render_markdown("- [x] feito\n- [ ] pendente".into());
+        assert_eq!(html.matches("<input").count(), 2);
+        assert_eq!(html.matches("type=\"checkbox\"").count(), 2);
+        assert_eq!(html.matches("disabled=\"\"").count(), 2);
+        assert_eq!(html.matches("checked").count(), 1);
+
+        let html = render_markdown("<script>alert(1)</script>texto".into());
+        assert!(!html.contains("<script") && !html.contains("alert(1)"));
+        assert!(html.contains("texto"));
+
+        let html = render_markdown("<img src=\"x.png\" onerror=\"alert(1)\">".into());
+        assert!(html.contains("<img"));
+        assert!(!html.contains("onerror"));
+
+        let html = render_markdown("[clique](javascript:alert(1))".into());
+        assert!(!html.contains("javascript:"));
+
+        let html = render_markdown("<input type=\"text\" value=\"x\" onfocus=\"alert(1)\">".into());
+        assert!(html.contains("type=\"checkbox\""));
+        assert!(!html.contains("text") && !html.contains("onfocus"));
+    }
+
+    #[tokio::test]
+    async fn card_activity_checks_the_board_and_keeps_the_latest() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_board(&pool, "b2").await;
+        for (id, created_at) in [
+            ("a1", "2024-01-01T00:00:00Z"),
+            ("a2", "2024-01-02T00:00:00Z"),
+            ("a3", "2024-01-03T00:00:00Z"),
+        ] {
+            sqlx::query(
+                "INSERT INTO kanban_activity (id, board_id, entity_type, entity_id, activity_type, title, created_at) VALUES (?, 'b1', 'card', 'k1', 'card_moved', 'Card', ?)",
+            )
+            .bind(id)
+            .bind(created_at)
+            .execute(&*pool)
+            .await
+            .unwrap();
+        }
+        let timeline = |board_id: &str, limit: Option<i64>| {
+            get_card_activity(app.state(), "k1".into(), board_id.into(), limit)
+        };
+
+        let latest = timeline("b1", Some(2)).await.unwrap();
+        let ids: Vec<&str> = latest.iter().map(|entry| entry.id.as_str()).collect();
+        assert_eq!(ids, ["a2", "a3"]);
+        assert_eq!(latest[0].board_name, "Quadro b1");
 
-#[tauri::command]
-#[allow(dead_code)]
-async fn restore_attachment_version(
-    pool: State<'_, DbPool>,
-    args: ManageAttachmentVersionArgs,
-) -> Result<Value, String> {
-    let ManageAttachmentVersionArgs {
-        board_id,
-        card_id,
-        attachment_id,
-        target_version,
-    } = args;
+        assert_eq!(
+            timeline("b2", None).await.unwrap_err(),
+            "O cartão não pertence ao quadro informado."
+        );
 
-    let mut tx = pool
-        .begin()
+        let index = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'index' AND name = 'idx_activity_entity'",
+        )
+        .fetch_one(&*pool)
         .await
-        .map_err(|e| format!("Failed to begin transaction: {e}"))?;
-
-    let version = match target_version {
-        Some(version) => version,
-        None => sqlx::query_scalar::<_, Option<i64>>(
-            "SELECT MAX(version) FROM kanban_attachments WHERE id = ? AND board_id = ? AND card_id = ?",
+        .unwrap();
+        assert_eq!(index, 1);
+    }
+
+    #[tokio::test]
+    async fn missing_columns_report_not_found_on_update_and_delete() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_board(&pool, "b1").await;
+
+        let updated = update_column(
+            app.handle().clone(),
+            app.state(),
+            UpdateColumnArgs {
+                id: "nope".into(),
+                board_id: "b1".into(),
+                title: Some("Novo".into()),
+                color: None,
+                icon: None,
+                is_enabled: None,
+            },
         )
-        .bind(&attachment_id)
-        .bind(&board_id)
-        .bind(&card_id)
-        .fetch_one(&mut *tx)
         .await
-        .map_err(|e| format!("Failed to load attachment versions: {e}"))?
-        .ok_or_else(|| "Attachment not found".to_string())?,
-    };
-
-    let attachment = sqlx::query(
-        "SELECT id, card_id, board_id, version, filename, original_name, mime_type, size_bytes, checksum, storage_path, thumbnail_path, created_at, updated_at FROM kanban_attachments WHERE id = ? AND board_id = ? AND card_id = ? AND version = ?",
-    )
-    .bind(&attachment_id)
-    .bind(&board_id)
-    .bind(&card_id)
-    .bind(version)
-    .map(|row: SqliteRow| AttachmentRecord::from_row(row))
-    .fetch_optional(&mut *tx)
-    .await
-    .map_err(|e| format!("Failed to load attachment version: {e}"))?
-    .ok_or_else(|| "Attachment version not found".to_string())?
-    .map_err(|e| format!("Failed to parse attachment row: {e}"))?;
+        .unwrap_err();
+        let deleted = delete_column(
+            app.handle().clone(),
+            app.state(),
+            "nope".into(),
+            "b1".into(),
+        )
+        .await
+        .unwrap_err();
 
-    let attachment_clone = attachment.clone();
+        for error in [updated, deleted] {
+            assert_eq!(serde_json::to_value(&error).unwrap()["code"], "not_found");
+        }
+    }
 
-    let existing_attachments: Option<String> =
-        sqlx::query_scalar("SELECT attachments FROM kanban_cards WHERE id = ? AND board_id = ?")
-            .bind(&card_id)
-            .bind(&board_id)
-            .fetch_one(&mut *tx)
-            .await
-            .map_err(|e| format!("Failed to fetch existing attachments: {e}"))?;
+    #[test]
+    fn command_errors_serialize_code_and_message() {
+        let cases = [
+            (
+                CommandError::NotFound("Cartão não encontrado.".into()),
+                "not_found",
+            ),
+            (
+                CommandError::Validation("Título vazio.".into()),
+                "validation",
+            ),
+            (
+                CommandError::Conflict("Posição ocupada.".into()),
+                "conflict",
+            ),
+            (CommandError::Database("Falha no banco.".into()), "database"),
+            (CommandError::Io("Falha no disco.".into()), "io"),
+        ];
+        for (error, code) in cases {
+            let message = error.to_string();
+            assert_eq!(
+                serde_json::to_value(&error).unwrap(),
+                json!({ "code": code, "message": message })
+            );
+        }
 
-    let mut attachments_vec: Vec<String> = existing_attachments
-        .as_deref()
-        .and_then(|json_str| serde_json::from_str(json_str).ok())
-        .unwrap_or_default();
-    if !attachments_vec.contains(&attachment.storage_path) {
-        attachments_vec.push(attachment.storage_path.clone());
+        let row_not_found = CommandError::from(sqlx::Error::RowNotFound);
+        assert_eq!(
+            serde_json::to_value(&row_not_found).unwrap()["code"],
+            "not_found"
+        );
     }
 
-    let attachments_json = serde_json::to_string(&attachments_vec)
-        .map_err(|e| format!("Failed to serialize attachments: {e}"))?;
+    #[tokio::test]
+    async fn card_commands_map_errors_to_specific_variants() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        let create =
+            |id: &'static str, column_id: &'static str, priority: &'static str, position| {
+                create_card(
+                    app.handle().clone(),
+                    app.state(),
+                    id.into(),
+                    "b1".into(),
+                    column_id.into(),
+                    "Novo".into(),
+                    None,
+                    position,
+                    priority.into(),
+                    None,
+                    None,
+                    None,
+                )
+            };
 
-    sqlx::query(
-        "UPDATE kanban_cards SET attachments = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ? AND board_id = ?",
-    )
-    .bind(&attachments_json)
-    .bind(&card_id)
-    .bind(&board_id)
-    .execute(&mut *tx)
-    .await
-    .map_err(|e| format!("Failed to update card attachments: {e}"))?;
+        assert!(matches!(
+            create("k2", "c1", "urgent", 1).await,
+            Err(CommandError::Validation(_))
+        ));
+        assert!(matches!(
+            create("k2", "nope", "low", 0).await,
+            Err(CommandError::NotFound(message)) if message == "Coluna não encontrada."
+        ));
+        assert!(matches!(
+            create("k1", "c1", "low", 1).await,
+            Err(CommandError::Database(_))
+        ));
 
-    tx.commit()
+        let missing = drag_move_card(
+            app.handle().clone(),
+            app.state(),
+            DragMoveArgs {
+                board_id: "b1".into(),
+                card_id: "nope".into(),
+                from_column_id: "c1".into(),
+                to_column_id: "c1".into(),
+                target_index: 0,
+                priority: None,
+                force: None,
+            },
+        )
+        .await;
+        assert!(matches!(missing, Err(CommandError::NotFound(_))));
+
+        let bulk = move_cards_bulk(
+            app.handle().clone(),
+            app.state(),
+            "b1".into(),
+            vec!["k1".into(), "k1".into()],
+            "c1".into(),
+            0,
+        )
+        .await;
+        assert!(matches!(bulk, Err(CommandError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn merge_tags_retags_and_touches_affected_cards() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_card(&pool, "b1", "c1", "k2", "Outro", 1).await;
+        seed_tag(&pool, "b1", "t1", "Bug", &["k1"]).await;
+        seed_tag(&pool, "b1", "t2", "Defeito", &[]).await;
+        sqlx::query("UPDATE kanban_cards SET updated_at = '2024-01-01T00:00:00.000Z'")
+            .execute(&*pool)
+            .await
+            .unwrap();
+
+        let retagged = merge_tags(
+            app.handle().clone(),
+            app.state(),
+            "b1".into(),
+            vec!["t1".into()],
+            "t2".into(),
+        )
         .await
-        .map_err(|e| format!("Failed to commit transaction: {e}"))?;
+        .unwrap();
+        assert_eq!(retagged, 1);
 
-    Ok(attachment_clone.into_json())
-}
+        let links: Vec<(String, String)> =
+            sqlx::query_as("SELECT card_id, tag_id FROM kanban_card_tags")
+                .fetch_all(&*pool)
+                .await
+                .unwrap();
+        assert_eq!(links, vec![("k1".to_string(), "t2".to_string())]);
 
-#[tauri::command]
-#[allow(dead_code)]
-async fn delete_attachment_version(
-    app: AppHandle,
-    pool: State<'_, DbPool>,
-    args: ManageAttachmentVersionArgs,
-) -> Result<(), String> {
-    let ManageAttachmentVersionArgs {
-        board_id,
-        card_id,
-        attachment_id,
-        target_version,
-    } = args;
+        let stamps: Vec<(String, String)> =
+            sqlx::query_as("SELECT id, updated_at FROM kanban_cards ORDER BY id")
+                .fetch_all(&*pool)
+                .await
+                .unwrap();
+        assert_ne!(stamps[0].1, "2024-01-01T00:00:00.000Z");
+        assert_eq!(stamps[1].1, "2024-01-01T00:00:00.000Z");
+    }
 
-    let app_data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to resolve app data directory: {e}"))?;
+    #[tokio::test]
+    async fn touch_last_board_requires_an_existing_board() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
 
-    let mut tx = pool
-        .begin()
+        assert_eq!(
+            touch_last_board(app.handle().clone(), app.state(), "nope".into()).await,
+            Err("Quadro não encontrado.".to_string())
+        );
+        assert!(
+            touch_last_board(app.handle().clone(), app.state(), "  ".into())
+                .await
+                .is_err()
+        );
+
+        touch_last_board(app.handle().clone(), app.state(), " b1 ".into())
+            .await
+            .unwrap();
+        let preferences = load_preferences(app.handle().clone()).await.unwrap();
+        assert_eq!(preferences.last_board_id.as_deref(), Some("b1"));
+    }
+
+    #[tokio::test]
+    async fn set_all_subtasks_completed_settles_a_mixed_card() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        for (id, position, is_completed) in [("s1", 0, 1), ("s2", 1, 0), ("s3", 2, 0)] {
+            sqlx::query(
+                "INSERT INTO kanban_subtasks (id, board_id, card_id, title, position, is_completed) VALUES (?, 'b1', 'k1', 'Passo', ?, ?)",
+            )
+            .bind(id)
+            .bind(position)
+            .bind(is_completed)
+            .execute(&*pool)
+            .await
+            .unwrap();
+        }
+        let db: &DbPool = &pool;
+        let progress = move || async move {
+            let (completed, total) = sqlx::query_as::<_, (i64, i64)>(
+                "SELECT COALESCE(SUM(is_completed), 0), COUNT(*) FROM kanban_subtasks WHERE card_id = 'k1'",
+            )
+            .fetch_one(db)
+            .await
+            .unwrap();
+            (completed, total, completion_percent(completed, total))
+        };
+
+        let before = progress().await;
+        let completed = set_all_subtasks_completed(app.state(), "k1".into(), "b1".into(), true)
+            .await
+            .unwrap();
+        let after_complete = progress().await;
+        let reset = set_all_subtasks_completed(app.state(), "k1".into(), "b1".into(), false)
+            .await
+            .unwrap();
+        let after_reset = progress().await;
+
+        assert_eq!(before, (1, 3, 33));
+        assert_eq!(completed, 2);
+        assert_eq!(after_complete, (3, 3, 100));
+        assert_eq!(reset, 3);
+        assert_eq!(after_reset, (0, 3, 0));
+    }
+
+    #[tokio::test]
+    async fn card_tags_follow_the_board_tag_order() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_tag(&pool, "b1", "t1", "Bug", &["k1"]).await;
+        seed_tag(&pool, "b1", "t2", "UX", &["k1"]).await;
+
+        reorder_tags(
+            app.handle().clone(),
+            app.state(),
+            "b1".into(),
+            vec!["t2".into(), "t1".into()],
+        )
         .await
-        .map_err(|e| format!("Failed to begin transaction: {e}"))?;
+        .unwrap();
 
-    let storage_paths_to_check: Vec<String> = if let Some(version) = target_version {
-        sqlx::query_scalar(
-            "SELECT storage_path FROM kanban_attachments WHERE id = ? AND board_id = ? AND card_id = ? AND version = ?",
+        let cards = load_board_cards(&app, "b1").await;
+        let tags: Vec<(&str, i64)> = cards[0]["tags"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|tag| {
+                (
+                    tag["id"].as_str().unwrap(),
+                    tag["position"].as_i64().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(tags, [("t2", 0), ("t1", 1)]);
+    }
+
+    #[tokio::test]
+    async fn every_card_creation_path_logs_activity() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+
+        let quick = quick_add_card(
+            app.handle().clone(),
+            app.state(),
+            "b1".into(),
+            "Rápido".into(),
+            None,
         )
-        .bind(&attachment_id)
-        .bind(&board_id)
-        .bind(&card_id)
-        .bind(version)
-        .fetch_all(&mut *tx)
         .await
-        .map_err(|e| format!("Failed to fetch attachment storage path: {e}"))?
-    } else {
-        sqlx::query_scalar(
-            "SELECT storage_path FROM kanban_attachments WHERE id = ? AND board_id = ? AND card_id = ?",
+        .unwrap();
+        let copy = copy_card(
+            app.handle().clone(),
+            app.state(),
+            "k1".into(),
+            "b1".into(),
+            "c1".into(),
+            0,
         )
-        .bind(&attachment_id)
-        .bind(&board_id)
-        .bind(&card_id)
-        .fetch_all(&mut *tx)
         .await
-        .map_err(|e| format!("Failed to fetch attachment storage paths: {e}"))?
-    };
+        .unwrap();
 
-    if storage_paths_to_check.is_empty() {
-        return Err("Attachment version not found".to_string());
+        for card_id in [quick["id"].as_str().unwrap(), copy["id"].as_str().unwrap()] {
+            let activity = get_card_activity(app.state(), card_id.into(), "b1".into(), None)
+                .await
+                .unwrap();
+            let types: Vec<&str> = activity
+                .iter()
+                .map(|entry| entry.activity_type.as_str())
+                .collect();
+            assert_eq!(types, ["card_created"]);
+        }
     }
 
-    let delete_query = if let Some(version) = target_version {
-        sqlx::query(
-            "DELETE FROM kanban_attachments WHERE id = ? AND board_id = ? AND card_id = ? AND version = ?",
+    #[tokio::test]
+    async fn drag_and_bulk_moves_into_done_complete_subtasks() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_card(&pool, "b1", "c1", "k2", "Dois", 1).await;
+        seed_column(&pool, "b1", "c2", "Feito", 1).await;
+        sqlx::query("UPDATE kanban_columns SET column_kind = 'done' WHERE id = 'c2'")
+            .execute(&*pool)
+            .await
+            .unwrap();
+        for (id, card_id) in [("s1", "k1"), ("s2", "k2")] {
+            sqlx::query(
+                "INSERT INTO kanban_subtasks (id, board_id, card_id, title, position) VALUES (?, 'b1', ?, 'Passo', 0)",
+            )
+            .bind(id)
+            .bind(card_id)
+            .execute(&*pool)
+            .await
+            .unwrap();
+        }
+        let mut preferences = load_preferences(app.handle().clone()).await.unwrap();
+        preferences.auto_complete_subtasks_on_done = true;
+        save_preferences(app.handle().clone(), preferences)
+            .await
+            .unwrap();
+
+        drag_move_card(
+            app.handle().clone(),
+            app.state(),
+            DragMoveArgs {
+                board_id: "b1".into(),
+                card_id: "k1".into(),
+                from_column_id: "c1".into(),
+                to_column_id: "c2".into(),
+                target_index: 0,
+                priority: None,
+                force: None,
+            },
         )
-        .bind(&attachment_id)
-        .bind(&board_id)
-        .bind(&card_id)
-        .bind(version)
-    } else {
-        sqlx::query("DELETE FROM kanban_attachments WHERE id = ? AND board_id = ? AND card_id = ?")
-            .bind(&attachment_id)
-            .bind(&board_id)
-            .bind(&card_id)
-    };
+        .await
+        .unwrap();
+        move_cards_bulk(
+            app.handle().clone(),
+            app.state(),
+            "b1".into(),
+            vec!["k2".into()],
+            "c2".into(),
+            1,
+        )
+        .await
+        .unwrap();
 
-    delete_query
-        .execute(&mut *tx)
+        let pending = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM kanban_subtasks WHERE is_completed = 0",
+        )
+        .fetch_one(&*pool)
         .await
-        .map_err(|e| format!("Failed to delete attachment version: {e}"))?;
+        .unwrap();
+        assert_eq!(pending, 0);
+    }
+
+    #[tokio::test]
+    async fn bulk_move_clamps_past_archived_cards_like_move_card() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_card(&pool, "b1", "c1", "k2", "Dois", 1).await;
+        seed_column(&pool, "b1", "c2", "Doing", 1).await;
+        seed_card(&pool, "b1", "c2", "k3", "Ativo", 0).await;
+        seed_card(&pool, "b1", "c2", "k4", "Arquivado", 1).await;
+        sqlx::query("UPDATE kanban_cards SET archived_at = '2024-01-01T00:00:00Z' WHERE id = 'k4'")
+            .execute(&*pool)
+            .await
+            .unwrap();
+
+        move_cards_bulk(
+            app.handle().clone(),
+            app.state(),
+            "b1".into(),
+            vec!["k1".into(), "k2".into()],
+            "c2".into(),
+            99,
+        )
+        .await
+        .unwrap();
 
-    let remaining_storage_paths: Vec<String> = sqlx::query_scalar(
-        "SELECT DISTINCT storage_path FROM kanban_attachments WHERE card_id = ? AND board_id = ?",
-    )
-    .bind(&card_id)
-    .bind(&board_id)
-    .fetch_all(&mut *tx)
-    .await
-    .map_err(|e| format!("Failed to fetch remaining attachments: {e}"))?;
+        let order = sqlx::query_scalar::<_, String>(
+            "SELECT id FROM kanban_cards WHERE column_id = 'c2' ORDER BY position ASC",
+        )
+        .fetch_all(&*pool)
+        .await
+        .unwrap();
+        assert_eq!(order, ["k3", "k4", "k1", "k2"]);
+    }
+
+    #[tokio::test]
+    async fn unarchive_card_places_the_card_at_the_requested_position() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        seed_card(&pool, "b1", "c1", "k2", "X", 1).await;
+        seed_card(&pool, "b1", "c1", "k3", "B", 2).await;
+        seed_card(&pool, "b1", "c1", "k4", "Y", 3).await;
+        seed_column(&pool, "b1", "c2", "Doing", 1).await;
+        seed_card(&pool, "b1", "c2", "k5", "C", 0).await;
+        sqlx::query(
+            "UPDATE kanban_cards SET archived_at = '2024-01-01T00:00:00Z' WHERE id IN ('k2', 'k4')",
+        )
+        .execute(&*pool)
+        .await
+        .unwrap();
+
+        unarchive_card(
+            app.handle().clone(),
+            app.state(),
+            "k2".into(),
+            "b1".into(),
+            "c1".into(),
+            0,
+        )
+        .await
+        .unwrap();
+        unarchive_card(
+            app.handle().clone(),
+            app.state(),
+            "k4".into(),
+            "b1".into(),
+            "c2".into(),
+            0,
+        )
+        .await
+        .unwrap();
 
-    let attachments_json = serde_json::to_string(&remaining_storage_paths)
-        .map_err(|e| format!("Failed to serialize attachments: {e}"))?;
+        let positions = |column_id: &'static str| {
+            let db: &DbPool = &pool;
+            async move {
+                sqlx::query_as::<_, (String, i64)>(
+                    "SELECT id, position FROM kanban_cards WHERE column_id = ? ORDER BY position ASC",
+                )
+                .bind(column_id)
+                .fetch_all(db)
+                .await
+                .unwrap()
+            }
+        };
+        assert_eq!(
+            positions("c1").await,
+            [
+                ("k2".to_string(), 0),
+                ("k1".to_string(), 1),
+                ("k3".to_string(), 2)
+            ]
+        );
+        assert_eq!(
+            positions("c2").await,
+            [("k4".to_string(), 0), ("k5".to_string(), 1)]
+        );
+    }
 
-    sqlx::query(
-        "UPDATE kanban_cards SET attachments = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ? AND board_id = ?",
-    )
-    .bind(&attachments_json)
-    .bind(&card_id)
-    .bind(&board_id)
-    .execute(&mut *tx)
-    .await
-    .map_err(|e| format!("Failed to update card attachments: {e}"))?;
+    #[tokio::test]
+    async fn preferences_use_camel_case_and_read_legacy_snake_case_files() {
+        let app = test_app().await;
+        let handle = app.handle().clone();
+        let path = get_preferences_path(&handle).unwrap();
+        fs::write(
+            &path,
+            r#"{"theme":"dark","last_workspace_id":"w1","last_board_id":"b1"}"#,
+        )
+        .unwrap();
 
-    tx.commit()
+        let legacy = load_preferences(handle.clone()).await.unwrap();
+        save_preferences(handle.clone(), legacy).await.unwrap();
+        let stored: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+
+        assert_eq!(stored["lastWorkspaceId"], "w1");
+        assert_eq!(stored["lastBoardId"], "b1");
+        assert!(stored.get("last_board_id").is_none());
+    }
+
+    #[tokio::test]
+    async fn set_note_tags_reports_errors_in_portuguese() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_basic_board(&pool).await;
+        sqlx::query(
+            "INSERT INTO notes (id, board_id, title, content) VALUES ('n1', 'b1', 'Ata', '')",
+        )
+        .execute(&*pool)
         .await
-        .map_err(|e| format!("Failed to commit transaction: {e}"))?;
+        .unwrap();
 
-    for storage_path in storage_paths_to_check {
-        let remaining: i64 =
-            sqlx::query_scalar("SELECT COUNT(*) FROM kanban_attachments WHERE storage_path = ?")
-                .bind(&storage_path)
-                .fetch_one(&*pool)
-                .await
-                .map_err(|e| format!("Failed to check attachment references: {e}"))?;
+        let saved = set_note_tags(
+            app.state(),
+            "n1".into(),
+            "b1".into(),
+            vec![" reunião ".into(), "reunião".into(), "".into()],
+        )
+        .await
+        .unwrap();
+        let too_long = set_note_tags(app.state(), "n1".into(), "b1".into(), vec!["x".repeat(51)])
+            .await
+            .unwrap_err();
+        let missing = set_note_tags(app.state(), "n2".into(), "b1".into(), vec![])
+            .await
+            .unwrap_err();
 
-        if remaining == 0 {
-            let full_file_path = app_data_dir.join(&storage_path);
-            if full_file_path.exists()
-                && let Err(e) = fs::remove_file(&full_file_path)
-            {
-                eprintln!(
-                    "Warning: Failed to delete file {}: {}",
-                    full_file_path.display(),
-                    e
-                );
-            }
-        }
+        assert_eq!(saved["tags"], json!(["reunião"]));
+        assert!(too_long.ends_with("excede o limite de 50 caracteres."));
+        assert_eq!(missing, "Nota não encontrada.");
     }
 
-    Ok(())
+    #[tokio::test]
+    async fn boards_of_archived_workspaces_stay_out_of_listings() {
+        let app = test_app().await;
+        let pool = app.state::<DbPool>();
+        seed_board(&pool, "b1").await;
+        seed_board(&pool, "b2").await;
+        sqlx::query(
+            "INSERT INTO workspaces (id, name, archived_at) VALUES ('w1', 'Antigo', '2024-01-01T00:00:00Z')",
+        )
+        .execute(&*pool)
+        .await
+        .unwrap();
+        sqlx::query("UPDATE kanban_boards SET workspace_id = 'w1' WHERE id = 'b2'")
+            .execute(&*pool)
+            .await
+            .unwrap();
+
+        let visible = load_boards(app.state(), None).await.unwrap();
+        let everything = load_boards(app.state(), Some(true)).await.unwrap();
+        let with_stats = load_boards_with_stats(app.handle().clone(), app.state())
+            .await
+            .unwrap();
+        let badges = get_sidebar_badges(app.handle().clone(), app.state())
+            .await
+            .unwrap();
+        let search = global_search(app.state(), "Quadro".into(), None, None)
+            .await
+            .unwrap();
+
+        let ids = |boards: &[Value]| -> Vec<String> {
+            let mut ids: Vec<String> = boards
+                .iter()
+                .map(|board| board["id"].as_str().unwrap().to_string())
+                .collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(ids(&visible), ["b1"]);
+        assert_eq!(ids(&everything), ["b1", "b2"]);
+        assert_eq!(ids(&with_stats), ["b1"]);
+        assert_eq!(badges.len(), 1);
+        assert_eq!(badges[0]["boardId"], "b1");
+        assert_eq!(search.total_boards, 1);
+        assert_eq!(search.results[0].id, "b1");
+    }
 }
@@ -15,8 +15,22 @@ export function ActivityItem({ activity, onClick }: ActivityItemProps) {
         return 'created'
       case 'card_updated':
         return 'updated'
+      case 'card_moved':
+        return 'moved'
+      case 'card_deleted':
+        return 'deleted'
+      case 'card_archived':
+        return 'archived'
+      case 'card_unarchived':
+        return 'restored'
+      case 'card_priority_changed':
+        return 'changed priority of'
+      case 'card_due_date_changed':
+        return 'changed due date of'
       case 'board_created':
         return 'created board'
+      case 'board_deleted':
+        return 'deleted board'
       default:
         return activity.activity_type
     }
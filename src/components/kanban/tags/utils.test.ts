@@ -23,6 +23,7 @@ describe('tag utils', () => {
       boardId: 'board-1',
       label: 'Important',
       color: '#0ea5e9',
+      position: 0,
       createdAt: new Date().toISOString(),
       updatedAt: new Date().toISOString(),
     }
@@ -41,6 +42,7 @@ describe('tag utils', () => {
       boardId: 'board-1',
       label: 'General',
       color: null,
+      position: 1,
       createdAt: new Date().toISOString(),
       updatedAt: new Date().toISOString(),
     }
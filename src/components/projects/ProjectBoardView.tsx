@@ -10,6 +10,7 @@ import {
 import { Button } from '@/components/ui/button'
 import { Skeleton } from '@/components/ui/skeleton'
 import { useBoards } from '@/services/kanban'
+import { useTouchLastBoard } from '@/services/preferences'
 import { useWorkspaceStore } from '@/store/workspace-store'
 
 export function ProjectBoardView() {
@@ -20,6 +21,7 @@ export function ProjectBoardView() {
   const setSelectedWorkspaceId = useWorkspaceStore(
     state => state.setSelectedWorkspaceId
   )
+  const { mutate: touchLastBoard } = useTouchLastBoard()
 
   const viewParam = searchParams.get('view')
   const viewMode = useMemo<BoardViewMode>(() => {
@@ -40,6 +42,13 @@ export function ProjectBoardView() {
     }
   }, [board, setSelectedWorkspaceId])
 
+  const openedBoardId = board?.id
+  useEffect(() => {
+    if (openedBoardId) {
+      touchLastBoard(openedBoardId)
+    }
+  }, [openedBoardId, touchLastBoard])
+
   useEffect(() => {
     if (viewParam && !isBoardViewMode(viewParam)) {
       setSearchParams(
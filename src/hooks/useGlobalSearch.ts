@@ -11,9 +11,17 @@ export interface SearchResult {
   board_id: string
   board_name: string
   description?: string
+  rank?: number
   url?: string
 }
 
+export interface SearchResponse {
+  results: SearchResult[]
+  totalBoards: number
+  totalCards: number
+  totalNotes: number
+}
+
 export function useGlobalSearch() {
   const [query, setQuery] = useState('')
   const [isOpen, setIsOpen] = useState(false)
@@ -24,10 +32,10 @@ export function useGlobalSearch() {
     queryKey: ['global-search', debouncedQuery],
     queryFn: async () => {
       if (!debouncedQuery.trim()) return []
-      const searchResults = await invoke<SearchResult[]>('global_search', {
+      const response = await invoke<SearchResponse>('global_search', {
         query: debouncedQuery,
       })
-      return searchResults
+      return response.results
     },
     enabled: debouncedQuery.trim().length > 0,
     staleTime: 5 * 60 * 1000, // 5 minutes
@@ -10,6 +10,7 @@ export interface Activity {
   timestamp: string
   entity_id: string
   entity_type: string
+  meta?: Record<string, unknown> | null
 }
 
 export function useRecentActivity(limit = 10) {
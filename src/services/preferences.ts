@@ -91,3 +91,22 @@ export function useSavePreferences() {
     },
   })
 }
+
+export function useTouchLastBoard() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async (boardId: string) => {
+      await invoke('touch_last_board', { boardId })
+      return boardId
+    },
+    onSuccess: () => {
+      queryClient.invalidateQueries({
+        queryKey: preferencesQueryKeys.preferences(),
+      })
+    },
+    onError: (error, boardId) => {
+      logger.warn('Failed to record last opened board', { error, boardId })
+    },
+  })
+}
@@ -20,6 +20,7 @@ export interface Workspace {
   name: string
   color?: string | null
   iconPath?: string | null
+  position: number
   createdAt: string
   updatedAt: string
   archivedAt?: string | null
@@ -51,6 +52,7 @@ export interface KanbanCard {
   priority: KanbanPriority
   dueDate?: string | null
   remindAt?: string | null
+  remindRecurrence?: 'daily' | 'weekly' | null
   attachments?: KanbanAttachment[] | null
   subtasks: KanbanSubtask[]
   tags: KanbanTag[]
@@ -93,6 +95,7 @@ export interface KanbanTag {
   boardId: EntityId
   label: string
   color?: string | null
+  position: number
   createdAt: string
   updatedAt: string
 }
@@ -107,6 +110,12 @@ export interface KanbanActivity {
   createdAt: string
 }
 
+// Rejection payload of card and column commands
+export interface CommandError {
+  code: 'not_found' | 'validation' | 'conflict' | 'database' | 'io'
+  message: string
+}
+
 /**
  * Canonical SQLite schema used to bootstrap the local database.
  * This string can be executed via the Tauri SQLite plugin or a custom
@@ -5,6 +5,7 @@ export interface AppPreferences {
   transparencyEnabled?: boolean
   sidebarLayout?: number[]
   lastWorkspaceId?: string | null
+  lastBoardId?: string | null
   // Add new persistent preferences here, e.g.:
   // auto_save: boolean
   // language: string
@@ -15,5 +16,6 @@ export const defaultPreferences: AppPreferences = {
   transparencyEnabled: true,
   sidebarLayout: [15, 65, 20],
   lastWorkspaceId: null,
+  lastBoardId: null,
   // Add defaults for new preferences here
 }